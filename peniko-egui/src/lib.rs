@@ -0,0 +1,304 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Property editors for the styling types of [Peniko] in [egui], such as
+//! [`ui_for_brush`], for use in demos and tools.
+//!
+//! ## Features
+//!
+//! - `gradient` (enabled by default): Enable [`ui_for_gradient`], and editing gradient
+//!   brushes with [`ui_for_brush`].
+//!
+//! [Peniko]: https://crates.io/crates/peniko
+//! [egui]: https://crates.io/crates/egui
+// LINEBENDER LINT SET - lib.rs - v1
+// See https://linebender.org/wiki/canonical-lints/
+// These lints aren't included in Cargo.toml because they
+// shouldn't apply to examples and tests
+#![warn(unused_crate_dependencies)]
+#![warn(clippy::print_stdout, clippy::print_stderr)]
+// END LINEBENDER LINT SET
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+use egui::{ComboBox, DragValue, Response, Ui};
+use peniko::color::{AlphaColor, DynamicColor, LinearSrgb};
+use peniko::{
+    BlendMode, Brush, Color, Compose, Extend, Image, ImageQuality, MinificationQuality, Mix,
+};
+#[cfg(feature = "gradient")]
+use peniko::{Gradient, GradientKind, Point};
+
+/// Shows an editor for a [blend mode](BlendMode).
+///
+/// The returned response is marked as changed if the blend mode was edited.
+pub fn ui_for_blend_mode(ui: &mut Ui, mode: &mut BlendMode) -> Response {
+    let before = *mode;
+    let mut response = ui
+        .horizontal(|ui| {
+            enum_combo(ui, "Mix", &mut mode.mix, &Mix::ALL, |mix| mix.name());
+            enum_combo(ui, "Compose", &mut mode.compose, &Compose::ALL, |compose| {
+                compose.name()
+            });
+        })
+        .response;
+    if *mode != before {
+        response.mark_changed();
+    }
+    response
+}
+
+/// Shows an editor for a [gradient](Gradient).
+///
+/// This edits the extend mode, the position of the gradient and the offsets and
+/// colors of the stops. Colors are edited in sRGB, so a stop is converted to sRGB
+/// when its color is edited.
+///
+/// The returned response is marked as changed if the gradient was edited.
+#[cfg(feature = "gradient")]
+pub fn ui_for_gradient(ui: &mut Ui, gradient: &mut Gradient) -> Response {
+    let mut changed = false;
+    let mut response = ui
+        .vertical(|ui| {
+            changed |= extend_combo(ui, "Extend", &mut gradient.extend);
+            match &mut gradient.kind {
+                GradientKind::Linear { start, end } => {
+                    changed |= point_row(ui, "Start", start);
+                    changed |= point_row(ui, "End", end);
+                }
+                GradientKind::Radial {
+                    start_center,
+                    start_radius,
+                    end_center,
+                    end_radius,
+                } => {
+                    changed |= point_row(ui, "Start center", start_center);
+                    changed |= value_row(ui, "Start radius", start_radius);
+                    changed |= point_row(ui, "End center", end_center);
+                    changed |= value_row(ui, "End radius", end_radius);
+                }
+                GradientKind::Sweep {
+                    center,
+                    start_angle,
+                    end_angle,
+                } => {
+                    changed |= point_row(ui, "Center", center);
+                    changed |= value_row(ui, "Start angle", start_angle);
+                    changed |= value_row(ui, "End angle", end_angle);
+                }
+            }
+            for (index, stop) in gradient.stops.iter_mut().enumerate() {
+                ui.push_id(index, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Stop");
+                        changed |= ui
+                            .add(
+                                DragValue::new(&mut stop.offset)
+                                    .speed(0.01)
+                                    .range(0.0..=1.0),
+                            )
+                            .changed();
+                        changed |= dynamic_color_edit(ui, &mut stop.color);
+                    });
+                });
+            }
+        })
+        .response;
+    if changed {
+        response.mark_changed();
+    }
+    response
+}
+
+/// Shows an editor for a [brush](Brush).
+///
+/// Solid brushes are edited with a color picker, gradients with
+/// [`ui_for_gradient`], and images by their sampling parameters. Procedural brushes
+/// aren't editable.
+///
+/// The returned response is marked as changed if the brush was edited.
+pub fn ui_for_brush(ui: &mut Ui, brush: &mut Brush) -> Response {
+    match brush {
+        Brush::Solid(color) => color_edit(ui, color),
+        Brush::DynamicSolid(color) => {
+            let mut response = ui.horizontal(|ui| dynamic_color_edit(ui, color));
+            if response.inner {
+                response.response.mark_changed();
+            }
+            response.response
+        }
+        #[cfg(feature = "gradient")]
+        Brush::Gradient(gradient) => ui_for_gradient(ui, gradient),
+        #[cfg(not(feature = "gradient"))]
+        Brush::Gradient(_) => ui.label("Gradient"),
+        Brush::Image(image) => image_ui(ui, image),
+        Brush::Procedural(_) => ui.label("Procedural brush"),
+    }
+}
+
+/// Shows an editor for the sampling parameters of an image.
+fn image_ui(ui: &mut Ui, image: &mut Image) -> Response {
+    let mut changed = false;
+    let mut response = ui
+        .vertical(|ui| {
+            changed |= extend_combo(ui, "Horizontal extend", &mut image.x_extend);
+            changed |= extend_combo(ui, "Vertical extend", &mut image.y_extend);
+            changed |= enum_combo(
+                ui,
+                "Quality",
+                &mut image.quality,
+                &ImageQuality::ALL,
+                |quality| match quality {
+                    ImageQuality::Low => "Low",
+                    ImageQuality::Medium => "Medium",
+                    ImageQuality::High => "High",
+                },
+            );
+            changed |= enum_combo(
+                ui,
+                "Minification",
+                &mut image.minification,
+                &MinificationQuality::ALL,
+                |minification| match minification {
+                    MinificationQuality::Auto => "Auto",
+                    MinificationQuality::Base => "Base",
+                    MinificationQuality::Mipmap => "Mipmap",
+                    MinificationQuality::Trilinear => "Trilinear",
+                    MinificationQuality::Anisotropic => "Anisotropic",
+                },
+            );
+            ui.horizontal(|ui| {
+                ui.label("Alpha");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut image.alpha)
+                            .speed(0.01)
+                            .range(0.0..=1.0),
+                    )
+                    .changed();
+            });
+            if image.x_extend == Extend::Border || image.y_extend == Extend::Border {
+                ui.horizontal(|ui| {
+                    ui.label("Border color");
+                    changed |= color_edit(ui, &mut image.border_color).changed();
+                });
+            }
+        })
+        .response;
+    if changed {
+        response.mark_changed();
+    }
+    response
+}
+
+/// Shows a combo box for choosing between the given values, returning true if the
+/// value was changed.
+fn enum_combo<T: Copy + PartialEq>(
+    ui: &mut Ui,
+    label: &str,
+    value: &mut T,
+    all: &[T],
+    name: impl Fn(T) -> &'static str,
+) -> bool {
+    let before = *value;
+    ComboBox::new(ui.auto_id_with(label), label)
+        .selected_text(name(*value))
+        .show_ui(ui, |ui| {
+            for &option in all {
+                ui.selectable_value(value, option, name(option));
+            }
+        });
+    *value != before
+}
+
+fn extend_combo(ui: &mut Ui, label: &str, extend: &mut Extend) -> bool {
+    enum_combo(ui, label, extend, &Extend::ALL, |extend| match extend {
+        Extend::Pad => "Pad",
+        Extend::Repeat => "Repeat",
+        Extend::Reflect => "Reflect",
+        Extend::Border => "Border",
+    })
+}
+
+#[cfg(feature = "gradient")]
+fn point_row(ui: &mut Ui, label: &str, point: &mut Point) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let x = ui.add(DragValue::new(&mut point.x).prefix("x: ")).changed();
+        let y = ui.add(DragValue::new(&mut point.y).prefix("y: ")).changed();
+        x || y
+    })
+    .inner
+}
+
+#[cfg(feature = "gradient")]
+fn value_row(ui: &mut Ui, label: &str, value: &mut f32) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(DragValue::new(value).speed(0.01)).changed()
+    })
+    .inner
+}
+
+/// Shows a color picker for an sRGB color.
+fn color_edit(ui: &mut Ui, color: &mut Color) -> Response {
+    // egui's float color pickers work in linear sRGB.
+    let mut linear = color.convert::<LinearSrgb>().components;
+    let response = ui.color_edit_button_rgba_unmultiplied(&mut linear);
+    if response.changed() {
+        *color = AlphaColor::<LinearSrgb>::new(linear).convert();
+    }
+    response
+}
+
+/// Shows a color picker for a color in any color space, returning true if it was
+/// changed. The color is converted to sRGB if it is edited.
+fn dynamic_color_edit(ui: &mut Ui, color: &mut DynamicColor) -> bool {
+    let mut srgb: Color = color.to_alpha_color();
+    let changed = color_edit(ui, &mut srgb).changed();
+    if changed {
+        *color = DynamicColor::from_alpha_color(srgb);
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ui_for_blend_mode, ui_for_brush};
+    use peniko::{BlendMode, Blob, Brush, Color, Image, ImageFormat};
+
+    /// Runs a frame of egui with the given contents, returning whether any of
+    /// the responses were marked as changed.
+    fn run(mut add_contents: impl FnMut(&mut egui::Ui) -> egui::Response) -> bool {
+        let ctx = egui::Context::default();
+        let mut changed = false;
+        let _output = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                changed |= add_contents(ui).changed();
+            });
+        });
+        changed
+    }
+
+    #[test]
+    fn editors_show_without_changes() {
+        let mut mode = BlendMode::default();
+        assert!(!run(|ui| ui_for_blend_mode(ui, &mut mode)));
+        assert_eq!(mode, BlendMode::default());
+
+        let mut brushes = vec![
+            Brush::from(Color::WHITE),
+            Brush::DynamicSolid(peniko::color::DynamicColor::from_alpha_color(Color::BLACK)),
+            Brush::Image(Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1)),
+        ];
+        #[cfg(feature = "gradient")]
+        brushes.push(Brush::Gradient(
+            peniko::Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+                .with_stops([Color::WHITE, Color::BLACK]),
+        ));
+        let expected = brushes.clone();
+        for brush in &mut brushes {
+            assert!(!run(|ui| ui_for_brush(ui, brush)));
+        }
+        assert_eq!(brushes, expected);
+    }
+}
@@ -1,9 +1,12 @@
 // Copyright 2022 the Peniko Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use core::fmt;
+
 /// Defines the color mixing function for a [blend operation](BlendMode).
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Mix {
     /// Default attribute which specifies no blending. The blending formula simply selects the source color.
@@ -52,12 +55,128 @@ pub enum Mix {
     /// `Clip` is the same as `Normal`, but the latter always creates an isolated blend group and the
     /// former can optimize that out.
     Clip = 128,
-    // NOTICE: If a new value is added, be sure to update the bytemuck CheckedBitPattern impl.
+    // NOTICE: If a new value is added, be sure to update the bytemuck CheckedBitPattern impl,
+    // `Mix::ALL`, `Mix::name`, `Mix::pdf_name` and the `u8` conversions.
+}
+
+impl_u8_conversions!(Mix {
+    Normal = 0,
+    Multiply = 1,
+    Screen = 2,
+    Overlay = 3,
+    Darken = 4,
+    Lighten = 5,
+    ColorDodge = 6,
+    ColorBurn = 7,
+    HardLight = 8,
+    SoftLight = 9,
+    Difference = 10,
+    Exclusion = 11,
+    Hue = 12,
+    Saturation = 13,
+    Color = 14,
+    Luminosity = 15,
+    Clip = 128,
+});
+
+impl Mix {
+    /// All color mixing functions, in order of their values.
+    pub const ALL: [Self; 17] = [
+        Self::Normal,
+        Self::Multiply,
+        Self::Screen,
+        Self::Overlay,
+        Self::Darken,
+        Self::Lighten,
+        Self::ColorDodge,
+        Self::ColorBurn,
+        Self::HardLight,
+        Self::SoftLight,
+        Self::Difference,
+        Self::Exclusion,
+        Self::Hue,
+        Self::Saturation,
+        Self::Color,
+        Self::Luminosity,
+        Self::Clip,
+    ];
+
+    /// Returns the name of the mixing function.
+    ///
+    /// This is the name used by the CSS [`mix-blend-mode`] property, apart from
+    /// [`Clip`](Self::Clip) which is specific to this crate.
+    ///
+    /// [`mix-blend-mode`]: https://drafts.fxtf.org/compositing/#mix-blend-mode
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Multiply => "multiply",
+            Self::Screen => "screen",
+            Self::Overlay => "overlay",
+            Self::Darken => "darken",
+            Self::Lighten => "lighten",
+            Self::ColorDodge => "color-dodge",
+            Self::ColorBurn => "color-burn",
+            Self::HardLight => "hard-light",
+            Self::SoftLight => "soft-light",
+            Self::Difference => "difference",
+            Self::Exclusion => "exclusion",
+            Self::Hue => "hue",
+            Self::Saturation => "saturation",
+            Self::Color => "color",
+            Self::Luminosity => "luminosity",
+            Self::Clip => "clip",
+        }
+    }
+
+    /// Returns the name of the mixing function in PDF, as used by the `BM` entry of
+    /// graphics state parameter dictionaries in [ISO 32000].
+    ///
+    /// This is the variant name, such as `"ColorDodge"`. [`Clip`](Self::Clip) is
+    /// `"Normal"`, as it only differs in whether a layer is isolated.
+    ///
+    /// [ISO 32000]: https://pdfa.org/resource/iso-32000-2/
+    #[must_use]
+    pub const fn pdf_name(self) -> &'static str {
+        match self {
+            Self::Normal | Self::Clip => "Normal",
+            Self::Multiply => "Multiply",
+            Self::Screen => "Screen",
+            Self::Overlay => "Overlay",
+            Self::Darken => "Darken",
+            Self::Lighten => "Lighten",
+            Self::ColorDodge => "ColorDodge",
+            Self::ColorBurn => "ColorBurn",
+            Self::HardLight => "HardLight",
+            Self::SoftLight => "SoftLight",
+            Self::Difference => "Difference",
+            Self::Exclusion => "Exclusion",
+            Self::Hue => "Hue",
+            Self::Saturation => "Saturation",
+            Self::Color => "Color",
+            Self::Luminosity => "Luminosity",
+        }
+    }
+
+    /// Returns the mixing function with the given [PDF name](Self::pdf_name), or
+    /// `None` if there is none.
+    ///
+    /// The deprecated name `"Compatible"` is accepted as [`Normal`](Self::Normal).
+    #[must_use]
+    pub fn from_pdf_name(name: &str) -> Option<Self> {
+        if name == "Compatible" {
+            return Some(Self::Normal);
+        }
+        // `Clip` has the same PDF name as `Normal`, and comes after it.
+        Self::ALL.into_iter().find(|mix| mix.pdf_name() == name)
+    }
 }
 
 /// Defines the layer composition function for a [blend operation](BlendMode).
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Compose {
     /// No regions are enabled.
@@ -91,12 +210,74 @@ pub enum Compose {
     /// Allows two elements to cross fade by changing their opacities from 0 to 1 on one
     /// element and 1 to 0 on the other element.
     PlusLighter = 13,
-    // NOTICE: If a new value is added, be sure to modify `MAX_VALUE` in the bytemuck impl.
+    // NOTICE: If a new value is added, be sure to modify `MAX_VALUE` in the bytemuck impl,
+    // `Compose::ALL`, `Compose::name` and the `u8` conversions.
+}
+
+impl_u8_conversions!(Compose {
+    Clear = 0,
+    Copy = 1,
+    Dest = 2,
+    SrcOver = 3,
+    DestOver = 4,
+    SrcIn = 5,
+    DestIn = 6,
+    SrcOut = 7,
+    DestOut = 8,
+    SrcAtop = 9,
+    DestAtop = 10,
+    Xor = 11,
+    Plus = 12,
+    PlusLighter = 13,
+});
+
+impl Compose {
+    /// All composition functions, in order of their values.
+    pub const ALL: [Self; 14] = [
+        Self::Clear,
+        Self::Copy,
+        Self::Dest,
+        Self::SrcOver,
+        Self::DestOver,
+        Self::SrcIn,
+        Self::DestIn,
+        Self::SrcOut,
+        Self::DestOut,
+        Self::SrcAtop,
+        Self::DestAtop,
+        Self::Xor,
+        Self::Plus,
+        Self::PlusLighter,
+    ];
+
+    /// Returns the name of the composition function.
+    ///
+    /// This is the name of the variant in kebab case, such as `"src-over"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Clear => "clear",
+            Self::Copy => "copy",
+            Self::Dest => "dest",
+            Self::SrcOver => "src-over",
+            Self::DestOver => "dest-over",
+            Self::SrcIn => "src-in",
+            Self::DestIn => "dest-in",
+            Self::SrcOut => "src-out",
+            Self::DestOut => "dest-out",
+            Self::SrcAtop => "src-atop",
+            Self::DestAtop => "dest-atop",
+            Self::Xor => "xor",
+            Self::Plus => "plus",
+            Self::PlusLighter => "plus-lighter",
+        }
+    }
 }
 
 /// Blend mode consisting of [color mixing](Mix) and [composition functions](Compose).
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct BlendMode {
     /// The color mixing function.
     pub mix: Mix,
@@ -105,12 +286,174 @@ pub struct BlendMode {
 }
 
 impl BlendMode {
+    // Color mixing functions, composited with source-over.
+
+    /// [`Mix::Normal`] with [`Compose::SrcOver`].
+    pub const NORMAL: Self = Self::new(Mix::Normal, Compose::SrcOver);
+
+    /// [`Mix::Multiply`] with [`Compose::SrcOver`].
+    pub const MULTIPLY: Self = Self::new(Mix::Multiply, Compose::SrcOver);
+
+    /// [`Mix::Screen`] with [`Compose::SrcOver`].
+    pub const SCREEN: Self = Self::new(Mix::Screen, Compose::SrcOver);
+
+    /// [`Mix::Overlay`] with [`Compose::SrcOver`].
+    pub const OVERLAY: Self = Self::new(Mix::Overlay, Compose::SrcOver);
+
+    /// [`Mix::Darken`] with [`Compose::SrcOver`].
+    pub const DARKEN: Self = Self::new(Mix::Darken, Compose::SrcOver);
+
+    /// [`Mix::Lighten`] with [`Compose::SrcOver`].
+    pub const LIGHTEN: Self = Self::new(Mix::Lighten, Compose::SrcOver);
+
+    /// [`Mix::ColorDodge`] with [`Compose::SrcOver`].
+    pub const COLOR_DODGE: Self = Self::new(Mix::ColorDodge, Compose::SrcOver);
+
+    /// [`Mix::ColorBurn`] with [`Compose::SrcOver`].
+    pub const COLOR_BURN: Self = Self::new(Mix::ColorBurn, Compose::SrcOver);
+
+    /// [`Mix::HardLight`] with [`Compose::SrcOver`].
+    pub const HARD_LIGHT: Self = Self::new(Mix::HardLight, Compose::SrcOver);
+
+    /// [`Mix::SoftLight`] with [`Compose::SrcOver`].
+    pub const SOFT_LIGHT: Self = Self::new(Mix::SoftLight, Compose::SrcOver);
+
+    /// [`Mix::Difference`] with [`Compose::SrcOver`].
+    pub const DIFFERENCE: Self = Self::new(Mix::Difference, Compose::SrcOver);
+
+    /// [`Mix::Exclusion`] with [`Compose::SrcOver`].
+    pub const EXCLUSION: Self = Self::new(Mix::Exclusion, Compose::SrcOver);
+
+    /// [`Mix::Hue`] with [`Compose::SrcOver`].
+    pub const HUE: Self = Self::new(Mix::Hue, Compose::SrcOver);
+
+    /// [`Mix::Saturation`] with [`Compose::SrcOver`].
+    pub const SATURATION: Self = Self::new(Mix::Saturation, Compose::SrcOver);
+
+    /// [`Mix::Color`] with [`Compose::SrcOver`].
+    pub const COLOR: Self = Self::new(Mix::Color, Compose::SrcOver);
+
+    /// [`Mix::Luminosity`] with [`Compose::SrcOver`].
+    pub const LUMINOSITY: Self = Self::new(Mix::Luminosity, Compose::SrcOver);
+
+    /// [`Mix::Clip`] with [`Compose::SrcOver`].
+    pub const CLIP: Self = Self::new(Mix::Clip, Compose::SrcOver);
+
+    // Composition functions, without color mixing.
+
+    /// [`Compose::Clear`] with [`Mix::Normal`].
+    pub const CLEAR: Self = Self::new(Mix::Normal, Compose::Clear);
+
+    /// [`Compose::Copy`] with [`Mix::Normal`].
+    pub const COPY: Self = Self::new(Mix::Normal, Compose::Copy);
+
+    /// [`Compose::Dest`] with [`Mix::Normal`].
+    pub const DEST: Self = Self::new(Mix::Normal, Compose::Dest);
+
+    /// [`Compose::SrcOver`] with [`Mix::Normal`].
+    pub const SRC_OVER: Self = Self::new(Mix::Normal, Compose::SrcOver);
+
+    /// [`Compose::DestOver`] with [`Mix::Normal`].
+    pub const DEST_OVER: Self = Self::new(Mix::Normal, Compose::DestOver);
+
+    /// [`Compose::SrcIn`] with [`Mix::Normal`].
+    pub const SRC_IN: Self = Self::new(Mix::Normal, Compose::SrcIn);
+
+    /// [`Compose::DestIn`] with [`Mix::Normal`].
+    pub const DEST_IN: Self = Self::new(Mix::Normal, Compose::DestIn);
+
+    /// [`Compose::SrcOut`] with [`Mix::Normal`].
+    pub const SRC_OUT: Self = Self::new(Mix::Normal, Compose::SrcOut);
+
+    /// [`Compose::DestOut`] with [`Mix::Normal`].
+    pub const DEST_OUT: Self = Self::new(Mix::Normal, Compose::DestOut);
+
+    /// [`Compose::SrcAtop`] with [`Mix::Normal`].
+    pub const SRC_ATOP: Self = Self::new(Mix::Normal, Compose::SrcAtop);
+
+    /// [`Compose::DestAtop`] with [`Mix::Normal`].
+    pub const DEST_ATOP: Self = Self::new(Mix::Normal, Compose::DestAtop);
+
+    /// [`Compose::Xor`] with [`Mix::Normal`].
+    pub const XOR: Self = Self::new(Mix::Normal, Compose::Xor);
+
+    /// [`Compose::Plus`] with [`Mix::Normal`].
+    pub const PLUS: Self = Self::new(Mix::Normal, Compose::Plus);
+
+    /// [`Compose::PlusLighter`] with [`Mix::Normal`].
+    pub const PLUS_LIGHTER: Self = Self::new(Mix::Normal, Compose::PlusLighter);
+
     /// Creates a new blend mode from color mixing and layer composition
     /// functions.
     #[must_use]
     pub const fn new(mix: Mix, compose: Compose) -> Self {
         Self { mix, compose }
     }
+
+    /// Returns the PDF name of the blend mode, or `None` if it can't be expressed in
+    /// PDF.
+    ///
+    /// PDF always composites with source-over, so this is the
+    /// [PDF name of the mixing function](Mix::pdf_name) for blend modes with
+    /// [`Compose::SrcOver`], and `None` for the other composition functions.
+    #[must_use]
+    pub const fn pdf_name(self) -> Option<&'static str> {
+        match self.compose {
+            Compose::SrcOver => Some(self.mix.pdf_name()),
+            _ => None,
+        }
+    }
+
+    /// Returns the blend mode packed into a single byte, as a stable encoding for
+    /// compact display lists and GPU uniforms.
+    ///
+    /// The byte holds the [`u8` representations](Mix::to_u8) of the functions in two
+    /// 4-bit fields:
+    ///
+    /// | Bits   | Field                                                   |
+    /// |--------|---------------------------------------------------------|
+    /// | `0..4` | The composition function.                               |
+    /// | `4..8` | The mixing function, apart from [`Mix::Clip`].          |
+    ///
+    /// As [`Mix::Clip`] doesn't fit in 4 bits, it is marked by the otherwise unused
+    /// composition value `0xF`, with the composition function moved to bits `4..8`.
+    /// So [`BlendMode::default`] packs as `0x3F`.
+    #[must_use]
+    pub const fn to_packed_u8(self) -> u8 {
+        match self.mix {
+            Mix::Clip => self.compose.to_u8() << 4 | 0xF,
+            mix => mix.to_u8() << 4 | self.compose.to_u8(),
+        }
+    }
+
+    /// Returns the blend mode packed with [`to_packed_u8`](Self::to_packed_u8), or
+    /// `None` if the byte isn't a valid encoding.
+    #[must_use]
+    pub const fn from_packed_u8(packed: u8) -> Option<Self> {
+        let (high, low) = (packed >> 4, packed & 0xF);
+        let (mix, compose) = if low == 0xF {
+            (Some(Mix::Clip), Compose::from_u8(high))
+        } else {
+            (Mix::from_u8(high), Compose::from_u8(low))
+        };
+        match (mix, compose) {
+            (Some(mix), Some(compose)) => Some(Self::new(mix, compose)),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over every combination of [color mixing](Mix) and
+    /// [composition](Compose) functions.
+    ///
+    /// Blend modes are ordered by mixing function and then by composition function,
+    /// following the order of [`Mix::ALL`] and [`Compose::ALL`].
+    pub fn all() -> impl Iterator<Item = Self> + Clone {
+        Mix::ALL.into_iter().flat_map(|mix| {
+            Compose::ALL
+                .into_iter()
+                .map(move |compose| Self::new(mix, compose))
+        })
+    }
 }
 
 impl Default for BlendMode {
@@ -139,3 +482,461 @@ impl From<Compose> for BlendMode {
         }
     }
 }
+
+/// Porter-Duff composition with a constant alpha, for renderers which don't support
+/// [color mixing](Mix).
+///
+/// This is converted from a [`BlendMode`] with [`TryFrom`], which fails for blend
+/// modes with color mixing, so that scene producers can check which blend modes a
+/// limited renderer can draw.
+///
+/// [`Mix::Clip`] is accepted like [`Mix::Normal`], so that the default blend mode
+/// converts. A simple blend doesn't distinguish clip layers from isolated blend
+/// groups, so renderers which optimize clips must check the blend mode instead.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SimpleBlend {
+    /// The layer composition function.
+    pub compose: Compose,
+    /// The alpha multiplier applied to the source before composition.
+    pub alpha: f32,
+}
+
+impl SimpleBlend {
+    /// Creates a new simple blend from a composition function and alpha multiplier.
+    #[must_use]
+    pub const fn new(compose: Compose, alpha: f32) -> Self {
+        Self { compose, alpha }
+    }
+
+    /// Returns the blend mode of the composition function, without the alpha
+    /// multiplier.
+    #[must_use]
+    pub const fn blend_mode(self) -> BlendMode {
+        BlendMode::new(Mix::Normal, self.compose)
+    }
+}
+
+impl Default for SimpleBlend {
+    fn default() -> Self {
+        Self::new(Compose::SrcOver, 1.0)
+    }
+}
+
+impl From<Compose> for SimpleBlend {
+    fn from(compose: Compose) -> Self {
+        Self::new(compose, 1.0)
+    }
+}
+
+/// Error returned when converting a [`BlendMode`] with color mixing into a
+/// [`SimpleBlend`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct UnsupportedMix(pub Mix);
+
+impl fmt::Display for UnsupportedMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "color mixing function `{}` isn't supported by simple blends",
+            self.0.name()
+        )
+    }
+}
+
+impl core::error::Error for UnsupportedMix {}
+
+impl TryFrom<BlendMode> for SimpleBlend {
+    type Error = UnsupportedMix;
+
+    /// Converts a blend mode with [`Mix::Normal`] or [`Mix::Clip`] to a simple blend
+    /// with an alpha of one.
+    fn try_from(mode: BlendMode) -> Result<Self, Self::Error> {
+        match mode.mix {
+            Mix::Normal | Mix::Clip => Ok(mode.compose.into()),
+            mix => Err(UnsupportedMix(mix)),
+        }
+    }
+}
+
+/// A 2D graphics library with a single flat enum of blend modes, for converting
+/// [blend modes](BlendMode) with [`BlendMode::to_backend_name`] and
+/// [`BlendMode::from_backend_name`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+pub enum BlendBackend {
+    /// The `tiny_skia::BlendMode` enum, with names such as `"SourceOver"`.
+    TinySkia,
+    /// The `SkBlendMode` enum of Skia, with names such as `"kSrcOver"`.
+    Skia,
+    /// The `cairo_operator_t` enum of cairo, with names such as `"CAIRO_OPERATOR_OVER"`.
+    Cairo,
+}
+
+/// Error returned when converting between [blend modes](BlendMode) and the blend
+/// modes of a [backend](BlendBackend).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum BackendBlendError {
+    /// The backend has no equivalent of the blend mode, such as a mixing function
+    /// other than [`Mix::Normal`] with a composition function other than
+    /// [`Compose::SrcOver`].
+    Unrepresentable(BlendMode),
+    /// The name isn't a blend mode of the backend.
+    UnknownName,
+    /// The backend blend mode has no equivalent [`BlendMode`], such as `Modulate`.
+    NoEquivalent,
+}
+
+impl fmt::Display for BackendBlendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unrepresentable(mode) => write!(
+                f,
+                "blend mode `{}` with `{}` has no backend equivalent",
+                mode.mix.name(),
+                mode.compose.name()
+            ),
+            Self::UnknownName => f.write_str("unknown backend blend mode"),
+            Self::NoEquivalent => f.write_str("backend blend mode has no peniko equivalent"),
+        }
+    }
+}
+
+impl core::error::Error for BackendBlendError {}
+
+/// Names of the blend modes of each [backend](BlendBackend), indexed by
+/// [`BlendBackend::index`].
+const BACKEND_NAMES: [(BlendMode, [&str; 3]); 28] = [
+    (
+        BlendMode::CLEAR,
+        ["Clear", "kClear", "CAIRO_OPERATOR_CLEAR"],
+    ),
+    (BlendMode::COPY, ["Source", "kSrc", "CAIRO_OPERATOR_SOURCE"]),
+    (
+        BlendMode::DEST,
+        ["Destination", "kDst", "CAIRO_OPERATOR_DEST"],
+    ),
+    (
+        BlendMode::SRC_OVER,
+        ["SourceOver", "kSrcOver", "CAIRO_OPERATOR_OVER"],
+    ),
+    (
+        BlendMode::DEST_OVER,
+        ["DestinationOver", "kDstOver", "CAIRO_OPERATOR_DEST_OVER"],
+    ),
+    (
+        BlendMode::SRC_IN,
+        ["SourceIn", "kSrcIn", "CAIRO_OPERATOR_IN"],
+    ),
+    (
+        BlendMode::DEST_IN,
+        ["DestinationIn", "kDstIn", "CAIRO_OPERATOR_DEST_IN"],
+    ),
+    (
+        BlendMode::SRC_OUT,
+        ["SourceOut", "kSrcOut", "CAIRO_OPERATOR_OUT"],
+    ),
+    (
+        BlendMode::DEST_OUT,
+        ["DestinationOut", "kDstOut", "CAIRO_OPERATOR_DEST_OUT"],
+    ),
+    (
+        BlendMode::SRC_ATOP,
+        ["SourceAtop", "kSrcATop", "CAIRO_OPERATOR_ATOP"],
+    ),
+    (
+        BlendMode::DEST_ATOP,
+        ["DestinationAtop", "kDstATop", "CAIRO_OPERATOR_DEST_ATOP"],
+    ),
+    (BlendMode::XOR, ["Xor", "kXor", "CAIRO_OPERATOR_XOR"]),
+    (BlendMode::PLUS, ["Plus", "kPlus", "CAIRO_OPERATOR_ADD"]),
+    (
+        BlendMode::SCREEN,
+        ["Screen", "kScreen", "CAIRO_OPERATOR_SCREEN"],
+    ),
+    (
+        BlendMode::OVERLAY,
+        ["Overlay", "kOverlay", "CAIRO_OPERATOR_OVERLAY"],
+    ),
+    (
+        BlendMode::DARKEN,
+        ["Darken", "kDarken", "CAIRO_OPERATOR_DARKEN"],
+    ),
+    (
+        BlendMode::LIGHTEN,
+        ["Lighten", "kLighten", "CAIRO_OPERATOR_LIGHTEN"],
+    ),
+    (
+        BlendMode::COLOR_DODGE,
+        ["ColorDodge", "kColorDodge", "CAIRO_OPERATOR_COLOR_DODGE"],
+    ),
+    (
+        BlendMode::COLOR_BURN,
+        ["ColorBurn", "kColorBurn", "CAIRO_OPERATOR_COLOR_BURN"],
+    ),
+    (
+        BlendMode::HARD_LIGHT,
+        ["HardLight", "kHardLight", "CAIRO_OPERATOR_HARD_LIGHT"],
+    ),
+    (
+        BlendMode::SOFT_LIGHT,
+        ["SoftLight", "kSoftLight", "CAIRO_OPERATOR_SOFT_LIGHT"],
+    ),
+    (
+        BlendMode::DIFFERENCE,
+        ["Difference", "kDifference", "CAIRO_OPERATOR_DIFFERENCE"],
+    ),
+    (
+        BlendMode::EXCLUSION,
+        ["Exclusion", "kExclusion", "CAIRO_OPERATOR_EXCLUSION"],
+    ),
+    (
+        BlendMode::MULTIPLY,
+        ["Multiply", "kMultiply", "CAIRO_OPERATOR_MULTIPLY"],
+    ),
+    (BlendMode::HUE, ["Hue", "kHue", "CAIRO_OPERATOR_HSL_HUE"]),
+    (
+        BlendMode::SATURATION,
+        ["Saturation", "kSaturation", "CAIRO_OPERATOR_HSL_SATURATION"],
+    ),
+    (
+        BlendMode::COLOR,
+        ["Color", "kColor", "CAIRO_OPERATOR_HSL_COLOR"],
+    ),
+    (
+        BlendMode::LUMINOSITY,
+        ["Luminosity", "kLuminosity", "CAIRO_OPERATOR_HSL_LUMINOSITY"],
+    ),
+];
+
+/// Blend modes of each [backend](BlendBackend) which have no equivalent [`BlendMode`].
+const BACKEND_NO_EQUIVALENT: [&str; 3] = ["Modulate", "kModulate", "CAIRO_OPERATOR_SATURATE"];
+
+impl BlendBackend {
+    const fn index(self) -> usize {
+        match self {
+            Self::TinySkia => 0,
+            Self::Skia => 1,
+            Self::Cairo => 2,
+        }
+    }
+}
+
+impl BlendMode {
+    /// Returns the name of the equivalent blend mode of the backend, such as
+    /// `"kSrcOver"` for [`BlendMode::SRC_OVER`] in Skia.
+    ///
+    /// The backends support every [composition function](Compose) with
+    /// [`Mix::Normal`], and every mixing function with [`Compose::SrcOver`].
+    /// [`Mix::Clip`] is treated as [`Mix::Normal`], as it only differs in whether a
+    /// layer is isolated.
+    ///
+    /// Each backend clamps the sum of its additive blend mode, which is the
+    /// behavior of [`Compose::PlusLighter`]; both [`Compose::Plus`] and
+    /// [`Compose::PlusLighter`] convert to it, and it converts back to
+    /// [`Compose::Plus`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BackendBlendError::Unrepresentable`] if the backend has no
+    /// equivalent blend mode.
+    pub fn to_backend_name(self, backend: BlendBackend) -> Result<&'static str, BackendBlendError> {
+        let mix = match self.mix {
+            Mix::Clip => Mix::Normal,
+            mix => mix,
+        };
+        let compose = match self.compose {
+            Compose::PlusLighter => Compose::Plus,
+            compose => compose,
+        };
+        let normalized = Self::new(mix, compose);
+        BACKEND_NAMES
+            .iter()
+            .find(|(mode, _)| *mode == normalized)
+            .map(|(_, names)| names[backend.index()])
+            .ok_or(BackendBlendError::Unrepresentable(self))
+    }
+
+    /// Returns the blend mode equivalent to the named blend mode of the backend,
+    /// such as `"CAIRO_OPERATOR_DEST_OVER"` in cairo.
+    ///
+    /// See [`to_backend_name`](Self::to_backend_name) for how the modes correspond.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BackendBlendError::NoEquivalent`] for backend blend modes without an
+    /// equivalent, such as `Modulate` in tiny-skia, and
+    /// [`BackendBlendError::UnknownName`] if the name isn't a blend mode of the
+    /// backend.
+    pub fn from_backend_name(backend: BlendBackend, name: &str) -> Result<Self, BackendBlendError> {
+        let index = backend.index();
+        if BACKEND_NO_EQUIVALENT[index] == name {
+            return Err(BackendBlendError::NoEquivalent);
+        }
+        BACKEND_NAMES
+            .iter()
+            .find(|(_, names)| names[index] == name)
+            .map(|(mode, _)| *mode)
+            .ok_or(BackendBlendError::UnknownName)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BackendBlendError, BlendBackend, BlendMode, Compose, Mix, SimpleBlend, UnsupportedMix,
+    };
+
+    #[test]
+    fn enumerate_blend_modes() {
+        assert_eq!(
+            BlendMode::all().count(),
+            Mix::ALL.len() * Compose::ALL.len()
+        );
+        assert_eq!(BlendMode::all().next(), Some(BlendMode::CLEAR));
+        assert_eq!(
+            BlendMode::all().last(),
+            Some(BlendMode::new(Mix::Clip, Compose::PlusLighter))
+        );
+        // The arrays are ordered by value.
+        assert!(Mix::ALL
+            .windows(2)
+            .all(|pair| (pair[0] as u8) < (pair[1] as u8)));
+        assert!(Compose::ALL
+            .iter()
+            .enumerate()
+            .all(|(ix, compose)| *compose as usize == ix));
+        assert_eq!(Mix::ColorDodge.name(), "color-dodge");
+        assert_eq!(Compose::SrcOver.name(), "src-over");
+    }
+
+    #[test]
+    fn backend_names() {
+        let backends = [
+            BlendBackend::TinySkia,
+            BlendBackend::Skia,
+            BlendBackend::Cairo,
+        ];
+        let mut representable = 0;
+        for mode in BlendMode::all() {
+            for backend in backends {
+                let Ok(name) = mode.to_backend_name(backend) else {
+                    assert_eq!(
+                        mode.to_backend_name(backend),
+                        Err(BackendBlendError::Unrepresentable(mode))
+                    );
+                    continue;
+                };
+                representable += 1;
+                let back = BlendMode::from_backend_name(backend, name).unwrap();
+                assert_eq!(back.to_backend_name(backend), Ok(name));
+                if mode.mix != Mix::Clip && mode.compose != Compose::PlusLighter {
+                    assert_eq!(back, mode);
+                }
+            }
+        }
+        // Every composition function with normal and clip mixing, and the other 15
+        // mixing functions with source-over.
+        assert_eq!(representable, 3 * (2 * Compose::ALL.len() + 15));
+
+        assert_eq!(
+            BlendMode::PLUS_LIGHTER.to_backend_name(BlendBackend::Skia),
+            Ok("kPlus")
+        );
+        assert_eq!(
+            BlendMode::from_backend_name(BlendBackend::Cairo, "CAIRO_OPERATOR_OVER"),
+            Ok(BlendMode::SRC_OVER)
+        );
+        assert_eq!(
+            BlendMode::from_backend_name(BlendBackend::TinySkia, "Modulate"),
+            Err(BackendBlendError::NoEquivalent)
+        );
+        assert_eq!(
+            BlendMode::from_backend_name(BlendBackend::TinySkia, "kSrcOver"),
+            Err(BackendBlendError::UnknownName)
+        );
+        assert_eq!(
+            BlendMode::new(Mix::Multiply, Compose::Xor).to_backend_name(BlendBackend::Cairo),
+            Err(BackendBlendError::Unrepresentable(BlendMode::new(
+                Mix::Multiply,
+                Compose::Xor
+            )))
+        );
+    }
+
+    #[test]
+    fn simple_blend() {
+        assert_eq!(
+            SimpleBlend::try_from(BlendMode::DEST_OUT),
+            Ok(SimpleBlend::new(Compose::DestOut, 1.0))
+        );
+        assert_eq!(
+            SimpleBlend::try_from(BlendMode::default()),
+            Ok(SimpleBlend::default())
+        );
+        assert_eq!(
+            SimpleBlend::try_from(BlendMode::SCREEN),
+            Err(UnsupportedMix(Mix::Screen))
+        );
+        assert_eq!(
+            SimpleBlend::try_from(BlendMode::new(Mix::Clip, Compose::Xor)),
+            Ok(SimpleBlend::new(Compose::Xor, 1.0))
+        );
+        assert_eq!(
+            SimpleBlend::new(Compose::Xor, 0.5).blend_mode(),
+            BlendMode::XOR
+        );
+    }
+
+    #[test]
+    fn pdf_names() {
+        for mix in Mix::ALL {
+            let expected = if mix == Mix::Clip { Mix::Normal } else { mix };
+            assert_eq!(Mix::from_pdf_name(mix.pdf_name()), Some(expected));
+        }
+        assert_eq!(Mix::ColorDodge.pdf_name(), "ColorDodge");
+        assert_eq!(Mix::from_pdf_name("Compatible"), Some(Mix::Normal));
+        assert_eq!(Mix::from_pdf_name("color-dodge"), None);
+        assert_eq!(BlendMode::MULTIPLY.pdf_name(), Some("Multiply"));
+        assert_eq!(BlendMode::default().pdf_name(), Some("Normal"));
+        assert_eq!(BlendMode::XOR.pdf_name(), None);
+    }
+
+    #[test]
+    fn u8_conversions() {
+        for mix in Mix::ALL {
+            assert_eq!(mix.to_u8(), mix as u8);
+            assert_eq!(Mix::from_u8(mix as u8), Some(mix));
+        }
+        for compose in Compose::ALL {
+            assert_eq!(compose.to_u8(), compose as u8);
+            assert_eq!(Compose::from_u8(compose as u8), Some(compose));
+        }
+        assert_eq!(Mix::from_u8(16), None);
+        assert_eq!(Compose::from_u8(14), None);
+        const SRC_OVER: Option<Compose> = Compose::from_u8(3);
+        assert_eq!(SRC_OVER, Some(Compose::SrcOver));
+    }
+
+    #[test]
+    fn packed_blend_mode() {
+        assert_eq!(BlendMode::NORMAL.to_packed_u8(), 0x03);
+        assert_eq!(
+            BlendMode::new(Mix::Luminosity, Compose::Xor).to_packed_u8(),
+            0xFB
+        );
+        assert_eq!(BlendMode::default().to_packed_u8(), 0x3F);
+        let mut packed: Vec<u8> = BlendMode::all().map(BlendMode::to_packed_u8).collect();
+        for (mode, &byte) in BlendMode::all().zip(&packed) {
+            assert_eq!(BlendMode::from_packed_u8(byte), Some(mode));
+        }
+        packed.sort_unstable();
+        packed.dedup();
+        assert_eq!(packed.len(), Mix::ALL.len() * Compose::ALL.len());
+        // Composition values 14 and 15 are invalid, apart from the `Clip` marker.
+        assert_eq!(BlendMode::from_packed_u8(0x0E), None);
+        assert_eq!(BlendMode::from_packed_u8(0xEF), None);
+    }
+}
@@ -12,6 +12,9 @@ use alloc::vec::Vec;
 pub struct Blob<T> {
     data: Arc<dyn AsRef<[T]> + Send + Sync>,
     id: u64,
+    // Shared rather than inline to avoid growing every blob (and so every
+    // brush) by the size and alignment of a `u128`.
+    content_hash: Option<Arc<u128>>,
 }
 
 #[cfg(feature = "serde")]
@@ -39,7 +42,14 @@ where
     {
         let byte_buf: serde_bytes::ByteBuf = serde_bytes::Deserialize::deserialize(des)?;
         let boxed_slice: Box<[u8]> = byte_buf.into_boxed_slice();
-        Ok(Self::new(Arc::new(boxed_slice)))
+        // Only the data is serialized, so the content hash is recomputed to keep
+        // deserialized blobs deduplicable across processes.
+        let content_hash = fnv1a_128(&boxed_slice);
+        Ok(Self {
+            data: Arc::new(boxed_slice),
+            id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            content_hash: Some(Arc::new(content_hash)),
+        })
     }
 }
 
@@ -62,6 +72,7 @@ impl<T> Clone for Blob<T> {
         Self {
             data: Arc::clone(&self.data),
             id: self.id,
+            content_hash: self.content_hash.clone(),
         }
     }
 }
@@ -91,6 +102,38 @@ impl<T> Blob<T> {
         Self {
             data,
             id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            content_hash: None,
+        }
+    }
+
+    /// Creates a new blob from the given data and generates a unique
+    /// identifier, calling `on_drop` with that identifier once the last strong
+    /// reference to the data is dropped.
+    ///
+    /// This allows renderers to free resources such as GPU textures as soon as
+    /// the blob is no longer in use, rather than periodically checking
+    /// [weak references](WeakBlob) for liveness.
+    ///
+    /// The hook runs on whichever thread drops the last reference, so it should
+    /// be cheap and must not block; sending the identifier over a channel to
+    /// the renderer is typical.
+    pub fn new_with_drop_hook(
+        data: Arc<dyn AsRef<[T]> + Send + Sync>,
+        on_drop: impl FnOnce(u64) + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: 'static,
+    {
+        let id = ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let data = Arc::new(DropHook {
+            data,
+            id,
+            on_drop: Some(Box::new(on_drop)),
+        });
+        Self {
+            data,
+            id,
+            content_hash: None,
         }
     }
 
@@ -103,10 +146,17 @@ impl<T> Blob<T> {
     /// This is primarily for libraries that wish to interop with vello but are
     /// unable to depend on our resource types.
     pub fn from_raw_parts(data: Arc<dyn AsRef<[T]> + Send + Sync>, id: u64) -> Self {
-        Self { data, id }
+        Self {
+            data,
+            id,
+            content_hash: None,
+        }
     }
 
     /// Consumes self and returns the inner components of the blob.
+    ///
+    /// The [content hash](Self::content_hash) isn't included, so a blob rebuilt with
+    /// [`from_raw_parts`](Self::from_raw_parts) has none.
     #[must_use]
     pub fn into_raw_parts(self) -> (Arc<dyn AsRef<[T]> + Send + Sync>, u64) {
         (self.data, self.id)
@@ -136,6 +186,17 @@ impl<T> Blob<T> {
         self.id
     }
 
+    /// Returns the hash of the content of the blob, if it was created with
+    /// [`new_content_hashed`](Blob::new_content_hashed) or deserialized.
+    ///
+    /// Unlike the [identifier](Self::id), the content hash is stable across
+    /// processes, so it can be used to deduplicate and reconnect resources in
+    /// serialized scenes.
+    #[must_use]
+    pub fn content_hash(&self) -> Option<u128> {
+        self.content_hash.as_deref().copied()
+    }
+
     /// Returns the number of existing strong pointers to this blob.
     #[must_use]
     pub fn strong_count(&self) -> usize {
@@ -148,6 +209,53 @@ impl<T> Blob<T> {
         WeakBlob {
             data: Arc::downgrade(&self.data),
             id: self.id,
+            content_hash: self.content_hash.clone(),
+        }
+    }
+}
+
+impl Blob<u8> {
+    /// Creates a new blob from the given data, generating a unique identifier
+    /// and computing a 128-bit [content hash](Self::content_hash) of the data.
+    ///
+    /// The hash is the 128-bit FNV-1a hash of the bytes, which is deterministic
+    /// across processes and platforms, but is not cryptographically secure.
+    pub fn new_content_hashed(data: Arc<dyn AsRef<[u8]> + Send + Sync>) -> Self {
+        let content_hash = fnv1a_128((*data).as_ref());
+        Self {
+            data,
+            id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
+            content_hash: Some(Arc::new(content_hash)),
+        }
+    }
+}
+
+/// Computes the 128-bit FNV-1a hash of the given bytes.
+fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e_07bb0142_62b82175_6295c58d;
+    const PRIME: u128 = 0x00000000_01000000_00000000_0000013b;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Data wrapper which notifies a hook when it is dropped.
+struct DropHook<T> {
+    data: Arc<dyn AsRef<[T]> + Send + Sync>,
+    id: u64,
+    on_drop: Option<Box<dyn FnOnce(u64) + Send + Sync>>,
+}
+
+impl<T> AsRef<[T]> for DropHook<T> {
+    fn as_ref(&self) -> &[T] {
+        self.data.as_ref().as_ref()
+    }
+}
+
+impl<T> Drop for DropHook<T> {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop.take() {
+            on_drop(self.id);
         }
     }
 }
@@ -157,6 +265,7 @@ impl<T> Blob<T> {
 pub struct WeakBlob<T> {
     data: Weak<dyn AsRef<[T]> + Send + Sync>,
     id: u64,
+    content_hash: Option<Arc<u128>>,
 }
 
 impl<T> Clone for WeakBlob<T> {
@@ -164,6 +273,7 @@ impl<T> Clone for WeakBlob<T> {
         Self {
             data: Weak::clone(&self.data),
             id: self.id,
+            content_hash: self.content_hash.clone(),
         }
     }
 }
@@ -175,6 +285,14 @@ impl<T> WeakBlob<T> {
         self.id
     }
 
+    /// Returns the hash of the content of the blob, if it has one.
+    ///
+    /// See [`Blob::content_hash`].
+    #[must_use]
+    pub fn content_hash(&self) -> Option<u128> {
+        self.content_hash.as_deref().copied()
+    }
+
     /// Upgrades the weak reference. Returns `None` if the inner value has been
     /// dropped.
     #[must_use]
@@ -182,6 +300,60 @@ impl<T> WeakBlob<T> {
         Some(Blob {
             data: self.data.upgrade()?,
             id: self.id,
+            content_hash: self.content_hash.clone(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Blob;
+    use std::sync::{mpsc, Arc};
+
+    #[test]
+    fn drop_hook() {
+        let (sender, receiver) = mpsc::channel();
+        let blob = Blob::<u8>::new_with_drop_hook(Arc::new([1_u8, 2, 3]), move |id| {
+            sender.send(id).unwrap();
+        });
+        let id = blob.id();
+        assert_eq!(blob.data(), &[1, 2, 3]);
+        let weak = blob.downgrade();
+        let clone = blob.clone();
+        drop(blob);
+        assert!(receiver.try_recv().is_err());
+        drop(clone);
+        assert_eq!(receiver.try_recv(), Ok(id));
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn content_hash() {
+        let a = Blob::new_content_hashed(Arc::new(*b"peniko"));
+        let b = Blob::new_content_hashed(Arc::new(b"peniko".to_vec()));
+        assert_ne!(a, b);
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_eq!(a.downgrade().content_hash(), a.content_hash());
+        // Known-answer value for 128-bit FNV-1a of the empty string.
+        let empty = Blob::new_content_hashed(Arc::new([]));
+        assert_eq!(
+            empty.content_hash(),
+            Some(0x6c62272e_07bb0142_62b82175_6295c58d)
+        );
+        assert_ne!(
+            Blob::new_content_hashed(Arc::new(*b"penikO")).content_hash(),
+            a.content_hash()
+        );
+        assert_eq!(Blob::from(vec![0_u8]).content_hash(), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn deserialize_content_hash() {
+        let blob = Blob::new_content_hashed(Arc::new(*b"peniko"));
+        let json = serde_json::to_string(&blob).unwrap();
+        let round_trip: Blob<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip.data(), blob.data());
+        assert_eq!(round_trip.content_hash(), blob.content_hash());
+    }
+}
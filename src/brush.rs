@@ -1,9 +1,12 @@
 // Copyright 2022 the Peniko Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::{Gradient, Image};
+#[cfg(feature = "gradient")]
+use super::{ColorStop, GradientKind};
+use super::{EncodeGradient, EncodeImage, Gradient, Image, NoiseBrush, Point, ProceduralBrush};
 
-use color::{AlphaColor, ColorSpace, DynamicColor, OpaqueColor, Srgb};
+use color::{AlphaColor, ColorSpace, DynamicColor, OpaqueColor, PremulColor, Srgb};
+use core::cmp::Ordering;
 
 /// Describes the color content of a filled or stroked shape.
 ///
@@ -13,10 +16,19 @@ use color::{AlphaColor, ColorSpace, DynamicColor, OpaqueColor, Srgb};
 pub enum Brush {
     /// Solid color brush.
     Solid(AlphaColor<Srgb>),
+    /// Solid color brush with a color in any color space.
+    ///
+    /// Unlike [`Solid`](Self::Solid), this preserves colors which are outside of the
+    /// sRGB gamut, such as those in Display P3 or Oklch, so that renderers with a
+    /// wide-gamut target can draw them faithfully. Renderers which only support sRGB
+    /// can convert the color with [`DynamicColor::to_alpha_color`].
+    DynamicSolid(DynamicColor),
     /// Gradient brush.
     Gradient(Gradient),
     /// Image brush.
     Image(Image),
+    /// Procedural brush, such as noise.
+    Procedural(ProceduralBrush),
 }
 
 impl<CS: ColorSpace> From<AlphaColor<CS>> for Brush {
@@ -27,7 +39,13 @@ impl<CS: ColorSpace> From<AlphaColor<CS>> for Brush {
 
 impl From<DynamicColor> for Brush {
     fn from(c: DynamicColor) -> Self {
-        Self::Solid(c.to_alpha_color::<Srgb>())
+        Self::DynamicSolid(c)
+    }
+}
+
+impl<CS: ColorSpace> From<PremulColor<CS>> for Brush {
+    fn from(c: PremulColor<CS>) -> Self {
+        Self::Solid(c.un_premultiply().convert())
     }
 }
 
@@ -49,6 +67,18 @@ impl From<Image> for Brush {
     }
 }
 
+impl From<ProceduralBrush> for Brush {
+    fn from(value: ProceduralBrush) -> Self {
+        Self::Procedural(value)
+    }
+}
+
+impl From<NoiseBrush> for Brush {
+    fn from(value: NoiseBrush) -> Self {
+        Self::Procedural(value.into())
+    }
+}
+
 impl Default for Brush {
     fn default() -> Self {
         Self::Solid(AlphaColor::<Srgb>::TRANSPARENT)
@@ -56,13 +86,28 @@ impl Default for Brush {
 }
 
 impl Brush {
+    /// Returns the color of a solid brush in sRGB with premultiplied alpha, or `None`
+    /// for other brushes.
+    ///
+    /// Colors of [`DynamicSolid`](Self::DynamicSolid) brushes are converted to sRGB.
+    #[must_use]
+    pub fn to_premul_solid(&self) -> Option<PremulColor<Srgb>> {
+        match self {
+            Self::Solid(color) => Some(color.premultiply()),
+            Self::DynamicSolid(color) => Some(color.to_alpha_color::<Srgb>().premultiply()),
+            _ => None,
+        }
+    }
+
     /// Returns the brush with the alpha component set to `alpha`.
     #[must_use]
     pub fn with_alpha(self, alpha: f32) -> Self {
         match self {
             Self::Solid(color) => color.with_alpha(alpha).into(),
+            Self::DynamicSolid(color) => color.with_alpha(alpha).into(),
             Self::Gradient(gradient) => gradient.with_alpha(alpha).into(),
             Self::Image(image) => image.with_alpha(alpha).into(),
+            Self::Procedural(procedural) => procedural.with_alpha(alpha).into(),
         }
     }
 
@@ -83,11 +128,55 @@ impl Brush {
         } else {
             match self {
                 Self::Solid(color) => color.multiply_alpha(alpha).into(),
+                Self::DynamicSolid(color) => color.multiply_alpha(alpha).into(),
                 Self::Gradient(gradient) => gradient.multiply_alpha(alpha).into(),
                 Self::Image(image) => image.multiply_alpha(alpha).into(),
+                Self::Procedural(procedural) => procedural.multiply_alpha(alpha).into(),
             }
         }
     }
+
+    /// Returns the color of a [solid](Self::Solid) brush, or `None` for other kinds of
+    /// brush.
+    ///
+    /// This doesn't return the colors of [dynamic solid](Self::DynamicSolid) brushes, as
+    /// they may not be representable in sRGB. Use
+    /// [`as_dynamic_solid`](Self::as_dynamic_solid) for those.
+    #[must_use]
+    pub fn as_solid(&self) -> Option<AlphaColor<Srgb>> {
+        BrushRef::from(self).as_solid()
+    }
+
+    /// Returns the color of a [dynamic solid](Self::DynamicSolid) brush, or `None` for
+    /// other kinds of brush.
+    #[must_use]
+    pub fn as_dynamic_solid(&self) -> Option<DynamicColor> {
+        BrushRef::from(self).as_dynamic_solid()
+    }
+
+    /// Returns the gradient of a [gradient](Self::Gradient) brush, or `None` for other
+    /// kinds of brush.
+    #[must_use]
+    pub fn as_gradient(&self) -> Option<&Gradient> {
+        match self {
+            Self::Gradient(gradient) => Some(gradient),
+            _ => None,
+        }
+    }
+
+    /// Returns the image of an [image](Self::Image) brush, or `None` for other kinds of
+    /// brush.
+    #[must_use]
+    pub fn as_image(&self) -> Option<&Image> {
+        BrushRef::from(self).as_image()
+    }
+
+    /// Returns the procedure of a [procedural](Self::Procedural) brush, or `None` for
+    /// other kinds of brush.
+    #[must_use]
+    pub fn as_procedural(&self) -> Option<ProceduralBrush> {
+        BrushRef::from(self).as_procedural()
+    }
 }
 
 /// Reference to a [brush](Brush).
@@ -103,20 +192,85 @@ impl Brush {
 pub enum BrushRef<'a> {
     /// Solid color brush.
     Solid(AlphaColor<Srgb>),
+    /// Solid color brush with a color in any color space.
+    DynamicSolid(DynamicColor),
     /// Gradient brush.
     Gradient(&'a Gradient),
+    /// Gradient brush which borrows only its stops.
+    ///
+    /// Unlike [`Gradient`](Self::Gradient), this doesn't need a [`Gradient`] to be
+    /// stored anywhere, so it can be built on the stack for each draw without
+    /// allocating. See [`EncodeGradient::new`].
+    BorrowedGradient(EncodeGradient<'a>),
     /// Image brush.
     Image(&'a Image),
+    /// Procedural brush, such as noise.
+    Procedural(ProceduralBrush),
 }
 
-impl BrushRef<'_> {
+impl<'a> BrushRef<'a> {
     /// Converts the reference to an owned brush.
     #[must_use]
     pub fn to_owned(&self) -> Brush {
         match self {
             Self::Solid(color) => Brush::Solid(*color),
+            Self::DynamicSolid(color) => Brush::DynamicSolid(*color),
             Self::Gradient(gradient) => Brush::Gradient((*gradient).clone()),
+            Self::BorrowedGradient(gradient) => Brush::Gradient(gradient.to_gradient()),
             Self::Image(image) => Brush::Image((*image).clone()),
+            Self::Procedural(procedural) => Brush::Procedural(*procedural),
+        }
+    }
+
+    /// Returns the color of a [solid](Self::Solid) brush, or `None` for other kinds of
+    /// brush.
+    #[must_use]
+    pub fn as_solid(&self) -> Option<AlphaColor<Srgb>> {
+        match self {
+            Self::Solid(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    /// Returns the color of a [dynamic solid](Self::DynamicSolid) brush, or `None` for
+    /// other kinds of brush.
+    #[must_use]
+    pub fn as_dynamic_solid(&self) -> Option<DynamicColor> {
+        match self {
+            Self::DynamicSolid(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    /// Returns the borrowed form of a [gradient](Self::Gradient) or
+    /// [borrowed gradient](Self::BorrowedGradient) brush, or `None` for other kinds of
+    /// brush.
+    #[must_use]
+    pub fn as_encode_gradient(&self) -> Option<EncodeGradient<'a>> {
+        match self {
+            Self::Gradient(gradient) => Some((*gradient).into()),
+            Self::BorrowedGradient(gradient) => Some(*gradient),
+            _ => None,
+        }
+    }
+
+    /// Returns the image of an [image](Self::Image) brush, or `None` for other kinds of
+    /// brush.
+    #[must_use]
+    pub fn as_image(&self) -> Option<&'a Image> {
+        match self {
+            Self::Image(image) => Some(image),
+            _ => None,
+        }
+    }
+
+    /// Returns the procedure of a [procedural](Self::Procedural) brush, or `None` for
+    /// other kinds of brush.
+    #[must_use]
+    pub fn as_procedural(&self) -> Option<ProceduralBrush> {
+        match self {
+            Self::Procedural(procedural) => Some(*procedural),
+            _ => None,
         }
     }
 }
@@ -129,7 +283,7 @@ impl<CS: ColorSpace> From<AlphaColor<CS>> for BrushRef<'_> {
 
 impl From<DynamicColor> for BrushRef<'_> {
     fn from(color: DynamicColor) -> Self {
-        Self::Solid(color.to_alpha_color::<Srgb>())
+        Self::DynamicSolid(color)
     }
 }
 
@@ -147,7 +301,7 @@ impl<'a, CS: ColorSpace> From<&'a AlphaColor<CS>> for BrushRef<'_> {
 
 impl<'a> From<&'a DynamicColor> for BrushRef<'_> {
     fn from(color: &'a DynamicColor) -> Self {
-        Self::Solid((*color).to_alpha_color::<Srgb>())
+        Self::DynamicSolid(*color)
     }
 }
 
@@ -163,26 +317,295 @@ impl<'a> From<&'a Gradient> for BrushRef<'a> {
     }
 }
 
+impl<'a> From<EncodeGradient<'a>> for BrushRef<'a> {
+    fn from(gradient: EncodeGradient<'a>) -> Self {
+        Self::BorrowedGradient(gradient)
+    }
+}
+
 impl<'a> From<&'a Image> for BrushRef<'a> {
     fn from(image: &'a Image) -> Self {
         Self::Image(image)
     }
 }
 
+impl From<ProceduralBrush> for BrushRef<'_> {
+    fn from(procedural: ProceduralBrush) -> Self {
+        Self::Procedural(procedural)
+    }
+}
+
 impl<'a> From<&'a Brush> for BrushRef<'a> {
     fn from(brush: &'a Brush) -> Self {
         match brush {
             Brush::Solid(color) => Self::Solid(*color),
+            Brush::DynamicSolid(color) => Self::DynamicSolid(*color),
             Brush::Gradient(gradient) => Self::Gradient(gradient),
             Brush::Image(image) => Self::Image(image),
+            Brush::Procedural(procedural) => Self::Procedural(*procedural),
+        }
+    }
+}
+
+/// Borrowed form of a [brush](Brush), designed as the input of scene encoders.
+///
+/// Unlike [`BrushRef`], this exposes the fields that an encoder needs directly,
+/// borrowing the gradient stops and image pixels rather than the containing types.
+/// Creating one doesn't allocate or touch reference counts, so it is suitable for
+/// hot encoding loops.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EncodeBrush<'a> {
+    /// Solid color brush.
+    Solid(AlphaColor<Srgb>),
+    /// Solid color brush with a color in any color space.
+    DynamicSolid(DynamicColor),
+    /// Gradient brush.
+    Gradient(EncodeGradient<'a>),
+    /// Image brush.
+    Image(EncodeImage<'a>),
+    /// Procedural brush, such as noise.
+    Procedural(ProceduralBrush),
+}
+
+impl<'a> From<BrushRef<'a>> for EncodeBrush<'a> {
+    fn from(brush: BrushRef<'a>) -> Self {
+        match brush {
+            BrushRef::Solid(color) => Self::Solid(color),
+            BrushRef::DynamicSolid(color) => Self::DynamicSolid(color),
+            BrushRef::Gradient(gradient) => Self::Gradient(gradient.into()),
+            BrushRef::BorrowedGradient(gradient) => Self::Gradient(gradient),
+            BrushRef::Image(image) => Self::Image(image.into()),
+            BrushRef::Procedural(procedural) => Self::Procedural(procedural),
         }
     }
 }
 
+impl<'a> From<&'a Brush> for EncodeBrush<'a> {
+    fn from(brush: &'a Brush) -> Self {
+        BrushRef::from(brush).into()
+    }
+}
+
+impl<'a> From<&'a Gradient> for EncodeBrush<'a> {
+    fn from(gradient: &'a Gradient) -> Self {
+        Self::Gradient(gradient.into())
+    }
+}
+
+impl<'a> From<&'a Image> for EncodeBrush<'a> {
+    fn from(image: &'a Image) -> Self {
+        Self::Image(image.into())
+    }
+}
+
+/// Wrapper providing a total order over brushes.
+///
+/// Brushes contain floating point values, so they don't implement [`Ord`]. This key
+/// orders brushes deterministically, which is useful for sorting draws into batches
+/// reproducibly:
+///
+/// - Brushes are first ordered by kind: solid colors, dynamic solid colors, gradients
+///   (owned or borrowed, which compare alike), images and then procedural brushes.
+/// - Brushes of the same kind are then ordered by their fields, comparing floating
+///   point values by their bits.
+/// - Image data is compared by [blob id](crate::Blob::id), not by content.
+///
+/// The order is not meaningful beyond being total and stable across runs (apart from
+/// blob ids, which depend on the order in which blobs are created). Two keys are equal
+/// exactly when their brushes are bitwise equal.
+#[derive(Copy, Clone, Debug)]
+pub struct OrdBrushKey<'a>(pub BrushRef<'a>);
+
+impl<'a> OrdBrushKey<'a> {
+    /// Creates a key for the given brush.
+    #[must_use]
+    pub fn new(brush: impl Into<BrushRef<'a>>) -> Self {
+        Self(brush.into())
+    }
+}
+
+impl<'a> From<&'a Brush> for OrdBrushKey<'a> {
+    fn from(brush: &'a Brush) -> Self {
+        Self(brush.into())
+    }
+}
+
+impl PartialEq for OrdBrushKey<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrdBrushKey<'_> {}
+
+impl PartialOrd for OrdBrushKey<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdBrushKey<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn rank(brush: &BrushRef<'_>) -> u8 {
+            match brush {
+                BrushRef::Solid(_) => 0,
+                BrushRef::DynamicSolid(_) => 1,
+                BrushRef::Gradient(_) | BrushRef::BorrowedGradient(_) => 2,
+                BrushRef::Image(_) => 3,
+                BrushRef::Procedural(_) => 4,
+            }
+        }
+        // Owned and borrowed gradients are compared alike.
+        if let (Some(a), Some(b)) = (self.0.as_encode_gradient(), other.0.as_encode_gradient()) {
+            return cmp_gradient(a, b);
+        }
+        match (&self.0, &other.0) {
+            (BrushRef::Solid(a), BrushRef::Solid(b)) => cmp_bits(&a.components, &b.components),
+            (BrushRef::DynamicSolid(a), BrushRef::DynamicSolid(b)) => cmp_dynamic_color(a, b),
+            (BrushRef::Image(a), BrushRef::Image(b)) => cmp_image(a, b),
+            (BrushRef::Procedural(a), BrushRef::Procedural(b)) => cmp_procedural(a, b),
+            (a, b) => rank(a).cmp(&rank(b)),
+        }
+    }
+}
+
+fn cmp_bits(a: &[f32], b: &[f32]) -> Ordering {
+    a.iter()
+        .map(|x| x.to_bits())
+        .cmp(b.iter().map(|x| x.to_bits()))
+}
+
+fn point_bits(point: Point) -> [u64; 2] {
+    [point.x.to_bits(), point.y.to_bits()]
+}
+
+fn cmp_dynamic_color(a: &DynamicColor, b: &DynamicColor) -> Ordering {
+    fn flags_key(color: &DynamicColor) -> (u8, bool, Option<&'static str>) {
+        let missing = (0..4)
+            .filter(|ix| color.flags.missing().contains(*ix))
+            .fold(0_u8, |bits, ix| bits | 1 << ix);
+        (missing, color.flags.named(), color.flags.color_name())
+    }
+    (a.cs as u8)
+        .cmp(&(b.cs as u8))
+        .then_with(|| flags_key(a).cmp(&flags_key(b)))
+        .then_with(|| cmp_bits(&a.components, &b.components))
+}
+
+#[cfg(not(feature = "gradient"))]
+fn cmp_gradient(_: EncodeGradient<'_>, _: EncodeGradient<'_>) -> Ordering {
+    // Gradients can't be constructed without the feature.
+    Ordering::Equal
+}
+
+#[cfg(feature = "gradient")]
+fn cmp_gradient(a: EncodeGradient<'_>, b: EncodeGradient<'_>) -> Ordering {
+    fn kind_key(kind: &GradientKind) -> (u8, [u64; 6]) {
+        match *kind {
+            GradientKind::Linear { start, end } => {
+                let ([sx, sy], [ex, ey]) = (point_bits(start), point_bits(end));
+                (0, [sx, sy, ex, ey, 0, 0])
+            }
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => {
+                let ([sx, sy], [ex, ey]) = (point_bits(start_center), point_bits(end_center));
+                let (sr, er) = (start_radius.to_bits().into(), end_radius.to_bits().into());
+                (1, [sx, sy, sr, ex, ey, er])
+            }
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                let [cx, cy] = point_bits(center);
+                let (sa, ea) = (start_angle.to_bits().into(), end_angle.to_bits().into());
+                (2, [cx, cy, sa, ea, 0, 0])
+            }
+        }
+    }
+    fn cmp_stop(a: &ColorStop, b: &ColorStop) -> Ordering {
+        a.offset
+            .to_bits()
+            .cmp(&b.offset.to_bits())
+            .then_with(|| cmp_dynamic_color(&a.color, &b.color))
+            .then_with(|| a.hint.map(f32::to_bits).cmp(&b.hint.map(f32::to_bits)))
+    }
+    kind_key(&a.kind)
+        .cmp(&kind_key(&b.kind))
+        .then_with(|| (a.extend as u8).cmp(&(b.extend as u8)))
+        .then_with(|| (a.interpolation_cs as u8).cmp(&(b.interpolation_cs as u8)))
+        .then_with(|| (a.hue_direction as u8).cmp(&(b.hue_direction as u8)))
+        .then_with(|| {
+            a.stops
+                .iter()
+                .zip(b.stops.iter())
+                .map(|(x, y)| cmp_stop(x, y))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.stops.len().cmp(&b.stops.len()))
+        })
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+fn cmp_image(a: &Image, b: &Image) -> Ordering {
+    fn key(image: &Image) -> ([u64; 4], [u8; 6], [u64; 5], [u32; 4]) {
+        let tiling = &image.tiling;
+        let [ox, oy] = point_bits(tiling.origin);
+        (
+            [
+                image.data.id(),
+                image.format as u64,
+                image.width.into(),
+                image.height.into(),
+            ],
+            [
+                image.x_extend as u8,
+                image.y_extend as u8,
+                image.quality as u8,
+                tiling.x_mode as u8,
+                tiling.y_mode as u8,
+                image.minification as u8,
+            ],
+            [
+                tiling.spacing.x.to_bits(),
+                tiling.spacing.y.to_bits(),
+                ox,
+                oy,
+                image.alpha.to_bits().into(),
+            ],
+            image.border_color.components.map(f32::to_bits),
+        )
+    }
+    key(a).cmp(&key(b))
+}
+
+fn cmp_procedural(a: &ProceduralBrush, b: &ProceduralBrush) -> Ordering {
+    fn key(procedural: &ProceduralBrush) -> (u32, [u64; 5], bool) {
+        match procedural {
+            ProceduralBrush::Noise(noise) => (
+                procedural.kind_tag(),
+                [
+                    noise.noise_type as u64,
+                    noise.base_frequency.x.to_bits(),
+                    noise.base_frequency.y.to_bits(),
+                    (u64::from(noise.octaves) << 32) | u64::from(noise.seed as u32),
+                    noise.alpha.to_bits().into(),
+                ],
+                noise.stitch_tiles,
+            ),
+        }
+    }
+    key(a).cmp(&key(b))
+}
+
 /// Defines how a brush is extended when the content does not
 /// fill a shape.
-#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Extend {
     /// Extends the image by repeating the edge color of the brush.
@@ -192,4 +615,263 @@ pub enum Extend {
     Repeat = 1,
     /// Extends the image by reflecting the brush.
     Reflect = 2,
+    /// Extends the image with a border color, as with a `ClampToBorder` GPU sampler.
+    ///
+    /// For images, this is the [border color](crate::Image::border_color) of the
+    /// image. Gradients are transparent outside of their stops.
+    Border = 3,
+}
+
+impl_u8_conversions!(Extend {
+    Pad = 0,
+    Repeat = 1,
+    Reflect = 2,
+    Border = 3,
+});
+
+impl Extend {
+    /// All extend modes, in order of their values.
+    pub const ALL: [Self; 4] = [Self::Pad, Self::Repeat, Self::Reflect, Self::Border];
+
+    /// Maps a texel index, which may lie outside of the image, to the index of the
+    /// texel that is sampled according to this extend mode.
+    ///
+    /// `size` is the number of texels along the axis.
+    ///
+    /// Texels outside of the image are in the border for [`Extend::Border`], which
+    /// this maps to the nearest edge texel, as for [`Extend::Pad`]. Use
+    /// [`resolve_texel_or_border`](Self::resolve_texel_or_border) to distinguish them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    #[must_use]
+    pub fn resolve_texel(self, index: i64, size: u32) -> u32 {
+        assert!(size != 0, "Cannot resolve texels of an empty image.");
+        let size = i64::from(size);
+        let resolved = match self {
+            Self::Pad | Self::Border => index.clamp(0, size - 1),
+            Self::Repeat => index.rem_euclid(size),
+            Self::Reflect => {
+                let period = index.rem_euclid(2 * size);
+                if period < size {
+                    period
+                } else {
+                    2 * size - 1 - period
+                }
+            }
+        };
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "The resolved index is within the range of `size`, which is a `u32`."
+        )]
+        let resolved = resolved as u32;
+        resolved
+    }
+
+    /// Maps a texel index as [`resolve_texel`](Self::resolve_texel) does, but returns
+    /// `None` for texels in the border of [`Extend::Border`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    #[must_use]
+    pub fn resolve_texel_or_border(self, index: i64, size: u32) -> Option<u32> {
+        if self == Self::Border && !(0..i64::from(size)).contains(&index) {
+            assert!(size != 0, "Cannot resolve texels of an empty image.");
+            return None;
+        }
+        Some(self.resolve_texel(index, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Brush, BrushRef};
+    use color::{AlphaColor, DisplayP3, DynamicColor};
+
+    #[test]
+    fn resolve_texel_or_border() {
+        use super::Extend;
+
+        assert_eq!(Extend::Border.resolve_texel_or_border(-1, 4), None);
+        assert_eq!(Extend::Border.resolve_texel_or_border(4, 4), None);
+        assert_eq!(Extend::Border.resolve_texel_or_border(2, 4), Some(2));
+        assert_eq!(Extend::Pad.resolve_texel_or_border(-1, 4), Some(0));
+        assert_eq!(Extend::Repeat.resolve_texel_or_border(5, 4), Some(1));
+    }
+
+    #[test]
+    fn premul_solid() {
+        use color::{PremulColor, Srgb};
+
+        let color = AlphaColor::<Srgb>::new([1.0, 0.5, 0.0, 0.5]);
+        let premul = Brush::from(color).to_premul_solid().unwrap();
+        assert_eq!(premul.components, [0.5, 0.25, 0.0, 0.5]);
+        assert_eq!(Brush::from(premul), Brush::from(color));
+        let dynamic = Brush::from(DynamicColor::from_alpha_color(color));
+        assert_eq!(dynamic.to_premul_solid(), Some(premul));
+        assert_eq!(
+            Brush::default().to_premul_solid(),
+            Some(PremulColor::TRANSPARENT)
+        );
+    }
+
+    #[test]
+    fn encode_brush() {
+        use super::EncodeBrush;
+        use crate::{Blob, Image, ImageFormat};
+        use color::palette;
+
+        let image = Image::new(Blob::from(vec![0, 0, 0, 255]), ImageFormat::Rgba8, 1, 1);
+        let image_brush = Brush::from(image.clone().with_alpha(0.5));
+        let EncodeBrush::Image(encoded) = EncodeBrush::from(&image_brush) else {
+            panic!("expected an image brush");
+        };
+        assert_eq!(encoded.alpha, 0.5);
+        // The pixels are borrowed from the blob.
+        assert!(core::ptr::eq(encoded.data.data, image.data.data()));
+        assert_eq!(
+            EncodeBrush::from(BrushRef::from(palette::css::RED)),
+            EncodeBrush::Solid(palette::css::RED)
+        );
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn encode_gradient_brush() {
+        use super::EncodeBrush;
+        use crate::Gradient;
+        use color::palette;
+
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([palette::css::RED, palette::css::BLUE]);
+        let brush = Brush::from(gradient.clone());
+        let EncodeBrush::Gradient(encoded) = EncodeBrush::from(&brush) else {
+            panic!("expected a gradient brush");
+        };
+        assert_eq!(encoded.kind, gradient.kind);
+        assert_eq!(encoded.stops, &gradient.stops[..]);
+        assert_eq!(EncodeBrush::from(&gradient), EncodeBrush::Gradient(encoded));
+    }
+
+    #[test]
+    fn procedural_brush() {
+        use super::OrdBrushKey;
+        use crate::{NoiseBrush, NoiseType, ProceduralBrush};
+        use color::palette;
+
+        let noise = NoiseBrush::new(NoiseType::FractalNoise, (0.05, 0.1), 3).with_seed(7);
+        let brush = Brush::from(noise).multiply_alpha(0.5);
+        let Brush::Procedural(ProceduralBrush::Noise(faded)) = brush else {
+            panic!("expected a noise brush");
+        };
+        assert_eq!(faded.alpha, 0.5);
+        assert_eq!(ProceduralBrush::from(faded).kind_tag(), 1);
+        assert_eq!(BrushRef::from(&brush).to_owned(), brush);
+        // Procedural brushes are ordered after all other kinds.
+        let solid = Brush::from(palette::css::RED);
+        assert!(OrdBrushKey::from(&solid) < OrdBrushKey::from(&brush));
+        assert!(OrdBrushKey::from(&Brush::from(noise)) > OrdBrushKey::from(&brush));
+    }
+
+    #[test]
+    fn accessors() {
+        use crate::{Blob, Image, ImageFormat};
+        use color::palette;
+
+        let solid = Brush::from(palette::css::RED);
+        assert_eq!(solid.as_solid(), Some(palette::css::RED));
+        assert_eq!(solid.as_image(), None);
+        assert!(solid.as_gradient().is_none());
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        let image_brush = Brush::from(image.clone());
+        assert_eq!(image_brush.as_image(), Some(&image));
+        assert_eq!(BrushRef::from(&image_brush).as_image(), Some(&image));
+        assert_eq!(image_brush.as_solid(), None);
+        assert_eq!(image_brush.as_procedural(), None);
+        let dynamic = Brush::from(DynamicColor::from_alpha_color(palette::css::BLUE));
+        assert_eq!(dynamic.as_solid(), None);
+        assert!(dynamic.as_dynamic_solid().is_some());
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn borrowed_gradient() {
+        use super::{EncodeBrush, OrdBrushKey};
+        use crate::{ColorStop, EncodeGradient, Extend, Gradient, GradientKind};
+        use color::palette;
+
+        let stops = [
+            ColorStop::from((0.0, palette::css::RED)),
+            ColorStop::from((1.0, palette::css::BLUE)),
+        ];
+        let kind = GradientKind::Linear {
+            start: (0.0, 0.0).into(),
+            end: (1.0, 0.0).into(),
+        };
+        let borrowed =
+            BrushRef::from(EncodeGradient::new(kind, &stops).with_extend(Extend::Reflect));
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_extend(Extend::Reflect)
+            .with_stops(stops.as_slice());
+        let owned = Brush::from(gradient.clone());
+        assert_eq!(borrowed.to_owned(), owned);
+        assert_eq!(
+            borrowed.as_encode_gradient(),
+            BrushRef::from(&owned).as_encode_gradient()
+        );
+        assert_eq!(EncodeBrush::from(borrowed), EncodeBrush::from(&gradient));
+        assert!(OrdBrushKey::new(borrowed) == OrdBrushKey::from(&owned));
+        assert!(OrdBrushKey::new(borrowed) > OrdBrushKey::new(palette::css::RED));
+    }
+
+    #[test]
+    fn dynamic_solid_preserves_gamut() {
+        // Pure Display P3 green is outside of the sRGB gamut.
+        let green = DynamicColor::from_alpha_color(AlphaColor::<DisplayP3>::new([0., 1., 0., 1.]));
+        let brush = Brush::from(green);
+        assert_eq!(brush, Brush::DynamicSolid(green));
+        assert_eq!(BrushRef::from(&brush).to_owned(), brush);
+        let Brush::DynamicSolid(faded) = brush.multiply_alpha(0.5) else {
+            panic!("expected a dynamic solid brush");
+        };
+        assert_eq!(faded.components, [0., 1., 0., 0.5]);
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn ord_brush_key() {
+        use super::OrdBrushKey;
+        use crate::{Blob, Gradient, Image, ImageFormat};
+        use color::{palette, Srgb};
+
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([palette::css::RED, palette::css::BLUE]);
+        let mut brushes = [
+            Brush::from(image.clone().with_alpha(0.5)),
+            Brush::from(gradient.clone().with_alpha(0.5)),
+            Brush::from(palette::css::BLUE),
+            Brush::from(image),
+            Brush::from(gradient),
+            Brush::from(palette::css::RED),
+            Brush::from(palette::css::BLUE),
+        ];
+        brushes.sort_by(|a, b| OrdBrushKey::from(a).cmp(&OrdBrushKey::from(b)));
+        assert_eq!(brushes[0], Brush::from(palette::css::BLUE));
+        assert_eq!(brushes[1], brushes[0]);
+        assert_eq!(brushes[2], Brush::from(palette::css::RED));
+        assert!(matches!(brushes[3], Brush::Gradient(_)));
+        assert!(matches!(brushes[5], Brush::Image(_)));
+        // Sorting is deterministic regardless of the initial order.
+        let mut reversed = brushes.clone();
+        reversed.reverse();
+        reversed.sort_by(|a, b| OrdBrushKey::from(a).cmp(&OrdBrushKey::from(b)));
+        assert_eq!(reversed, brushes);
+        // Negative and positive zero are distinct.
+        let zero = Brush::from(AlphaColor::<Srgb>::new([0.0; 4]));
+        let negative_zero = Brush::from(AlphaColor::<Srgb>::new([-0.0; 4]));
+        assert_ne!(OrdBrushKey::from(&zero), OrdBrushKey::from(&negative_zero));
+    }
 }
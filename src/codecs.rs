@@ -0,0 +1,152 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Encoding and decoding of images in the PNG format.
+
+use super::{Blob, Image, ImageFormat};
+
+use std::fmt;
+
+/// Error when decoding or encoding a PNG [image](Image).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PngError {
+    /// The PNG data could not be decoded.
+    Decoding(png::DecodingError),
+    /// The image could not be encoded as a PNG.
+    Encoding(png::EncodingError),
+    /// The image data is too short for the dimensions of the image.
+    InvalidData,
+}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decoding(err) => write!(f, "failed to decode PNG: {err}"),
+            Self::Encoding(err) => write!(f, "failed to encode PNG: {err}"),
+            Self::InvalidData => f.write_str("image data is too short for its dimensions"),
+        }
+    }
+}
+
+impl std::error::Error for PngError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decoding(err) => Some(err),
+            Self::Encoding(err) => Some(err),
+            Self::InvalidData => None,
+        }
+    }
+}
+
+impl From<png::DecodingError> for PngError {
+    fn from(err: png::DecodingError) -> Self {
+        Self::Decoding(err)
+    }
+}
+
+impl From<png::EncodingError> for PngError {
+    fn from(err: png::EncodingError) -> Self {
+        Self::Encoding(err)
+    }
+}
+
+impl Image {
+    /// Decodes an image from PNG data.
+    ///
+    /// Images of any color type and bit depth are converted to [`ImageFormat::Rgba8`],
+    /// with alpha which is separate (not premultiplied), as in PNG. Only the first
+    /// frame of an animated PNG is decoded. Gamma and color profile information is
+    /// ignored, so the pixels are assumed to be sRGB.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PngError::Decoding`] if the data is not a valid PNG image.
+    pub fn from_png(data: &[u8]) -> Result<Self, PngError> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(info.buffer_size());
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => expand(&buffer, 3, |p| [p[0], p[1], p[2], 255]),
+            png::ColorType::GrayscaleAlpha => expand(&buffer, 2, |p| [p[0], p[0], p[0], p[1]]),
+            // Indexed images are expanded to RGB or RGBA by the decoder.
+            png::ColorType::Grayscale | png::ColorType::Indexed => {
+                expand(&buffer, 1, |p| [p[0], p[0], p[0], 255])
+            }
+        };
+        Ok(Self::new(
+            Blob::from(pixels),
+            ImageFormat::Rgba8,
+            info.width,
+            info.height,
+        ))
+    }
+
+    /// Encodes the pixels of the image as PNG data.
+    ///
+    /// The pixels are written as 8-bit RGBA, with separate alpha. The
+    /// [alpha multiplier](Self::alpha) and sampling parameters are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PngError::InvalidData`] if the image data is too short for its
+    /// dimensions, and [`PngError::Encoding`] if the image can't be encoded, such as
+    /// when it is empty.
+    pub fn to_png(&self) -> Result<Vec<u8>, PngError> {
+        let pixels = self.as_data_ref().pixels().ok_or(PngError::InvalidData)?;
+        let mut output = Vec::new();
+        let mut encoder = png::Encoder::new(&mut output, self.width, self.height);
+        match self.format {
+            ImageFormat::Rgba8 => {
+                encoder.set_color(png::ColorType::Rgba);
+                encoder.set_depth(png::BitDepth::Eight);
+            }
+        }
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(pixels)?;
+        writer.finish()?;
+        Ok(output)
+    }
+}
+
+/// Converts pixels with `channels` bytes each to RGBA8.
+fn expand(data: &[u8], channels: usize, pixel: impl Fn(&[u8]) -> [u8; 4]) -> Vec<u8> {
+    data.chunks_exact(channels).flat_map(pixel).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PngError;
+    use crate::{Blob, Image, ImageFormat};
+    use color::palette::css;
+
+    #[test]
+    fn png_round_trip() {
+        let image = Image::from_color(css::RED.with_alpha(0.5), 3, 2, ImageFormat::Rgba8);
+        let encoded = image.to_png().unwrap();
+        let decoded = Image::from_png(&encoded).unwrap();
+        assert_eq!((decoded.width, decoded.height), (3, 2));
+        assert_eq!(decoded.data.data(), image.data.data());
+
+        // Grayscale images are expanded to RGBA.
+        let mut gray = Vec::new();
+        let mut encoder = png::Encoder::new(&mut gray, 2, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0, 200]).unwrap();
+        writer.finish().unwrap();
+        let decoded_gray = Image::from_png(&gray).unwrap();
+        assert_eq!(decoded_gray.data.data(), [0, 0, 0, 255, 200, 200, 200, 255]);
+
+        assert!(matches!(
+            Image::from_png(b"not a png"),
+            Err(PngError::Decoding(_))
+        ));
+        let truncated = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 2, 2);
+        assert!(matches!(truncated.to_png(), Err(PngError::InvalidData)));
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use color::ColorSpaceTag;
+
+/// Returns the CSS name of a color space, if it has one.
+pub(crate) fn css_color_space_name(cs: ColorSpaceTag) -> Option<&'static str> {
+    Some(match cs {
+        ColorSpaceTag::Srgb => "srgb",
+        ColorSpaceTag::LinearSrgb => "srgb-linear",
+        ColorSpaceTag::Lab => "lab",
+        ColorSpaceTag::Lch => "lch",
+        ColorSpaceTag::Hsl => "hsl",
+        ColorSpaceTag::Hwb => "hwb",
+        ColorSpaceTag::Oklab => "oklab",
+        ColorSpaceTag::Oklch => "oklch",
+        ColorSpaceTag::DisplayP3 => "display-p3",
+        ColorSpaceTag::A98Rgb => "a98-rgb",
+        ColorSpaceTag::ProphotoRgb => "prophoto-rgb",
+        ColorSpaceTag::Rec2020 => "rec2020",
+        ColorSpaceTag::XyzD50 => "xyz-d50",
+        ColorSpaceTag::XyzD65 => "xyz-d65",
+        _ => return None,
+    })
+}
+
+/// Returns the color space with the given CSS name.
+pub(crate) fn css_color_space_from_name(name: &str) -> Option<ColorSpaceTag> {
+    // `xyz` is an alias for `xyz-d65`.
+    if name.eq_ignore_ascii_case("xyz") {
+        return Some(ColorSpaceTag::XyzD65);
+    }
+    [
+        ColorSpaceTag::Srgb,
+        ColorSpaceTag::LinearSrgb,
+        ColorSpaceTag::Lab,
+        ColorSpaceTag::Lch,
+        ColorSpaceTag::Hsl,
+        ColorSpaceTag::Hwb,
+        ColorSpaceTag::Oklab,
+        ColorSpaceTag::Oklch,
+        ColorSpaceTag::DisplayP3,
+        ColorSpaceTag::A98Rgb,
+        ColorSpaceTag::ProphotoRgb,
+        ColorSpaceTag::Rec2020,
+        ColorSpaceTag::XyzD50,
+        ColorSpaceTag::XyzD65,
+    ]
+    .into_iter()
+    .find(|&cs| css_color_space_name(cs).is_some_and(|css| name.eq_ignore_ascii_case(css)))
+}
@@ -0,0 +1,378 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A compact string form of gradients, modelled on the CSS gradient functions.
+
+use core::fmt::{self, Write as _};
+
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use color::DynamicColor;
+
+use crate::{ColorStop, Extend, Gradient, GradientKind, Point};
+
+/// Errors which can occur when parsing the [compact form](CompactGradient) of a gradient.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CompactGradientError {
+    /// The string isn't well-formed, such as an unknown gradient function or a
+    /// missing number.
+    Syntax,
+    /// The color of a stop couldn't be parsed.
+    InvalidColor(color::ParseError),
+}
+
+impl fmt::Display for CompactGradientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => f.write_str("malformed compact gradient"),
+            Self::InvalidColor(err) => write!(f, "invalid color in compact gradient: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for CompactGradientError {}
+
+/// Wrapper which serializes a [`Gradient`] as a single string, in a compact form
+/// modelled on the CSS gradient functions.
+///
+/// This is much smaller than the field-by-field encoding of a gradient, which
+/// makes a difference for documents with many gradients. The form is:
+///
+/// ```text
+/// <kind>-gradient(<geometry> [<extend>] [id <id>] [<interpolation>], <stop-or-hint>, ...)
+/// ```
+///
+/// - `<kind>` and `<geometry>` are `linear` with `x0 y0 x1 y1`, `radial` with
+///   `x0 y0 r0 x1 y1 r1`, or `sweep` with `x y start_angle end_angle` in radians,
+///   matching the fields of [`GradientKind`].
+/// - `<extend>` is one of `repeat`, `reflect` or `border`, and is omitted for
+///   [`Extend::Pad`].
+/// - `<interpolation>` is a CSS [`<color-interpolation-method>`] as produced by
+///   [`Gradient::interpolation_to_css`], and is omitted for the default of sRGB.
+/// - Stops are a CSS color followed by the offset, such as `red 0.5`, and hints are
+///   an offset on their own between two stops, as in CSS. Offsets can also be
+///   written as percentages when parsing.
+///
+/// For example, `linear-gradient(0 0 100 0 reflect in oklch, red 0, blue 1)`.
+///
+/// Numbers are written so that they are read back exactly. Gradients whose
+/// interpolation color space has no CSS name can't be serialized in this form.
+///
+/// [`<color-interpolation-method>`]: https://drafts.csswg.org/css-color-4/#color-interpolation-method
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CompactGradient(pub Gradient);
+
+impl From<Gradient> for CompactGradient {
+    fn from(gradient: Gradient) -> Self {
+        Self(gradient)
+    }
+}
+
+impl From<CompactGradient> for Gradient {
+    fn from(compact: CompactGradient) -> Self {
+        compact.0
+    }
+}
+
+impl Gradient {
+    /// Returns the [compact form](CompactGradient) of the gradient.
+    ///
+    /// Returns `None` if the interpolation color space has no CSS name.
+    #[must_use]
+    pub fn to_compact_string(&self) -> Option<String> {
+        let mut s = String::new();
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                write!(
+                    s,
+                    "linear-gradient({} {} {} {}",
+                    start.x, start.y, end.x, end.y
+                )
+            }
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => write!(
+                s,
+                "radial-gradient({} {} {start_radius} {} {} {end_radius}",
+                start_center.x, start_center.y, end_center.x, end_center.y
+            ),
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => write!(
+                s,
+                "sweep-gradient({} {} {start_angle} {end_angle}",
+                center.x, center.y
+            ),
+        }
+        .ok()?;
+        match self.extend {
+            Extend::Pad => {}
+            Extend::Repeat => s.push_str(" repeat"),
+            Extend::Reflect => s.push_str(" reflect"),
+            Extend::Border => s.push_str(" border"),
+        }
+        if let Some(id) = self.id {
+            write!(s, " id {id}").ok()?;
+        }
+        let interpolation = self.interpolation_to_css()?;
+        if interpolation != "in srgb" {
+            s.push(' ');
+            s.push_str(&interpolation);
+        }
+        for stop in self.stops.iter() {
+            write!(s, ", {} {}", stop.color, stop.offset).ok()?;
+            if let Some(hint) = stop.hint {
+                write!(s, ", {hint}").ok()?;
+            }
+        }
+        s.push(')');
+        Some(s)
+    }
+
+    /// Parses the [compact form](CompactGradient) of a gradient.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CompactGradientError::InvalidColor`] if the color of a stop can't
+    /// be parsed, and [`CompactGradientError::Syntax`] for any other problem.
+    pub fn from_compact_str(s: &str) -> Result<Self, CompactGradientError> {
+        let (function, args) = s
+            .trim()
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or(CompactGradientError::Syntax)?;
+        let mut items = split_top_level(args).into_iter();
+        let mut header = items
+            .next()
+            .ok_or(CompactGradientError::Syntax)?
+            .split_ascii_whitespace()
+            .peekable();
+        let mut number = || -> Result<f64, CompactGradientError> {
+            parse_number(header.next().ok_or(CompactGradientError::Syntax)?)
+        };
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Radii and angles are stored as `f32`, and were written from `f32` values."
+        )]
+        let mut gradient = match function.trim() {
+            "linear-gradient" => {
+                let start = Point::new(number()?, number()?);
+                let end = Point::new(number()?, number()?);
+                Self::new_linear(start, end)
+            }
+            "radial-gradient" => {
+                let start_center = Point::new(number()?, number()?);
+                let start_radius = number()? as f32;
+                let end_center = Point::new(number()?, number()?);
+                let end_radius = number()? as f32;
+                Self::new_two_point_radial(start_center, start_radius, end_center, end_radius)
+            }
+            "sweep-gradient" => {
+                let center = Point::new(number()?, number()?);
+                let start_angle = number()? as f32;
+                let end_angle = number()? as f32;
+                Self::new_sweep(center, start_angle, end_angle)
+            }
+            _ => return Err(CompactGradientError::Syntax),
+        };
+        let extend = match header.peek() {
+            Some(&"pad") => Some(Extend::Pad),
+            Some(&"repeat") => Some(Extend::Repeat),
+            Some(&"reflect") => Some(Extend::Reflect),
+            Some(&"border") => Some(Extend::Border),
+            _ => None,
+        };
+        if let Some(extend) = extend {
+            header.next();
+            gradient.extend = extend;
+        }
+        if header.peek() == Some(&"id") {
+            header.next();
+            let id = header.next().ok_or(CompactGradientError::Syntax)?;
+            gradient.id = Some(id.parse().map_err(|_| CompactGradientError::Syntax)?);
+        }
+        let interpolation: Vec<&str> = header.collect();
+        if !interpolation.is_empty() {
+            let (cs, hue_direction) = Self::from_css_interpolation(&interpolation.join(" "))
+                .ok_or(CompactGradientError::Syntax)?;
+            gradient.interpolation_cs = cs;
+            gradient.hue_direction = hue_direction;
+        }
+        for item in items {
+            let item = item.trim();
+            if let Ok(hint) = parse_offset(item) {
+                let last = gradient
+                    .stops
+                    .last_mut()
+                    .filter(|stop| stop.hint.is_none())
+                    .ok_or(CompactGradientError::Syntax)?;
+                last.hint = Some(hint);
+                continue;
+            }
+            let (color, offset) = item
+                .rsplit_once(|c: char| c.is_ascii_whitespace())
+                .ok_or(CompactGradientError::Syntax)?;
+            let color: DynamicColor =
+                color::parse_color(color).map_err(CompactGradientError::InvalidColor)?;
+            gradient.stops.push(ColorStop {
+                offset: parse_offset(offset)?,
+                color,
+                hint: None,
+            });
+        }
+        Ok(gradient)
+    }
+}
+
+/// Splits a list of arguments at the commas which aren't nested in parentheses.
+fn split_top_level(args: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let (mut depth, mut start) = (0_usize, 0);
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&args[start..]);
+    items
+}
+
+fn parse_number(s: &str) -> Result<f64, CompactGradientError> {
+    s.parse().map_err(|_| CompactGradientError::Syntax)
+}
+
+/// Parses an offset, which is either a number or a percentage.
+fn parse_offset(s: &str) -> Result<f32, CompactGradientError> {
+    match s.strip_suffix('%') {
+        Some(percentage) => percentage
+            .parse::<f32>()
+            .map(|percentage| percentage / 100.0),
+        None => s.parse(),
+    }
+    .map_err(|_| CompactGradientError::Syntax)
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompactGradient {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s = self.0.to_compact_string().ok_or_else(|| {
+            serde::ser::Error::custom("the interpolation color space has no CSS name")
+        })?;
+        serializer.serialize_str(&s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CompactGradient {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Gradient::from_compact_str(&s)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactGradientError;
+    use crate::{ColorStop, Extend, Gradient};
+    use color::{palette::css, ColorSpaceTag, HueDirection};
+
+    fn round_trip(gradient: &Gradient) -> String {
+        let s = gradient.to_compact_string().unwrap();
+        assert_eq!(&Gradient::from_compact_str(&s).unwrap(), gradient, "{s}");
+        s
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let linear = Gradient::new_linear((0.0, 0.0), (100.0, 0.0))
+            .with_extend(Extend::Reflect)
+            .with_interpolation_cs(ColorSpaceTag::Oklch)
+            .with_hue_direction(HueDirection::Longer)
+            .with_stops([css::RED, css::BLUE]);
+        assert_eq!(
+            round_trip(&linear),
+            "linear-gradient(0 0 100 0 reflect in oklch longer hue, color(srgb 1 0 0) 0, color(srgb 0 0 1) 1)"
+        );
+
+        let mut radial = Gradient::new_two_point_radial((1.5, 2.0), 0.1, (3.0, 4.0), 10.0)
+            .with_id(42)
+            .with_stops([(0.0, css::WHITE), (0.75, css::BLACK.with_alpha(0.3))]);
+        radial.stops[0].hint = Some(0.2);
+        let s = round_trip(&radial);
+        assert!(s.starts_with("radial-gradient(1.5 2 0.1 3 4 10 id 42, color(srgb 1 1 1) 0, 0.2, "));
+
+        let sweep = Gradient::new_sweep((0.5, 0.5), 0.3, 6.0)
+            .with_stops([color::parse_color("oklab(0.5 0.1 -0.1 / 0.25)").unwrap()]);
+        round_trip(&sweep);
+    }
+
+    #[test]
+    fn compact_parsing() {
+        let gradient =
+            Gradient::from_compact_str(" linear-gradient(0 0 1 1, rgb(255, 0, 0) 0%, blue 50%)")
+                .unwrap();
+        assert_eq!(gradient.stops.len(), 2);
+        assert_eq!(gradient.stops[1].offset, 0.5);
+        assert_eq!(
+            gradient.stops[0],
+            ColorStop::from((0.0, color::parse_color("rgb(255, 0, 0)").unwrap()))
+        );
+
+        for invalid in [
+            "linear-gradient(0 0 1, red 0)",
+            "conic-gradient(0 0 1 1, red 0)",
+            "linear-gradient(0 0 1 1 in nowhere, red 0)",
+            "linear-gradient(0 0 1 1, 0.5, red 0)",
+            "linear-gradient(0 0 1 1, red)",
+            "linear-gradient(0 0 1 1, red 0",
+        ] {
+            assert_eq!(
+                Gradient::from_compact_str(invalid),
+                Err(CompactGradientError::Syntax),
+                "{invalid}"
+            );
+        }
+        assert!(matches!(
+            Gradient::from_compact_str("linear-gradient(0 0 1 1, notacolor 0)"),
+            Err(CompactGradientError::InvalidColor(_))
+        ));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn compact_serde() {
+        use super::CompactGradient;
+
+        let gradient =
+            Gradient::new_linear((0.0, 0.0), (1.0, 0.0)).with_stops([css::RED, css::BLUE]);
+        let json = serde_json::to_string(&CompactGradient(gradient.clone())).unwrap();
+        assert_eq!(
+            json,
+            r#""linear-gradient(0 0 1 0, color(srgb 1 0 0) 0, color(srgb 0 0 1) 1)""#
+        );
+        let compact: CompactGradient = serde_json::from_str(&json).unwrap();
+        assert_eq!(compact.0, gradient);
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Compatibility shims for the `Color` API of peniko 0.2.
+//!
+//! [`Color`] used to be a struct with 8-bit components, SVG color constants and
+//! parsing. It is now an alias for [`AlphaColor<Srgb>`](color::AlphaColor) from the
+//! [`color`] crate. This module maps the old API onto the new one, so that code can
+//! be migrated incrementally:
+//!
+//! - Import [`LegacyColor`] to keep calling the old methods, such as
+//!   `Color::rgba8`. Each method is deprecated, with a note naming its replacement.
+//! - `Color::to_premul_u32` is provided by [`PackedColor`](crate::PackedColor), which
+//!   isn't deprecated.
+//! - The SVG color constants, such as `Color::ALICE_BLUE`, are also provided by
+//!   [`LegacyColor`], and deprecated in favor of [`svg`], which is the CSS palette of
+//!   the `color` crate. `BLACK`, `WHITE` and `TRANSPARENT` are still constants of
+//!   [`Color`].
+//!
+//! This module will be removed in a future release.
+
+use crate::Color;
+use color::Srgb;
+
+/// The named SVG and CSS colors, which were associated constants of the old
+/// [`Color`], such as `svg::ALICE_BLUE` for `Color::ALICE_BLUE`.
+pub use color::palette::css as svg;
+
+/// Invokes `$callback` with the names of the SVG colors which aren't constants of
+/// the new [`Color`].
+macro_rules! with_svg_color_names {
+    ($callback:ident) => {
+        $callback!(
+            ALICE_BLUE,
+            ANTIQUE_WHITE,
+            AQUA,
+            AQUAMARINE,
+            AZURE,
+            BEIGE,
+            BISQUE,
+            BLANCHED_ALMOND,
+            BLUE,
+            BLUE_VIOLET,
+            BROWN,
+            BURLYWOOD,
+            CADET_BLUE,
+            CHARTREUSE,
+            CHOCOLATE,
+            CORAL,
+            CORNFLOWER_BLUE,
+            CORNSILK,
+            CRIMSON,
+            CYAN,
+            DARK_BLUE,
+            DARK_CYAN,
+            DARK_GOLDENROD,
+            DARK_GRAY,
+            DARK_GREEN,
+            DARK_KHAKI,
+            DARK_MAGENTA,
+            DARK_OLIVE_GREEN,
+            DARK_ORANGE,
+            DARK_ORCHID,
+            DARK_RED,
+            DARK_SALMON,
+            DARK_SEA_GREEN,
+            DARK_SLATE_BLUE,
+            DARK_SLATE_GRAY,
+            DARK_TURQUOISE,
+            DARK_VIOLET,
+            DEEP_PINK,
+            DEEP_SKY_BLUE,
+            DIM_GRAY,
+            DODGER_BLUE,
+            FIREBRICK,
+            FLORAL_WHITE,
+            FOREST_GREEN,
+            FUCHSIA,
+            GAINSBORO,
+            GHOST_WHITE,
+            GOLD,
+            GOLDENROD,
+            GRAY,
+            GREEN,
+            GREEN_YELLOW,
+            HONEYDEW,
+            HOT_PINK,
+            INDIAN_RED,
+            INDIGO,
+            IVORY,
+            KHAKI,
+            LAVENDER,
+            LAVENDER_BLUSH,
+            LAWN_GREEN,
+            LEMON_CHIFFON,
+            LIGHT_BLUE,
+            LIGHT_CORAL,
+            LIGHT_CYAN,
+            LIGHT_GOLDENROD_YELLOW,
+            LIGHT_GRAY,
+            LIGHT_GREEN,
+            LIGHT_PINK,
+            LIGHT_SALMON,
+            LIGHT_SEA_GREEN,
+            LIGHT_SKY_BLUE,
+            LIGHT_SLATE_GRAY,
+            LIGHT_STEEL_BLUE,
+            LIGHT_YELLOW,
+            LIME,
+            LIME_GREEN,
+            LINEN,
+            MAGENTA,
+            MAROON,
+            MEDIUM_AQUAMARINE,
+            MEDIUM_BLUE,
+            MEDIUM_ORCHID,
+            MEDIUM_PURPLE,
+            MEDIUM_SEA_GREEN,
+            MEDIUM_SLATE_BLUE,
+            MEDIUM_SPRING_GREEN,
+            MEDIUM_TURQUOISE,
+            MEDIUM_VIOLET_RED,
+            MIDNIGHT_BLUE,
+            MINT_CREAM,
+            MISTY_ROSE,
+            MOCCASIN,
+            NAVAJO_WHITE,
+            NAVY,
+            OLD_LACE,
+            OLIVE,
+            OLIVE_DRAB,
+            ORANGE,
+            ORANGE_RED,
+            ORCHID,
+            PALE_GOLDENROD,
+            PALE_GREEN,
+            PALE_TURQUOISE,
+            PALE_VIOLET_RED,
+            PAPAYA_WHIP,
+            PEACH_PUFF,
+            PERU,
+            PINK,
+            PLUM,
+            POWDER_BLUE,
+            PURPLE,
+            REBECCA_PURPLE,
+            RED,
+            ROSY_BROWN,
+            ROYAL_BLUE,
+            SADDLE_BROWN,
+            SALMON,
+            SANDY_BROWN,
+            SEA_GREEN,
+            SEASHELL,
+            SIENNA,
+            SILVER,
+            SKY_BLUE,
+            SLATE_BLUE,
+            SLATE_GRAY,
+            SNOW,
+            SPRING_GREEN,
+            STEEL_BLUE,
+            TAN,
+            TEAL,
+            THISTLE,
+            TOMATO,
+            TURQUOISE,
+            VIOLET,
+            WHEAT,
+            WHITE_SMOKE,
+            YELLOW,
+            YELLOW_GREEN,
+        );
+    };
+}
+
+macro_rules! declare_svg_colors {
+    ($($name:ident),+ $(,)?) => {
+        $(
+            #[doc = concat!("The SVG color `", stringify!($name), "`.")]
+            #[deprecated(note = "use the constant of the same name in `compat::svg` instead")]
+            const $name: Self;
+        )+
+    };
+}
+
+macro_rules! define_svg_colors {
+    ($($name:ident),+ $(,)?) => {
+        $(const $name: Self = svg::$name;)+
+    };
+}
+
+/// The methods and color constants of the old [`Color`] type, implemented for the
+/// new one.
+pub trait LegacyColor: Sized {
+    with_svg_color_names!(declare_svg_colors);
+
+    /// Creates a new opaque color from 8-bit red, green and blue components.
+    #[deprecated(note = "use `Color::from_rgb8` instead")]
+    fn rgb8(r: u8, g: u8, b: u8) -> Self;
+
+    /// Creates a new color from 8-bit red, green, blue and alpha components.
+    #[deprecated(note = "use `Color::from_rgba8` instead")]
+    fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Self;
+
+    /// Creates a new opaque color from red, green and blue components in the range
+    /// `0.0` to `1.0`.
+    #[deprecated(note = "use `Color::new([r, g, b, 1.0])` instead")]
+    fn rgb(r: f64, g: f64, b: f64) -> Self;
+
+    /// Creates a new color from red, green, blue and alpha components in the range
+    /// `0.0` to `1.0`.
+    #[deprecated(note = "use `Color::new([r, g, b, a])` instead")]
+    fn rgba(r: f64, g: f64, b: f64, a: f64) -> Self;
+
+    /// Parses a color from a CSS string, such as `"#ff0000"` or `"red"`.
+    #[deprecated(note = "use `color::parse_color` and `DynamicColor::to_alpha_color` instead")]
+    fn parse(s: &str) -> Option<Self>;
+
+    /// Returns the color with its alpha multiplied by `alpha`.
+    #[deprecated(note = "use `Color::multiply_alpha` instead")]
+    #[must_use]
+    fn with_alpha_factor(self, alpha: f32) -> Self;
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "The old API took `f64` components, which are stored as `f32`."
+)]
+impl LegacyColor for Color {
+    with_svg_color_names!(define_svg_colors);
+
+    fn rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::from_rgb8(r, g, b)
+    }
+
+    fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::from_rgba8(r, g, b, a)
+    }
+
+    fn rgb(r: f64, g: f64, b: f64) -> Self {
+        Self::new([r as f32, g as f32, b as f32, 1.0])
+    }
+
+    fn rgba(r: f64, g: f64, b: f64, a: f64) -> Self {
+        Self::new([r as f32, g as f32, b as f32, a as f32])
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        color::parse_color(s)
+            .ok()
+            .map(|color| color.to_alpha_color::<Srgb>())
+    }
+
+    fn with_alpha_factor(self, alpha: f32) -> Self {
+        self.multiply_alpha(alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![expect(deprecated, reason = "Testing the deprecated API.")]
+
+    use super::{svg, LegacyColor};
+    use crate::Color;
+
+    #[test]
+    fn legacy_color() {
+        assert_eq!(Color::rgb8(255, 0, 0), svg::RED);
+        assert_eq!(Color::ALICE_BLUE, svg::ALICE_BLUE);
+        assert_eq!(Color::REBECCA_PURPLE, Color::rgb8(102, 51, 153));
+        assert_eq!(
+            Color::rgba8(0, 0, 255, 128),
+            Color::from_rgba8(0, 0, 255, 128)
+        );
+        assert_eq!(Color::rgb(0.0, 1.0, 0.0), Color::new([0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(Color::parse("#0000ff"), Some(svg::BLUE));
+        assert_eq!(Color::parse("not a color"), None);
+        assert_eq!(
+            svg::WHITE.with_alpha_factor(0.5),
+            Color::new([1.0, 1.0, 1.0, 0.5])
+        );
+    }
+}
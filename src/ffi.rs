@@ -0,0 +1,563 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! C-compatible mirrors of the vocabulary types.
+//!
+//! The types in this module are `#[repr(C)]`, so bindings for other languages can
+//! construct them directly and convert them to the peniko types with the checked
+//! conversions provided here, rather than re-implementing their semantics.
+//!
+//! Fieldless enums are represented as `u8` values, as described by [`FfiEnum`].
+//! Values received from other languages are validated when converted, so invalid
+//! values produce an [`FfiError`] rather than undefined behavior.
+
+use core::fmt;
+
+use crate::{
+    BlendMode, Color, Compose, Extend, Fill, Image, ImageFormat, ImageQuality, ImageTiling,
+    MinificationQuality, Mix, Point, TileMode, Vec2,
+};
+#[cfg(feature = "gradient")]
+use crate::{ColorStop, GradientKind};
+#[cfg(feature = "gradient")]
+use color::{ColorSpaceTag, DynamicColor, Flags, Missing};
+
+/// Errors which can occur when converting from the C-compatible types.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum FfiError {
+    /// The value doesn't correspond to a variant of the enum with the given name.
+    InvalidEnumValue {
+        /// Name of the enum type.
+        name: &'static str,
+        /// The invalid value.
+        value: u8,
+    },
+    /// The gradient kind doesn't match the requested gradient position.
+    GradientKindMismatch,
+}
+
+impl fmt::Display for FfiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEnumValue { name, value } => {
+                write!(f, "{value} is not a valid value for `{name}`")
+            }
+            Self::GradientKindMismatch => f.write_str("gradient has a different kind"),
+        }
+    }
+}
+
+impl core::error::Error for FfiError {}
+
+/// Fieldless enums with a stable `u8` representation.
+///
+/// This is the representation of the inherent `to_u8` and `from_u8` methods of
+/// each type. For enums declared with `#[repr(u8)]` ([`Extend`], [`Mix`],
+/// [`Compose`] and [`Fill`]), it is the discriminant. For the others, it is the
+/// index of the variant in the `ALL` constant of the type, which only grows at
+/// the end.
+pub trait FfiEnum: Sized {
+    /// Returns the `u8` representation of the value.
+    fn to_ffi(self) -> u8;
+
+    /// Returns the value with the given `u8` representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FfiError::InvalidEnumValue`] if no variant has that representation.
+    fn from_ffi(value: u8) -> Result<Self, FfiError>;
+}
+
+macro_rules! impl_ffi_enum {
+    ($($ty:ident),+) => {
+        $(
+            impl FfiEnum for $ty {
+                fn to_ffi(self) -> u8 {
+                    self.to_u8()
+                }
+
+                fn from_ffi(value: u8) -> Result<Self, FfiError> {
+                    Self::from_u8(value).ok_or(FfiError::InvalidEnumValue {
+                        name: stringify!($ty),
+                        value,
+                    })
+                }
+            }
+        )+
+    };
+}
+
+impl_ffi_enum!(
+    Extend,
+    Mix,
+    Compose,
+    Fill,
+    ImageFormat,
+    ImageQuality,
+    MinificationQuality,
+    TileMode
+);
+
+/// C-compatible mirror of [`BlendMode`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(C)]
+pub struct FfiBlendMode {
+    /// The color mixing function, as a [`Mix`] discriminant.
+    pub mix: u8,
+    /// The layer composition function, as a [`Compose`] discriminant.
+    pub compose: u8,
+}
+
+impl From<BlendMode> for FfiBlendMode {
+    fn from(mode: BlendMode) -> Self {
+        Self {
+            mix: mode.mix.to_ffi(),
+            compose: mode.compose.to_ffi(),
+        }
+    }
+}
+
+impl TryFrom<FfiBlendMode> for BlendMode {
+    type Error = FfiError;
+
+    fn try_from(mode: FfiBlendMode) -> Result<Self, FfiError> {
+        Ok(Self::new(
+            Mix::from_ffi(mode.mix)?,
+            Compose::from_ffi(mode.compose)?,
+        ))
+    }
+}
+
+/// C-compatible mirror of the sampling parameters of an [`Image`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[repr(C)]
+pub struct FfiImageSampler {
+    /// Extend mode in the horizontal direction, as an [`Extend`] discriminant.
+    pub x_extend: u8,
+    /// Extend mode in the vertical direction, as an [`Extend`] discriminant.
+    pub y_extend: u8,
+    /// Sampling quality, as described by [`FfiEnum`].
+    pub quality: u8,
+    /// Minification quality, as described by [`FfiEnum`].
+    pub minification: u8,
+    /// Tile mode in the horizontal direction, as described by [`FfiEnum`].
+    pub x_tile_mode: u8,
+    /// Tile mode in the vertical direction, as described by [`FfiEnum`].
+    pub y_tile_mode: u8,
+    /// Minimum gap between adjacent tiles, as `[x, y]`.
+    pub tile_spacing: [f64; 2],
+    /// Position of the top left corner of one of the tiles, as `[x, y]`.
+    pub tile_origin: [f64; 2],
+    /// An additional alpha multiplier to use with the image.
+    pub alpha: f32,
+    /// Red, green, blue and alpha components of the border color, in sRGB.
+    pub border_color: [f32; 4],
+}
+
+impl FfiImageSampler {
+    /// Returns the sampling parameters of the image.
+    #[must_use]
+    pub fn from_image(image: &Image) -> Self {
+        Self {
+            x_extend: image.x_extend.to_ffi(),
+            y_extend: image.y_extend.to_ffi(),
+            quality: image.quality.to_ffi(),
+            minification: image.minification.to_ffi(),
+            x_tile_mode: image.tiling.x_mode.to_ffi(),
+            y_tile_mode: image.tiling.y_mode.to_ffi(),
+            tile_spacing: [image.tiling.spacing.x, image.tiling.spacing.y],
+            tile_origin: from_point(image.tiling.origin),
+            alpha: image.alpha,
+            border_color: image.border_color.components,
+        }
+    }
+
+    /// Returns the image with these sampling parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FfiError::InvalidEnumValue`] if any of the enum values are invalid.
+    pub fn apply(self, image: Image) -> Result<Image, FfiError> {
+        Ok(image
+            .with_x_extend(Extend::from_ffi(self.x_extend)?)
+            .with_y_extend(Extend::from_ffi(self.y_extend)?)
+            .with_quality(ImageQuality::from_ffi(self.quality)?)
+            .with_minification(MinificationQuality::from_ffi(self.minification)?)
+            .with_tiling(ImageTiling {
+                x_mode: TileMode::from_ffi(self.x_tile_mode)?,
+                y_mode: TileMode::from_ffi(self.y_tile_mode)?,
+                spacing: Vec2::new(self.tile_spacing[0], self.tile_spacing[1]),
+                origin: to_point(self.tile_origin),
+            })
+            .with_alpha(self.alpha)
+            .with_border_color(Color::new(self.border_color)))
+    }
+}
+
+/// Color spaces in the order of their `u8` representation in [`FfiColorStop`].
+///
+/// This only grows at the end, so the representation is stable.
+#[cfg(feature = "gradient")]
+const COLOR_SPACES: [ColorSpaceTag; 16] = [
+    ColorSpaceTag::Srgb,
+    ColorSpaceTag::LinearSrgb,
+    ColorSpaceTag::Lab,
+    ColorSpaceTag::Lch,
+    ColorSpaceTag::Hsl,
+    ColorSpaceTag::Hwb,
+    ColorSpaceTag::Oklab,
+    ColorSpaceTag::Oklch,
+    ColorSpaceTag::DisplayP3,
+    ColorSpaceTag::A98Rgb,
+    ColorSpaceTag::ProphotoRgb,
+    ColorSpaceTag::Rec2020,
+    ColorSpaceTag::AcesCg,
+    ColorSpaceTag::XyzD50,
+    ColorSpaceTag::XyzD65,
+    ColorSpaceTag::Aces2065_1,
+];
+
+/// C-compatible mirror of [`ColorStop`].
+///
+/// The color is stored as non-premultiplied components in its own color space,
+/// which is identified by `color_space`. The values of `color_space` are those of
+/// [`ColorSpaceTag`] in color 0.2, with `0` for sRGB and `7` for Oklch, and stay
+/// the same in later versions. Colors in color spaces without a value are converted
+/// to sRGB.
+///
+/// NaN is used where a value is absent: for a [missing](Missing) component of the
+/// color, and for a stop without an [interpolation hint](ColorStop::hint).
+#[cfg(feature = "gradient")]
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct FfiColorStop {
+    /// Normalized offset of the stop.
+    pub offset: f32,
+    /// Components of the color, with alpha last.
+    pub color: [f32; 4],
+    /// Color space of the components.
+    pub color_space: u8,
+    /// Position of the interpolation hint between this stop and the next, or NaN.
+    pub hint: f32,
+}
+
+#[cfg(feature = "gradient")]
+impl PartialEq for FfiColorStop {
+    fn eq(&self, other: &Self) -> bool {
+        // Compare bit patterns, so that absent values are equal to each other.
+        self.offset.to_bits() == other.offset.to_bits()
+            && self.color.map(f32::to_bits) == other.color.map(f32::to_bits)
+            && self.color_space == other.color_space
+            && self.hint.to_bits() == other.hint.to_bits()
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl From<ColorStop> for FfiColorStop {
+    fn from(stop: ColorStop) -> Self {
+        let (color_space, color) = match COLOR_SPACES.iter().position(|&cs| cs == stop.color.cs) {
+            Some(index) => (index, stop.color),
+            None => (0, stop.color.convert(ColorSpaceTag::Srgb)),
+        };
+        let missing = color.flags.missing();
+        let mut components = color.components;
+        for (ix, component) in components.iter_mut().enumerate() {
+            if missing.contains(ix) {
+                *component = f32::NAN;
+            }
+        }
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "There are fewer than 256 color spaces."
+        )]
+        Self {
+            offset: stop.offset,
+            color: components,
+            color_space: color_space as u8,
+            hint: stop.hint.unwrap_or(f32::NAN),
+        }
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl TryFrom<FfiColorStop> for ColorStop {
+    type Error = FfiError;
+
+    fn try_from(stop: FfiColorStop) -> Result<Self, FfiError> {
+        let cs =
+            *COLOR_SPACES
+                .get(usize::from(stop.color_space))
+                .ok_or(FfiError::InvalidEnumValue {
+                    name: "ColorSpaceTag",
+                    value: stop.color_space,
+                })?;
+        let mut missing = Missing::default();
+        let mut components = stop.color;
+        for (ix, component) in components.iter_mut().enumerate() {
+            if component.is_nan() {
+                missing.insert(ix);
+                *component = 0.0;
+            }
+        }
+        Ok(Self {
+            offset: stop.offset,
+            color: DynamicColor {
+                cs,
+                flags: Flags::from_missing(missing),
+                components,
+            },
+            hint: (!stop.hint.is_nan()).then_some(stop.hint),
+        })
+    }
+}
+
+/// C-compatible mirror of the position of a [linear gradient](GradientKind::Linear).
+#[cfg(feature = "gradient")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[repr(C)]
+pub struct FfiLinearGradientPosition {
+    /// Starting point, as `[x, y]`.
+    pub start: [f64; 2],
+    /// Ending point, as `[x, y]`.
+    pub end: [f64; 2],
+}
+
+/// C-compatible mirror of the position of a [radial gradient](GradientKind::Radial).
+#[cfg(feature = "gradient")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[repr(C)]
+pub struct FfiRadialGradientPosition {
+    /// Center of start circle, as `[x, y]`.
+    pub start_center: [f64; 2],
+    /// Radius of start circle.
+    pub start_radius: f32,
+    /// Center of end circle, as `[x, y]`.
+    pub end_center: [f64; 2],
+    /// Radius of end circle.
+    pub end_radius: f32,
+}
+
+/// C-compatible mirror of the position of a [sweep gradient](GradientKind::Sweep).
+#[cfg(feature = "gradient")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[repr(C)]
+pub struct FfiSweepGradientPosition {
+    /// Center point, as `[x, y]`.
+    pub center: [f64; 2],
+    /// Start angle of the sweep, in radians.
+    pub start_angle: f32,
+    /// End angle of the sweep, in radians.
+    pub end_angle: f32,
+}
+
+fn to_point([x, y]: [f64; 2]) -> Point {
+    Point::new(x, y)
+}
+
+fn from_point(point: Point) -> [f64; 2] {
+    [point.x, point.y]
+}
+
+#[cfg(feature = "gradient")]
+impl From<FfiLinearGradientPosition> for GradientKind {
+    fn from(position: FfiLinearGradientPosition) -> Self {
+        Self::Linear {
+            start: to_point(position.start),
+            end: to_point(position.end),
+        }
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl From<FfiRadialGradientPosition> for GradientKind {
+    fn from(position: FfiRadialGradientPosition) -> Self {
+        Self::Radial {
+            start_center: to_point(position.start_center),
+            start_radius: position.start_radius,
+            end_center: to_point(position.end_center),
+            end_radius: position.end_radius,
+        }
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl From<FfiSweepGradientPosition> for GradientKind {
+    fn from(position: FfiSweepGradientPosition) -> Self {
+        Self::Sweep {
+            center: to_point(position.center),
+            start_angle: position.start_angle,
+            end_angle: position.end_angle,
+        }
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl TryFrom<GradientKind> for FfiLinearGradientPosition {
+    type Error = FfiError;
+
+    fn try_from(kind: GradientKind) -> Result<Self, FfiError> {
+        match kind {
+            GradientKind::Linear { start, end } => Ok(Self {
+                start: from_point(start),
+                end: from_point(end),
+            }),
+            _ => Err(FfiError::GradientKindMismatch),
+        }
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl TryFrom<GradientKind> for FfiRadialGradientPosition {
+    type Error = FfiError;
+
+    fn try_from(kind: GradientKind) -> Result<Self, FfiError> {
+        match kind {
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => Ok(Self {
+                start_center: from_point(start_center),
+                start_radius,
+                end_center: from_point(end_center),
+                end_radius,
+            }),
+            _ => Err(FfiError::GradientKindMismatch),
+        }
+    }
+}
+
+#[cfg(feature = "gradient")]
+impl TryFrom<GradientKind> for FfiSweepGradientPosition {
+    type Error = FfiError;
+
+    fn try_from(kind: GradientKind) -> Result<Self, FfiError> {
+        match kind {
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => Ok(Self {
+                center: from_point(center),
+                start_angle,
+                end_angle,
+            }),
+            _ => Err(FfiError::GradientKindMismatch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FfiBlendMode, FfiEnum, FfiError, FfiImageSampler};
+    use crate::{
+        BlendMode, Blob, Compose, Extend, Fill, Image, ImageFormat, ImageQuality,
+        MinificationQuality, Mix, TileMode,
+    };
+
+    fn round_trip<T: FfiEnum + Copy + PartialEq + core::fmt::Debug>(values: &[T]) {
+        for &value in values {
+            assert_eq!(T::from_ffi(value.to_ffi()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn enums() {
+        round_trip(&Extend::ALL);
+        round_trip(&Mix::ALL);
+        round_trip(&Compose::ALL);
+        round_trip(&Fill::ALL);
+        round_trip(ImageFormat::ALL);
+        round_trip(&ImageQuality::ALL);
+        round_trip(&MinificationQuality::ALL);
+        round_trip(&TileMode::ALL);
+        assert_eq!(Mix::Clip.to_ffi(), 128);
+        assert_eq!(Fill::EvenOdd.to_ffi(), 1);
+        assert_eq!(
+            Mix::from_ffi(100),
+            Err(FfiError::InvalidEnumValue {
+                name: "Mix",
+                value: 100
+            })
+        );
+        assert!(TileMode::from_ffi(3).is_err());
+    }
+
+    #[test]
+    fn blend_mode_and_sampler() {
+        let mode = BlendMode::new(Mix::Screen, Compose::Xor);
+        assert_eq!(BlendMode::try_from(FfiBlendMode::from(mode)), Ok(mode));
+
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        let sampler = FfiImageSampler {
+            x_extend: Extend::Repeat.to_ffi(),
+            y_extend: Extend::Border.to_ffi(),
+            quality: ImageQuality::High.to_ffi(),
+            minification: MinificationQuality::Trilinear.to_ffi(),
+            x_tile_mode: TileMode::Space.to_ffi(),
+            y_tile_mode: TileMode::Round.to_ffi(),
+            tile_spacing: [2.0, 4.0],
+            tile_origin: [-1.0, 0.5],
+            alpha: 0.5,
+            border_color: [1.0, 0.0, 0.0, 1.0],
+        };
+        let image = sampler.apply(image).unwrap();
+        assert_eq!(FfiImageSampler::from_image(&image), sampler);
+        let invalid = FfiImageSampler {
+            quality: 7,
+            ..sampler
+        };
+        assert!(invalid.apply(image).is_err());
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn gradients() {
+        use super::{FfiColorStop, FfiLinearGradientPosition, FfiSweepGradientPosition};
+        use crate::{Color, ColorStop, GradientKind};
+        use color::{ColorSpaceTag, DynamicColor, Flags, Missing};
+
+        let stop = ColorStop::from((0.25, Color::new([1.0, 0.5, 0.0, 0.75])));
+        let ffi = FfiColorStop::from(stop);
+        assert_eq!(ffi.color, [1.0, 0.5, 0.0, 0.75]);
+        assert_eq!((ffi.color_space, ffi.hint.is_nan()), (0, true));
+        assert_eq!(ColorStop::try_from(ffi), Ok(stop));
+
+        // Hints, color spaces and missing components are preserved.
+        let oklch = DynamicColor {
+            cs: ColorSpaceTag::Oklch,
+            flags: Flags::from_missing(Missing::single(1)),
+            components: [0.5, 0.0, 120.0, 0.5],
+        };
+        let hinted = ColorStop::from((0.5, oklch)).with_hint(0.75);
+        let hinted_ffi = FfiColorStop::from(hinted);
+        assert_eq!(hinted_ffi.color_space, 7);
+        assert!(hinted_ffi.color[1].is_nan());
+        assert_eq!(hinted_ffi.hint, 0.75);
+        assert_eq!(ColorStop::try_from(hinted_ffi), Ok(hinted));
+        let invalid = FfiColorStop {
+            color_space: 16,
+            ..hinted_ffi
+        };
+        assert_eq!(
+            ColorStop::try_from(invalid),
+            Err(FfiError::InvalidEnumValue {
+                name: "ColorSpaceTag",
+                value: 16
+            })
+        );
+
+        let linear = FfiLinearGradientPosition {
+            start: [0.0, 1.0],
+            end: [2.0, 3.0],
+        };
+        let kind = GradientKind::from(linear);
+        assert_eq!(FfiLinearGradientPosition::try_from(kind), Ok(linear));
+        assert_eq!(
+            FfiSweepGradientPosition::try_from(kind),
+            Err(FfiError::GradientKindMismatch)
+        );
+    }
+}
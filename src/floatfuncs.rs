@@ -0,0 +1,61 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Shims for math functions that ordinarily come from std.
+//!
+//! Code which needs a float function that isn't available in `core` should import
+//! `FloatFuncs` when the `std` feature is disabled, adding the function below if
+//! necessary, so that it uses libm rather than silently requiring std.
+
+/// Defines a trait that chooses between libstd or libm implementations of float methods.
+macro_rules! define_float_funcs {
+    ($(
+        fn $name:ident(self $(,$arg:ident: $arg_ty:ty)*) -> $ret:ty
+        => $lfname:ident/$lfname32:ident;
+    )+) => {
+
+        /// Since core doesn't depend upon libm, this provides libm implementations
+        /// of float functions which are typically provided by the std library, when
+        /// the `std` feature is not enabled.
+        ///
+        /// For documentation see the respective functions in the std library.
+        #[cfg(not(feature = "std"))]
+        #[expect(dead_code, reason = "not all functions are used in every feature combination")]
+        pub(crate) trait FloatFuncs : Sized {
+            $(fn $name(self $(,$arg: $arg_ty)*) -> $ret;)+
+        }
+
+        #[cfg(not(feature = "std"))]
+        impl FloatFuncs for f32 {
+            $(fn $name(self $(,$arg: $arg_ty)*) -> $ret {
+                #[cfg(feature = "libm")]
+                return libm::$lfname32(self $(,$arg)*);
+
+                #[cfg(not(feature = "libm"))]
+                compile_error!("peniko requires either the `std` or `libm` feature")
+            })+
+        }
+
+        #[cfg(not(feature = "std"))]
+        impl FloatFuncs for f64 {
+            $(fn $name(self $(,$arg: $arg_ty)*) -> $ret {
+                #[cfg(feature = "libm")]
+                return libm::$lfname(self $(,$arg)*);
+
+                #[cfg(not(feature = "libm"))]
+                compile_error!("peniko requires either the `std` or `libm` feature")
+            })+
+        }
+    }
+}
+
+define_float_funcs! {
+    fn atan2(self, other: Self) -> Self => atan2/atan2f;
+    fn cos(self) -> Self => cos/cosf;
+    fn floor(self) -> Self => floor/floorf;
+    fn ln(self) -> Self => log/logf;
+    fn powf(self, n: Self) -> Self => pow/powf;
+    fn round(self) -> Self => round/roundf;
+    fn sin(self) -> Self => sin/sinf;
+    fn sqrt(self) -> Self => sqrt/sqrtf;
+}
@@ -3,6 +3,12 @@
 
 use super::Blob;
 
+use core::hash::{Hash, Hasher};
+
+extern crate alloc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
 /// Owned shareable font resource.
 #[derive(Clone, PartialEq, Debug)]
 pub struct Font {
@@ -19,3 +25,167 @@ impl Font {
         Self { data, index }
     }
 }
+
+impl Eq for Font {}
+
+impl Hash for Font {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.id().hash(state);
+        self.index.hash(state);
+    }
+}
+
+/// Inclusive range of Unicode code points.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct UnicodeRange {
+    /// First code point in the range.
+    pub start: u32,
+    /// Last code point in the range.
+    pub end: u32,
+}
+
+impl UnicodeRange {
+    /// Creates a new range from the first and last code point.
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns `true` if the range contains the given character.
+    #[must_use]
+    pub const fn contains(&self, ch: char) -> bool {
+        self.start <= ch as u32 && ch as u32 <= self.end
+    }
+}
+
+/// A font in a [`FontStack`], with an optional hint of the characters it covers.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FontStackEntry {
+    /// The font.
+    pub font: Font,
+    /// Ranges of characters which the font is expected to cover, like the CSS
+    /// [`unicode-range`] descriptor.
+    ///
+    /// This is only a hint for skipping fonts which can't cover a character, and
+    /// does not guarantee that the font has glyphs for every character in the ranges.
+    /// When empty, the font may cover any character.
+    ///
+    /// [`unicode-range`]: https://drafts.csswg.org/css-fonts/#unicode-range-desc
+    pub coverage: Vec<UnicodeRange>,
+}
+
+impl FontStackEntry {
+    /// Creates an entry for a font which may cover any character.
+    #[must_use]
+    pub fn new(font: Font) -> Self {
+        Self {
+            font,
+            coverage: Vec::new(),
+        }
+    }
+
+    /// Builder method for setting the [coverage hint](Self::coverage).
+    #[must_use]
+    pub fn with_coverage(mut self, coverage: impl IntoIterator<Item = UnicodeRange>) -> Self {
+        self.coverage = coverage.into_iter().collect();
+        self
+    }
+
+    /// Returns `true` if the coverage hint allows the font to contain the character.
+    #[must_use]
+    pub fn may_cover(&self, ch: char) -> bool {
+        self.coverage.is_empty() || self.coverage.iter().any(|range| range.contains(ch))
+    }
+}
+
+impl From<Font> for FontStackEntry {
+    fn from(font: Font) -> Self {
+        Self::new(font)
+    }
+}
+
+/// Ordered list of fonts, consisting of a primary font followed by its fallbacks.
+///
+/// The list is reference counted, so cloning a stack is cheap and the same stack can
+/// be shared by every run of text which uses it. Two stacks compare equal and hash
+/// identically when they contain the same fonts (by [blob id](Blob::id) and index)
+/// with the same coverage, so a stack can be used as a cache key.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FontStack(Arc<[FontStackEntry]>);
+
+impl FontStack {
+    /// Creates a new stack from the given fonts, in order of preference.
+    #[must_use]
+    pub fn new(entries: impl IntoIterator<Item = impl Into<FontStackEntry>>) -> Self {
+        Self(entries.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the number of fonts in the stack.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the stack contains no fonts.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the entries of the stack, in order of preference.
+    #[must_use]
+    pub fn entries(&self) -> &[FontStackEntry] {
+        &self.0
+    }
+
+    /// Returns the primary font of the stack.
+    #[must_use]
+    pub fn primary(&self) -> Option<&Font> {
+        self.0.first().map(|entry| &entry.font)
+    }
+
+    /// Returns an iterator over the fonts which may cover the given character,
+    /// in order of preference.
+    pub fn candidates(&self, ch: char) -> impl Iterator<Item = &Font> + '_ {
+        self.0
+            .iter()
+            .filter(move |entry| entry.may_cover(ch))
+            .map(|entry| &entry.font)
+    }
+}
+
+impl From<Font> for FontStack {
+    fn from(font: Font) -> Self {
+        Self::new([font])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Font, FontStack, FontStackEntry, UnicodeRange};
+    use crate::Blob;
+    use std::collections::HashSet;
+
+    #[test]
+    fn font_stack() {
+        let latin = Font::new(Blob::from(vec![0_u8]), 0);
+        let emoji = Font::new(Blob::from(vec![1_u8]), 0);
+        let stack = FontStack::new([
+            FontStackEntry::new(latin.clone()).with_coverage([UnicodeRange::new(0x0000, 0x024F)]),
+            FontStackEntry::new(emoji.clone()),
+        ]);
+        assert_eq!(stack.primary(), Some(&latin));
+        assert_eq!(stack.candidates('a').count(), 2);
+        assert_eq!(stack.candidates('😀').collect::<Vec<_>>(), [&emoji]);
+
+        // Stacks are compared by content, so equal stacks share a cache entry.
+        let rebuilt = FontStack::new([
+            FontStackEntry::new(latin).with_coverage([UnicodeRange::new(0x0000, 0x024F)]),
+            FontStackEntry::new(emoji),
+        ]);
+        let mut cache = HashSet::new();
+        cache.insert(stack.clone());
+        assert!(cache.contains(&rebuilt));
+        assert_ne!(stack, FontStack::from(stack.entries()[1].font.clone()));
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The geometry types used by brushes.
+//!
+//! These are the [`kurbo`] types when the `kurbo` feature is enabled. Otherwise,
+//! minimal stand-ins are provided so that brushes can be used without depending
+//! on kurbo. The API of each stand-in is a subset of that of the corresponding kurbo
+//! type, so code written against them continues to compile when the feature is
+//! enabled elsewhere in the dependency graph.
+
+#[cfg(feature = "kurbo")]
+pub use kurbo::{Point, Vec2};
+
+#[cfg(not(feature = "kurbo"))]
+pub use fallback::{Point, Vec2};
+
+#[cfg(not(feature = "kurbo"))]
+mod fallback {
+    /// A 2D point.
+    ///
+    /// This is a stand-in for `kurbo::Point`, used when the `kurbo` feature is disabled.
+    #[derive(Clone, Copy, Default, PartialEq, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Point {
+        /// The x coordinate.
+        pub x: f64,
+        /// The y coordinate.
+        pub y: f64,
+    }
+
+    impl Point {
+        /// The point (0, 0).
+        pub const ZERO: Self = Self::new(0., 0.);
+
+        /// The point at the origin; (0, 0).
+        pub const ORIGIN: Self = Self::new(0., 0.);
+
+        /// Create a new `Point` with the provided `x` and `y` coordinates.
+        #[inline]
+        pub const fn new(x: f64, y: f64) -> Self {
+            Self { x, y }
+        }
+
+        /// Linearly interpolate between two points.
+        #[inline]
+        #[must_use]
+        pub fn lerp(self, other: Self, t: f64) -> Self {
+            Self::new(
+                self.x + t * (other.x - self.x),
+                self.y + t * (other.y - self.y),
+            )
+        }
+    }
+
+    impl From<(f64, f64)> for Point {
+        #[inline]
+        fn from(v: (f64, f64)) -> Self {
+            Self { x: v.0, y: v.1 }
+        }
+    }
+
+    impl From<Point> for (f64, f64) {
+        #[inline]
+        fn from(v: Point) -> (f64, f64) {
+            (v.x, v.y)
+        }
+    }
+
+    /// A 2D vector.
+    ///
+    /// This is a stand-in for `kurbo::Vec2`, used when the `kurbo` feature is disabled.
+    #[derive(Clone, Copy, Default, PartialEq, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Vec2 {
+        /// The x-coordinate.
+        pub x: f64,
+        /// The y-coordinate.
+        pub y: f64,
+    }
+
+    impl Vec2 {
+        /// The vector (0, 0).
+        pub const ZERO: Self = Self::new(0., 0.);
+
+        /// Create a new vector.
+        #[inline]
+        pub const fn new(x: f64, y: f64) -> Self {
+            Self { x, y }
+        }
+    }
+
+    impl From<(f64, f64)> for Vec2 {
+        #[inline]
+        fn from(v: (f64, f64)) -> Self {
+            Self { x: v.0, y: v.1 }
+        }
+    }
+
+    impl From<Vec2> for (f64, f64) {
+        #[inline]
+        fn from(v: Vec2) -> (f64, f64) {
+            (v.x, v.y)
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Defines the degree to which glyph outlines are adjusted to the pixel grid.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hinting {
+    /// Outlines are rendered as designed, without any grid fitting.
+    #[default]
+    None,
+    /// Outlines are only fitted to the grid vertically.
+    ///
+    /// This improves contrast of horizontal stems while preserving glyph shapes
+    /// and advances.
+    Slight,
+    /// Outlines are fitted to the grid in both directions.
+    Full,
+}
+
+impl_u8_conversions!(Hinting {
+    None = 0,
+    Slight = 1,
+    Full = 2,
+});
+
+/// Defines how finely glyph positions are quantized along the baseline.
+///
+/// Glyphs are usually cached as rendered bitmaps, so every distinct subpixel offset
+/// of a glyph requires a separate cache entry. Coarser quantization trades spacing
+/// accuracy for better cache reuse.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubpixelPositioning {
+    /// Glyph positions are rounded to whole pixels.
+    None,
+    /// Glyph positions are rounded to the nearest half pixel.
+    Half,
+    /// Glyph positions are rounded to the nearest quarter pixel.
+    #[default]
+    Quarter,
+}
+
+impl_u8_conversions!(SubpixelPositioning {
+    None = 0,
+    Half = 1,
+    Quarter = 2,
+});
+
+impl SubpixelPositioning {
+    /// Returns the number of distinct glyph positions within a single pixel.
+    ///
+    /// A position `x` should be quantized to `round(x * steps) / steps`.
+    #[must_use]
+    pub const fn steps(self) -> u32 {
+        match self {
+            Self::None => 1,
+            Self::Half => 2,
+            Self::Quarter => 4,
+        }
+    }
+}
+
+/// Defines the antialiasing method used when rendering glyphs.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GlyphAntialiasing {
+    /// Glyphs are rendered without antialiasing.
+    None,
+    /// Glyphs are rendered with grayscale coverage.
+    #[default]
+    Grayscale,
+    /// Glyphs are rendered with per-channel coverage, taking advantage of the
+    /// subpixel layout of LCD displays.
+    ///
+    /// Renderers which cannot produce per-channel coverage (for example, when
+    /// drawing onto a transparent background) should use grayscale instead.
+    Lcd,
+}
+
+impl_u8_conversions!(GlyphAntialiasing {
+    None = 0,
+    Grayscale = 1,
+    Lcd = 2,
+});
+
+/// Options which control how glyphs are rendered.
+///
+/// This is shared by text layout, which needs to know how glyph positions will be
+/// quantized, and renderers, which need to know how to rasterize the glyphs.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlyphRenderingOptions {
+    /// Hinting mode.
+    pub hinting: Hinting,
+    /// Quantization of glyph positions.
+    pub subpixel_positioning: SubpixelPositioning,
+    /// Antialiasing method.
+    pub antialiasing: GlyphAntialiasing,
+}
+
+impl GlyphRenderingOptions {
+    /// Creates new glyph rendering options.
+    #[must_use]
+    pub const fn new(
+        hinting: Hinting,
+        subpixel_positioning: SubpixelPositioning,
+        antialiasing: GlyphAntialiasing,
+    ) -> Self {
+        Self {
+            hinting,
+            subpixel_positioning,
+            antialiasing,
+        }
+    }
+
+    /// Builder method for setting the [hinting mode](Hinting).
+    #[must_use]
+    pub const fn with_hinting(mut self, hinting: Hinting) -> Self {
+        self.hinting = hinting;
+        self
+    }
+
+    /// Builder method for setting the [subpixel positioning](SubpixelPositioning) mode.
+    #[must_use]
+    pub const fn with_subpixel_positioning(
+        mut self,
+        subpixel_positioning: SubpixelPositioning,
+    ) -> Self {
+        self.subpixel_positioning = subpixel_positioning;
+        self
+    }
+
+    /// Builder method for setting the [antialiasing method](GlyphAntialiasing).
+    #[must_use]
+    pub const fn with_antialiasing(mut self, antialiasing: GlyphAntialiasing) -> Self {
+        self.antialiasing = antialiasing;
+        self
+    }
+}
@@ -1,18 +1,31 @@
 // Copyright 2022 the Peniko Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::Extend;
+use super::{Blob, Extend, Point};
 
 use color::{
     cache_key::{BitEq, BitHash},
-    AlphaColor, ColorSpace, ColorSpaceTag, DynamicColor, HueDirection, OpaqueColor,
+    AlphaColor, ColorSpace, ColorSpaceTag, DynamicColor, Flags, HueDirection, OpaqueColor,
 };
-use kurbo::Point;
 use smallvec::SmallVec;
 
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::color_space::{css_color_space_from_name, css_color_space_name};
+#[cfg(not(feature = "std"))]
+use crate::floatfuncs::FloatFuncs as _;
+
+#[cfg(feature = "kurbo")]
+use kurbo::{Affine, Rect, Vec2};
+
 use core::{
-    hash::Hasher,
-    ops::{Deref, DerefMut},
+    cmp::Ordering,
+    f32::consts::{FRAC_PI_2, TAU},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut, Range},
 };
 
 /// The default for `Gradient::interpolation_cs`.
@@ -30,18 +43,50 @@ pub struct ColorStop {
     pub offset: f32,
     /// Color at the specified offset.
     pub color: DynamicColor,
+    /// Offset of the interpolation hint between this stop and the next one.
+    ///
+    /// This is the position where the color is halfway between the colors of the two
+    /// stops, as with a [CSS transition hint]. When `None`, the halfway point is midway
+    /// between the two stops, so the color transitions linearly. The hint has no effect
+    /// on the last stop.
+    ///
+    /// See [`interpolation_weight`](Self::interpolation_weight) for how this is applied.
+    ///
+    /// [CSS transition hint]: https://drafts.csswg.org/css-images-4/#color-transition-hint
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub hint: Option<f32>,
 }
 
 impl BitHash for ColorStop {
     fn bit_hash<H: Hasher>(&self, state: &mut H) {
         self.offset.bit_hash(state);
         self.color.bit_hash(state);
+        self.hint.map(f32::to_bits).hash(state);
     }
 }
 
 impl BitEq for ColorStop {
     fn bit_eq(&self, other: &Self) -> bool {
-        self.offset.bit_eq(&other.offset) && self.color.bit_eq(&other.color)
+        self.offset.bit_eq(&other.offset)
+            && self.color.bit_eq(&other.color)
+            && self.hint.map(f32::to_bits) == other.hint.map(f32::to_bits)
+    }
+}
+
+/// Formats the color as its components, as `color` does not support `defmt`.
+#[cfg(feature = "defmt")]
+impl defmt::Format for ColorStop {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "ColorStop {{ offset: {}, color: {}, hint: {} }}",
+            self.offset,
+            self.color.components,
+            self.hint
+        );
     }
 }
 
@@ -50,8 +95,8 @@ impl ColorStop {
     #[must_use]
     pub const fn with_alpha(self, alpha: f32) -> Self {
         Self {
-            offset: self.offset,
             color: self.color.with_alpha(alpha),
+            ..self
         }
     }
 
@@ -62,10 +107,131 @@ impl ColorStop {
     #[must_use]
     pub const fn multiply_alpha(self, alpha: f32) -> Self {
         Self {
-            offset: self.offset,
             color: self.color.multiply_alpha(alpha),
+            ..self
+        }
+    }
+
+    /// Returns the color stop with the [interpolation hint](Self::hint) set to `hint`.
+    #[must_use]
+    pub const fn with_hint(self, hint: f32) -> Self {
+        Self {
+            hint: Some(hint),
+            ..self
+        }
+    }
+
+    /// Returns the components of the color in `cs` with premultiplied alpha, as they
+    /// are interpolated by [`Gradient`]s with that [interpolation color
+    /// space](Gradient::interpolation_cs).
+    ///
+    /// As in CSS, the hue component of a color space with one isn't premultiplied.
+    /// Renderers can use this to bake color ramps without reimplementing the
+    /// conversion.
+    #[must_use]
+    pub fn to_premultiplied(&self, cs: ColorSpaceTag) -> [f32; 4] {
+        let mut components = self.color.convert(cs).components;
+        let alpha = components[3];
+        let hue = hue_channel(cs);
+        for (i, component) in components[..3].iter_mut().enumerate() {
+            if Some(i) != hue {
+                *component *= alpha;
+            }
+        }
+        components
+    }
+
+    /// Creates a stop from the components of a color in `cs` with premultiplied
+    /// alpha, such as those produced by [`to_premultiplied`](Self::to_premultiplied).
+    ///
+    /// The components of a fully transparent color can't be recovered, so they are
+    /// set to zero.
+    #[must_use]
+    pub fn from_premultiplied(offset: f32, cs: ColorSpaceTag, components: [f32; 4]) -> Self {
+        let mut components = components;
+        let alpha = components[3];
+        let hue = hue_channel(cs);
+        for (i, component) in components[..3].iter_mut().enumerate() {
+            if Some(i) != hue {
+                *component = if alpha == 0.0 {
+                    0.0
+                } else {
+                    *component / alpha
+                };
+            }
+        }
+        Self {
+            offset,
+            color: DynamicColor {
+                cs,
+                flags: Flags::default(),
+                components,
+            },
+            hint: None,
+        }
+    }
+
+    /// Returns the weight of the color of `next` at `offset`, for the transition
+    /// from this stop to the `next` stop.
+    ///
+    /// The color at `offset` is the interpolation between the colors of the two stops
+    /// with this weight, where `0` is the color of this stop and `1` is the color of
+    /// `next`. Offsets outside of the range between the stops are clamped to it.
+    ///
+    /// Without an [interpolation hint](Self::hint), the weight increases linearly.
+    /// Otherwise, it follows the curve defined for [CSS transition hints], which passes
+    /// through `0.5` at the hint.
+    ///
+    /// [CSS transition hints]: https://drafts.csswg.org/css-images-4/#coloring-gradient-line
+    #[must_use]
+    pub fn interpolation_weight(&self, next: &Self, offset: f32) -> f32 {
+        let span = next.offset - self.offset;
+        if span.is_nan() || span <= 0.0 {
+            return if offset < next.offset { 0.0 } else { 1.0 };
+        }
+        let t = ((offset - self.offset) / span).clamp(0.0, 1.0);
+        let Some(hint) = self.hint else {
+            return t;
+        };
+        let hint = (hint - self.offset) / span;
+        if hint <= 0.0 {
+            if t > 0.0 {
+                1.0
+            } else {
+                0.0
+            }
+        } else if hint >= 1.0 {
+            if t < 1.0 {
+                0.0
+            } else {
+                1.0
+            }
+        } else {
+            t.powf(0.5_f32.ln() / hint.ln())
         }
     }
+
+    /// Returns the mean of [`interpolation_weight`](Self::interpolation_weight) over
+    /// the range from `start` to `end`, which must be a non-empty range between the
+    /// offsets of this stop and `next`.
+    fn mean_interpolation_weight(&self, next: &Self, start: f32, end: f32) -> f32 {
+        let span = next.offset - self.offset;
+        let (t0, t1) = ((start - self.offset) / span, (end - self.offset) / span);
+        let exponent = match self.hint {
+            None => 1.0,
+            Some(hint) => {
+                let hint = (hint - self.offset) / span;
+                if hint <= 0.0 {
+                    return 1.0;
+                } else if hint >= 1.0 {
+                    return 0.0;
+                }
+                0.5_f32.ln() / hint.ln()
+            }
+        };
+        // The integral of `t^exponent`, divided by the length of the range.
+        (t1.powf(exponent + 1.0) - t0.powf(exponent + 1.0)) / ((exponent + 1.0) * (t1 - t0))
+    }
 }
 
 impl<CS: ColorSpace> From<(f32, AlphaColor<CS>)> for ColorStop {
@@ -73,6 +239,7 @@ impl<CS: ColorSpace> From<(f32, AlphaColor<CS>)> for ColorStop {
         Self {
             offset: pair.0,
             color: DynamicColor::from_alpha_color(pair.1),
+            hint: None,
         }
     }
 }
@@ -82,6 +249,7 @@ impl From<(f32, DynamicColor)> for ColorStop {
         Self {
             offset: pair.0,
             color: pair.1,
+            hint: None,
         }
     }
 }
@@ -91,25 +259,63 @@ impl<CS: ColorSpace> From<(f32, OpaqueColor<CS>)> for ColorStop {
         Self {
             offset: pair.0,
             color: DynamicColor::from_alpha_color(pair.1.with_alpha(1.)),
+            hint: None,
         }
     }
 }
 
 /// Collection of color stops.
-#[derive(Clone, PartialEq, Debug, Default)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ColorStops(pub SmallVec<[ColorStop; 4]>);
+///
+/// Stops are expected to be sorted by offset. When several stops share an offset,
+/// their order is significant: the first of them defines the color when approaching
+/// the offset from below, and the last defines the color from the offset onwards. Any
+/// stops between those two have no visible effect. This is how a hard transition between
+/// two colors is expressed.
+///
+/// The [`sort_by_offset`](Self::sort_by_offset) and [`normalize`](Self::normalize) methods
+/// preserve the relative order of stops at equal offsets, so that this meaning is never
+/// changed by sorting.
+///
+/// # Storage
+///
+/// Stops are stored inline when there are few of them, and on the heap otherwise.
+/// Stops created from [`SharedColorStops`] keep referring to its storage, so that
+/// cloning them (and the gradients and brushes which hold them) only bumps a
+/// reference count. Shared stops are copied the first time they are mutated.
+#[derive(Clone, Debug, Default)]
+pub struct ColorStops(StopStorage);
+
+#[derive(Clone, Debug)]
+enum StopStorage {
+    Inline(SmallVec<[ColorStop; 4]>),
+    Shared(SharedColorStops),
+}
+
+impl Default for StopStorage {
+    fn default() -> Self {
+        Self::Inline(SmallVec::new())
+    }
+}
 
 impl Deref for ColorStops {
-    type Target = SmallVec<[ColorStop; 4]>;
+    type Target = [ColorStop];
     fn deref(&self) -> &Self::Target {
-        &self.0
+        match &self.0 {
+            StopStorage::Inline(stops) => stops,
+            StopStorage::Shared(stops) => stops,
+        }
     }
 }
 
 impl DerefMut for ColorStops {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        self.to_mut()
+    }
+}
+
+impl PartialEq for ColorStops {
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
     }
 }
 
@@ -118,27 +324,411 @@ impl ColorStops {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Returns `true` if the stops refer to the storage of a [`SharedColorStops`].
+    #[must_use]
+    pub fn is_shared(&self) -> bool {
+        matches!(self.0, StopStorage::Shared(_))
+    }
+
+    /// Returns the stops as a mutable vector, copying them first if they are
+    /// [shared](Self::is_shared).
+    pub fn to_mut(&mut self) -> &mut SmallVec<[ColorStop; 4]> {
+        if let StopStorage::Shared(stops) = &self.0 {
+            self.0 = StopStorage::Inline(SmallVec::from_slice(stops));
+        }
+        match &mut self.0 {
+            StopStorage::Inline(stops) => stops,
+            StopStorage::Shared(_) => unreachable!("shared stops were copied above"),
+        }
+    }
+
+    /// Appends a color stop.
+    pub fn push(&mut self, stop: ColorStop) {
+        self.to_mut().push(stop);
+    }
+
+    /// Removes all stops.
+    pub fn clear(&mut self) {
+        self.0 = StopStorage::default();
+    }
+
+    /// Sorts the stops by offset.
+    ///
+    /// This sort is stable: stops that share an offset keep their relative order. Stops
+    /// with a NaN offset are moved to the end of the collection.
+    pub fn sort_by_offset(&mut self) {
+        self.sort_by(|a, b| compare_offsets(a.offset, b.offset));
+    }
+
+    /// Reverses the order of the stops, mapping each offset `t` to `1 - t`.
+    ///
+    /// Hints are moved so that they stay between the same pairs of stops.
+    #[cfg(feature = "kurbo")]
+    fn reverse_offsets(&mut self) {
+        let hints: SmallVec<[Option<f32>; 4]> = self.iter().map(|stop| stop.hint).collect();
+        self.reverse();
+        let count = self.len();
+        for (ix, stop) in self.iter_mut().enumerate() {
+            stop.offset = 1.0 - stop.offset;
+            // The stop which is now at `ix` was at `count - 1 - ix`, and is followed by
+            // the stop which preceded it.
+            stop.hint = (ix + 1 < count)
+                .then(|| hints[count - 2 - ix].map(|hint| 1.0 - hint))
+                .flatten();
+        }
+    }
+
+    /// Appends a color stop with the given offset and color.
+    ///
+    /// This is useful for building stops incrementally, such as when parsing CSS
+    /// stop by stop. Stops are expected to be added in order of offset.
+    ///
+    /// The color can be any type which can be paired with an offset to form a
+    /// [`ColorStop`], such as [`AlphaColor`] or [`DynamicColor`].
+    pub fn add_stop<C>(&mut self, offset: f32, color: C)
+    where
+        (f32, C): Into<ColorStop>,
+    {
+        self.push((offset, color).into());
+    }
+
+    /// Appends the stops from `source` to the existing stops.
+    ///
+    /// Sources which only provide colors are evenly spaced over the `[0, 1]` range on
+    /// their own, without regard to the existing stops.
+    pub fn extend_stops(&mut self, source: impl ColorStopsSource) {
+        source.collect_stops(self);
+    }
+
+    /// Returns `true` if the stops are sorted by offset.
+    #[must_use]
+    pub fn is_sorted_by_offset(&self) -> bool {
+        self.windows(2)
+            .all(|pair| compare_offsets(pair[0].offset, pair[1].offset) != Ordering::Greater)
+    }
+
+    /// Normalizes the stops in place.
+    ///
+    /// Stops with a NaN offset are removed, the remaining offsets are clamped to the
+    /// `[0, 1]` range and the stops are then [sorted](Self::sort_by_offset). Stops that
+    /// share an offset (including those that only do so after clamping) keep their
+    /// relative order.
+    ///
+    /// Normalizing an already normalized collection has no effect.
+    pub fn normalize(&mut self) {
+        let stops = self.to_mut();
+        stops.retain(|stop| !stop.offset.is_nan());
+        for stop in stops.iter_mut() {
+            // Adding zero turns a negative zero into a positive one.
+            stop.offset = stop.offset.clamp(0.0, 1.0) + 0.0;
+        }
+        self.sort_by_offset();
+    }
+
+    /// Removes consecutive stops that are exactly identical.
+    ///
+    /// Two stops are considered identical when both their offsets and colors are
+    /// [bitwise equal](BitEq). The first of each run of identical stops is kept.
+    ///
+    /// Since identical adjacent stops never change the appearance of a gradient, this
+    /// is always safe to apply to a [sorted](Self::sort_by_offset) collection, including
+    /// one containing hard stops.
+    pub fn dedup_exact(&mut self) {
+        self.to_mut().dedup_by(|a, b| a.bit_eq(b));
+    }
+
+    /// Converts the colors of all stops to the color space `cs`, in a single pass.
+    ///
+    /// This is useful for encoders which deliver stops in a fixed color space, such as
+    /// linear sRGB for GPU color ramps, so that they don't need to convert each color
+    /// as it is read. Alpha is preserved, and stops already in `cs` are left unchanged.
+    pub fn convert_colorspace_in_place(&mut self, cs: ColorSpaceTag) {
+        for stop in self.iter_mut().filter(|stop| stop.color.cs != cs) {
+            stop.color = stop.color.convert(cs);
+        }
+    }
+
+    /// Subdivides the transitions between stops by inserting stops interpolated in
+    /// [Oklab](ColorSpaceTag::Oklab), a perceptually uniform color space.
+    ///
+    /// Each level halves the distance between stops, so `levels` of `1` inserts a
+    /// single stop halfway between each pair of stops, and `levels` of `n` inserts
+    /// `2^n - 1` stops. Levels above 8 are treated as 8. The inserted colors take
+    /// [interpolation hints](ColorStop::hint) into account, and the hints are removed
+    /// from subdivided transitions.
+    ///
+    /// Renderers which can only interpolate in sRGB will then draw a gradient which
+    /// approximates one interpolated in Oklab. Hard transitions, where two stops share
+    /// an offset, are left unchanged. The stops are expected to be sorted.
+    pub fn insert_perceptual_midpoints(&mut self, levels: u8) {
+        let segments = 1_u16 << levels.min(8);
+        if segments == 1 || self.len() < 2 {
+            return;
+        }
+        let mut stops = SmallVec::with_capacity(self.len() * usize::from(segments));
+        for pair in self.windows(2) {
+            let (start, end) = (&pair[0], &pair[1]);
+            let span = end.offset - start.offset;
+            if span.is_nan() || span <= 0.0 {
+                stops.push(*start);
+                continue;
+            }
+            let interpolator =
+                start
+                    .color
+                    .interpolate(end.color, ColorSpaceTag::Oklab, HueDirection::Shorter);
+            stops.push(ColorStop {
+                hint: None,
+                ..*start
+            });
+            for i in 1..segments {
+                let offset = start.offset + span * f32::from(i) / f32::from(segments);
+                stops.push(ColorStop {
+                    offset,
+                    color: interpolator.eval(start.interpolation_weight(end, offset)),
+                    hint: None,
+                });
+            }
+        }
+        stops.extend(self.last().copied());
+        self.0 = StopStorage::Inline(stops);
+    }
+
+    /// Returns an iterator over the transitions between adjacent stops, along with the
+    /// range of offsets that each covers.
+    ///
+    /// The range before the first stop and after the last stop are included as
+    /// transitions from a stop to itself, so for [normalized](Self::normalize) stops the
+    /// ranges cover `[0, 1]` without gaps or overlaps. Empty ranges, such as those
+    /// between the stops of a hard transition, are skipped. An empty collection of stops
+    /// has no segments.
+    pub fn segments(&self) -> impl Iterator<Item = (ColorStop, ColorStop, Range<f32>)> + '_ {
+        let first = self.first().map(|stop| (*stop, *stop, 0.0..stop.offset));
+        let last = self.last().map(|stop| (*stop, *stop, stop.offset..1.0));
+        let pairs = self
+            .windows(2)
+            .map(|pair| (pair[0], pair[1], pair[0].offset..pair[1].offset));
+        first
+            .into_iter()
+            .chain(pairs)
+            .chain(last)
+            .filter(|(_, _, range)| range.start < range.end)
+    }
+
+    /// Returns the color which covers the largest part of the `[0, 1]` range.
+    ///
+    /// Each stop is considered to cover the range up to halfway to its neighboring
+    /// stops, and the first and last stop also cover the range before and after them.
+    /// Stops with [bitwise equal](BitEq) colors are counted together.
+    ///
+    /// This is suitable for picking a representative color, such as for a placeholder.
+    /// The stops are expected to be sorted. Returns `None` if there are no stops.
+    #[must_use]
+    pub fn dominant_color(&self) -> Option<DynamicColor> {
+        let mut coverage: SmallVec<[(DynamicColor, f32); 4]> = SmallVec::new();
+        for (i, stop) in self.iter().enumerate() {
+            let start = match i.checked_sub(1) {
+                Some(prev) => 0.5 * (self[prev].offset + stop.offset),
+                None => 0.0,
+            };
+            let end = match self.get(i + 1) {
+                Some(next) => 0.5 * (stop.offset + next.offset),
+                None => 1.0,
+            };
+            let covered = (end.min(1.0) - start.max(0.0)).max(0.0);
+            match coverage
+                .iter_mut()
+                .find(|(color, _)| color.bit_eq(&stop.color))
+            {
+                Some((_, total)) => *total += covered,
+                None => coverage.push((stop.color, covered)),
+            }
+        }
+        // Earlier colors win ties.
+        coverage
+            .into_iter()
+            .rev()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(color, _)| color)
+    }
+}
+
+/// Total ordering for stop offsets, used for sorting.
+///
+/// This differs from [`f32::total_cmp`] in that it treats `0.0` and `-0.0` as equal,
+/// and orders NaN after every other value.
+fn compare_offsets(a: f32, b: f32) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
 }
 
 impl BitEq for ColorStops {
     fn bit_eq(&self, other: &Self) -> bool {
-        self.as_slice().bit_eq(other.as_slice())
+        self[..].bit_eq(&other[..])
     }
 }
 
 impl BitHash for ColorStops {
     fn bit_hash<H: Hasher>(&self, state: &mut H) {
-        self.as_slice().bit_hash(state);
+        self[..].bit_hash(state);
     }
 }
 
 impl From<&[ColorStop]> for ColorStops {
     fn from(slice: &[ColorStop]) -> Self {
-        Self(slice.into())
+        Self(StopStorage::Inline(slice.into()))
+    }
+}
+
+impl From<SmallVec<[ColorStop; 4]>> for ColorStops {
+    fn from(stops: SmallVec<[ColorStop; 4]>) -> Self {
+        Self(StopStorage::Inline(stops))
+    }
+}
+
+impl From<Vec<ColorStop>> for ColorStops {
+    fn from(stops: Vec<ColorStop>) -> Self {
+        SmallVec::from_vec(stops).into()
+    }
+}
+
+impl From<SharedColorStops> for ColorStops {
+    fn from(stops: SharedColorStops) -> Self {
+        Self(StopStorage::Shared(stops))
+    }
+}
+
+impl FromIterator<ColorStop> for ColorStops {
+    fn from_iter<I: IntoIterator<Item = ColorStop>>(iter: I) -> Self {
+        SmallVec::from_iter(iter).into()
+    }
+}
+
+impl core::iter::Extend<ColorStop> for ColorStops {
+    fn extend<I: IntoIterator<Item = ColorStop>>(&mut self, iter: I) {
+        self.to_mut().extend(iter);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ColorStops {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        self[..].serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for ColorStops {
+    fn deserialize<D>(des: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        SmallVec::<[ColorStop; 4]>::deserialize(des).map(Self::from)
+    }
+}
+
+/// Color stops in shared, immutable storage.
+///
+/// Cloning [`ColorStops`] copies the stops once there are more than fit inline,
+/// which is costly for gradients with many stops, such as those imported from
+/// design files. Stops converted from this type, such as by passing it to
+/// [`Gradient::with_stops`], share its storage instead, so that cloning the
+/// gradient into a retained display list only bumps a reference count.
+///
+/// The [`id`](Self::id) identifies the storage, and can be used as a cheap cache key.
+/// Equality compares the stops, regardless of their storage.
+#[derive(Clone, Debug)]
+pub struct SharedColorStops(Blob<ColorStop>);
+
+impl SharedColorStops {
+    /// Returns the unique identifier of the storage, which is shared by clones.
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        self.0.id()
+    }
+
+    /// Returns the stops as [`ColorStops`] which share this storage.
+    #[must_use]
+    pub fn to_color_stops(&self) -> ColorStops {
+        self.clone().into()
+    }
+}
+
+impl Deref for SharedColorStops {
+    type Target = [ColorStop];
+    fn deref(&self) -> &Self::Target {
+        self.0.data()
+    }
+}
+
+impl PartialEq for SharedColorStops {
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl Default for SharedColorStops {
+    fn default() -> Self {
+        Vec::new().into()
+    }
+}
+
+impl From<Vec<ColorStop>> for SharedColorStops {
+    fn from(stops: Vec<ColorStop>) -> Self {
+        Self(stops.into())
+    }
+}
+
+impl From<&[ColorStop]> for SharedColorStops {
+    fn from(stops: &[ColorStop]) -> Self {
+        stops.to_vec().into()
+    }
+}
+
+impl From<ColorStops> for SharedColorStops {
+    fn from(stops: ColorStops) -> Self {
+        match stops.0 {
+            StopStorage::Inline(stops) => stops.into_vec().into(),
+            StopStorage::Shared(stops) => stops,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SharedColorStops {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        self[..].serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for SharedColorStops {
+    fn deserialize<D>(des: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        Vec::<ColorStop>::deserialize(des).map(Self::from)
     }
 }
 
 /// Properties for the supported [gradient](Gradient) types.
+///
+/// # Precision
+///
+/// Positions are [`Point`]s with `f64` coordinates, matching the rest of the
+/// geometry which a gradient is drawn with, so that they can be transformed with
+/// the same precision. Radii and angles are `f32`, as they are only consumed by
+/// renderers, which typically evaluate gradients with `f32` arithmetic.
+///
+/// Renderers which upload gradient geometry can use
+/// [`to_f32_parts`](Self::to_f32_parts) rather than converting each field.
 #[derive(Copy, Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GradientKind {
@@ -162,16 +752,211 @@ pub enum GradientKind {
     },
     /// Gradient that transitions between two or more colors that rotate around a center
     /// point.
+    ///
+    /// Angles are in radians, measured from the positive x-axis towards the positive
+    /// y-axis. In a y-down coordinate system, such as that of CSS, this is clockwise.
+    /// See [`Gradient::new_css_conic`] for converting from CSS conventions.
     Sweep {
         /// Center point.
         center: Point,
-        /// Start angle of the sweep, counter-clockwise of the x-axis.
+        /// Start angle of the sweep, in radians clockwise of the x-axis in y-down
+        /// coordinates.
         start_angle: f32,
-        /// End angle of the sweep, counter-clockwise of the x-axis.
+        /// End angle of the sweep, in radians clockwise of the x-axis in y-down
+        /// coordinates.
         end_angle: f32,
     },
 }
 
+impl GradientKind {
+    /// Returns the gradient kind with the start angle of a sweep gradient moved into
+    /// the range `[0, 2π)`.
+    ///
+    /// The end angle is moved by the same amount, so the sweep covers the same angles
+    /// in the same direction and the gradient is drawn identically. Other kinds are
+    /// returned unchanged.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        match self {
+            Self::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                let turns = (start_angle / TAU).floor() * TAU;
+                // Rounding can produce exactly 2π for tiny negative angles.
+                let shift = if start_angle - turns >= TAU {
+                    turns + TAU
+                } else {
+                    turns
+                };
+                Self::Sweep {
+                    center,
+                    start_angle: start_angle - shift,
+                    end_angle: end_angle - shift,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns the gradient kind with the start (focal) circle of a radial gradient
+    /// moved inside of the end circle.
+    ///
+    /// When the start circle isn't contained by the end circle, SVG 1.1 moves the
+    /// focal point onto the boundary of the end circle, while the canvas API and
+    /// SVG 2 draw a cone touching both circles. Renderers which want the SVG 1.1
+    /// behavior can apply it with this, by moving the start center towards the end
+    /// center until the start circle touches the end circle from the inside. Other
+    /// kinds are returned unchanged.
+    #[must_use]
+    pub fn clamp_focal(self) -> Self {
+        match self {
+            Self::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => {
+                let (dx, dy) = (start_center.x - end_center.x, start_center.y - end_center.y);
+                let distance = (dx * dx + dy * dy).sqrt();
+                let max_distance = f64::from((end_radius - start_radius).max(0.0));
+                if distance <= max_distance {
+                    return self;
+                }
+                Self::Radial {
+                    start_center: end_center.lerp(start_center, max_distance / distance),
+                    start_radius,
+                    end_center,
+                    end_radius,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Returns `true` if the geometry doesn't define a color ramp.
+    ///
+    /// This is the case for a linear gradient with coincident points, a radial
+    /// gradient with equal circles or negative radii, a sweep gradient with equal
+    /// angles, and geometry which isn't finite. Renderers differ in how they draw
+    /// these, typically painting nothing or the color of the last stop.
+    #[must_use]
+    pub fn is_degenerate(&self) -> bool {
+        let finite = |p: Point| p.x.is_finite() && p.y.is_finite();
+        match *self {
+            Self::Linear { start, end } => !finite(start) || !finite(end) || start == end,
+            Self::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => {
+                !finite(start_center)
+                    || !finite(end_center)
+                    || !start_radius.is_finite()
+                    || !end_radius.is_finite()
+                    || start_radius < 0.0
+                    || end_radius < 0.0
+                    || (start_center == end_center && start_radius == end_radius)
+            }
+            Self::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                !finite(center)
+                    || !start_angle.is_finite()
+                    || !end_angle.is_finite()
+                    || start_angle == end_angle
+            }
+        }
+    }
+
+    /// Returns the geometry of the gradient as `f32` values.
+    ///
+    /// The values are laid out as follows, with unused values set to zero:
+    ///
+    /// - Linear: `[start.x, start.y, end.x, end.y, 0, 0]`
+    /// - Radial: `[start_center.x, start_center.y, start_radius, end_center.x, end_center.y, end_radius]`
+    /// - Sweep: `[center.x, center.y, start_angle, end_angle, 0, 0]`
+    #[must_use]
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Renderers evaluate gradients with `f32` precision."
+    )]
+    pub fn to_f32_parts(&self) -> [f32; 6] {
+        match *self {
+            Self::Linear { start, end } => [
+                start.x as f32,
+                start.y as f32,
+                end.x as f32,
+                end.y as f32,
+                0.0,
+                0.0,
+            ],
+            Self::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => [
+                start_center.x as f32,
+                start_center.y as f32,
+                start_radius,
+                end_center.x as f32,
+                end_center.y as f32,
+                end_radius,
+            ],
+            Self::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => [
+                center.x as f32,
+                center.y as f32,
+                start_angle,
+                end_angle,
+                0.0,
+                0.0,
+            ],
+        }
+    }
+}
+
+/// Problem with the offsets passed to [`Gradient::with_stops_and_offsets`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum StopOffsetsError {
+    /// The number of offsets doesn't match the number of colors.
+    LengthMismatch {
+        /// The number of colors.
+        colors: usize,
+        /// The number of offsets.
+        offsets: usize,
+    },
+    /// The offset at this index is infinite or NaN.
+    NonFinite(usize),
+    /// The offset at this index is less than the one before it.
+    Unsorted(usize),
+}
+
+impl fmt::Display for StopOffsetsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { colors, offsets } => {
+                write!(f, "{offsets} offsets were given for {colors} colors")
+            }
+            Self::NonFinite(index) => write!(f, "offset {index} is not finite"),
+            Self::Unsorted(index) => {
+                write!(f, "offset {index} is less than the offset before it")
+            }
+        }
+    }
+}
+
+impl core::error::Error for StopOffsetsError {}
+
 /// Definition of a gradient that transitions between two or more colors.
 #[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -179,6 +964,12 @@ pub struct Gradient {
     /// Kind and properties of the gradient.
     pub kind: GradientKind,
     /// Extend mode.
+    ///
+    /// Unlike [images](crate::Image), which have an extend mode for each axis, gradients
+    /// have a single extend mode. Every kind of gradient maps each point to a single
+    /// position `t` along the gradient, and the extend mode only determines the color
+    /// for positions outside of `[0, 1]`, so there is no second axis to extend. Brushes
+    /// which are parameterized in two dimensions should be expressed as images.
     pub extend: Extend,
     /// The color space to be used for interpolation.
     ///
@@ -194,6 +985,23 @@ pub struct Gradient {
     pub hue_direction: HueDirection,
     /// Color stop collection.
     pub stops: ColorStops,
+    /// Optional stable identifier of the gradient, chosen by the application.
+    ///
+    /// Renderers can use this as the key when caching baked color ramps, so that a
+    /// ramp can be reused across frames and scenes when the gradient is rebuilt or
+    /// its fields change slightly, such as during an animation. Gradients with the
+    /// same identifier are assumed to have equivalent color ramps.
+    ///
+    /// Methods which derive a gradient with a different color ramp, such as
+    /// [`multiply_alpha`](Self::multiply_alpha), clear the identifier.
+    ///
+    /// This defaults to `None`, for which renderers identify gradients by their
+    /// contents.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub id: Option<u64>,
 }
 
 impl Default for Gradient {
@@ -207,6 +1015,7 @@ impl Default for Gradient {
             interpolation_cs: DEFAULT_GRADIENT_COLOR_SPACE,
             hue_direction: Default::default(),
             stops: Default::default(),
+            id: None,
         }
     }
 }
@@ -223,6 +1032,7 @@ impl Gradient {
             interpolation_cs: DEFAULT_GRADIENT_COLOR_SPACE,
             hue_direction: Default::default(),
             stops: Default::default(),
+            id: None,
         }
     }
 
@@ -240,6 +1050,7 @@ impl Gradient {
             interpolation_cs: DEFAULT_GRADIENT_COLOR_SPACE,
             hue_direction: Default::default(),
             stops: Default::default(),
+            id: None,
         }
     }
 
@@ -261,6 +1072,7 @@ impl Gradient {
             interpolation_cs: DEFAULT_GRADIENT_COLOR_SPACE,
             hue_direction: Default::default(),
             stops: Default::default(),
+            id: None,
         }
     }
 
@@ -277,9 +1089,23 @@ impl Gradient {
             interpolation_cs: DEFAULT_GRADIENT_COLOR_SPACE,
             hue_direction: Default::default(),
             stops: Default::default(),
+            id: None,
         }
     }
 
+    /// Creates a new sweep gradient equivalent to a CSS [`conic-gradient`] with
+    /// the specified center point and starting angle in degrees.
+    ///
+    /// CSS measures angles in degrees clockwise from the top, so `from_degrees` of
+    /// `0` starts the gradient pointing up and `90` starts it pointing right. The
+    /// gradient makes a full turn. This assumes a y-down coordinate system, as in CSS.
+    ///
+    /// [`conic-gradient`]: https://drafts.csswg.org/css-images-4/#conic-gradients
+    pub fn new_css_conic(center: impl Into<Point>, from_degrees: f32) -> Self {
+        let start_angle = from_degrees.to_radians() - FRAC_PI_2;
+        Self::new_sweep(center, start_angle, start_angle + TAU)
+    }
+
     /// Builder method for setting the gradient extend mode.
     #[must_use]
     pub const fn with_extend(mut self, mode: Extend) -> Self {
@@ -301,38 +1127,914 @@ impl Gradient {
         self
     }
 
-    /// Builder method for setting the color stop collection.
+    /// Builder method for setting the stable [identifier](Self::id) of the gradient.
     #[must_use]
-    pub fn with_stops(mut self, stops: impl ColorStopsSource) -> Self {
-        self.stops.clear();
-        stops.collect_stops(&mut self.stops);
+    pub const fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
         self
     }
 
-    /// Returns the gradient with the alpha component for all color stops set to `alpha`.
+    /// Builder method for setting the color stop collection.
+    ///
+    /// This replaces any existing stops. Use [`extend_stops`](Self::extend_stops) to
+    /// append stops instead.
     #[must_use]
-    pub fn with_alpha(mut self, alpha: f32) -> Self {
-        self.stops
-            .iter_mut()
-            .for_each(|stop| *stop = stop.with_alpha(alpha));
+    pub fn with_stops(mut self, stops: impl ColorStopsSource) -> Self {
+        self.stops.clear();
+        stops.collect_stops(&mut self.stops);
         self
     }
 
-    /// Returns the gradient with the alpha component for all color stops
-    /// multiplied by `alpha`.
+    /// Builder method for setting the stops to the given colors, evenly spaced from
+    /// offset `0` to `1`.
+    ///
+    /// This is the same as passing a slice of colors to
+    /// [`with_stops`](Self::with_stops), and replaces any existing stops.
     #[must_use]
-    pub fn multiply_alpha(mut self, alpha: f32) -> Self {
-        self.stops
-            .iter_mut()
-            .for_each(|stop| *stop = stop.multiply_alpha(alpha));
+    pub fn with_evenly_spaced_stops<C>(mut self, colors: &[C]) -> Self
+    where
+        C: Copy,
+        (f32, C): Into<ColorStop>,
+    {
+        self.stops.clear();
+        let denom = colors.len().saturating_sub(1).max(1) as f32;
+        self.stops.extend(
+            colors
+                .iter()
+                .enumerate()
+                .map(|(i, &color)| ((i as f32) / denom, color).into()),
+        );
         self
     }
-}
 
-/// Trait for types that represent a source of color stops.
-pub trait ColorStopsSource {
-    /// Append the stops represented within `self` into `stops`.
-    fn collect_stops(self, stops: &mut ColorStops);
+    /// Builder method for setting the stops to the given colors at the given offsets,
+    /// which are validated first.
+    ///
+    /// This replaces any existing stops. Offsets may be equal, for a hard transition
+    /// between colors, but not decreasing. This is intended for building gradients
+    /// from user data, where mismatched inputs should be reported rather than drawn.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of offsets doesn't match the number of colors,
+    /// or if the offsets aren't finite and sorted. See [`StopOffsetsError`].
+    pub fn with_stops_and_offsets<C>(
+        mut self,
+        colors: &[C],
+        offsets: &[f32],
+    ) -> Result<Self, StopOffsetsError>
+    where
+        C: Copy,
+        (f32, C): Into<ColorStop>,
+    {
+        if colors.len() != offsets.len() {
+            return Err(StopOffsetsError::LengthMismatch {
+                colors: colors.len(),
+                offsets: offsets.len(),
+            });
+        }
+        if let Some(index) = offsets.iter().position(|offset| !offset.is_finite()) {
+            return Err(StopOffsetsError::NonFinite(index));
+        }
+        if let Some(index) = offsets.windows(2).position(|pair| pair[1] < pair[0]) {
+            return Err(StopOffsetsError::Unsorted(index + 1));
+        }
+        self.stops.clear();
+        self.stops.extend(
+            offsets
+                .iter()
+                .zip(colors)
+                .map(|(&offset, &color)| (offset, color).into()),
+        );
+        Ok(self)
+    }
+
+    /// Appends a color stop with the given offset and color.
+    ///
+    /// See [`ColorStops::add_stop`].
+    pub fn add_stop<C>(&mut self, offset: f32, color: C)
+    where
+        (f32, C): Into<ColorStop>,
+    {
+        self.stops.add_stop(offset, color);
+    }
+
+    /// Appends the stops from `source` to the existing stops.
+    ///
+    /// See [`ColorStops::extend_stops`].
+    pub fn extend_stops(&mut self, source: impl ColorStopsSource) {
+        self.stops.extend_stops(source);
+    }
+
+    /// Converts the gradient into the equivalent of a CSS repeating gradient, such as
+    /// `repeating-linear-gradient`.
+    ///
+    /// In CSS, the stops of a repeating gradient are repeated with a period equal to
+    /// the distance between the first and last stop, and the offsets of those stops
+    /// may lie anywhere, including outside of the `[0, 1]` range. This returns a
+    /// gradient with [`Extend::Repeat`] where the first and last stop are at `0` and `1`
+    /// and the geometry is adjusted so that the gradient renders identically.
+    ///
+    /// As in CSS, the offset of a stop which is smaller than that of a previous stop
+    /// is raised to the offset of the previous stop. Radial gradients are shifted by
+    /// whole periods so that they don't start at a negative offset, as that would
+    /// require a negative radius.
+    ///
+    /// If the stops have no positive distance between them, only the extend mode
+    /// is changed.
+    #[must_use]
+    pub fn into_repeating(mut self) -> Self {
+        self.extend = Extend::Repeat;
+        let mut max_offset = f32::NEG_INFINITY;
+        for stop in self.stops.iter_mut() {
+            max_offset = max_offset.max(stop.offset);
+            stop.offset = max_offset;
+        }
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return self;
+        };
+        let (mut start, span) = (first.offset, last.offset - first.offset);
+        if !span.is_finite() || span <= 0.0 {
+            return self;
+        }
+        for stop in self.stops.iter_mut() {
+            stop.offset = (stop.offset - start) / span;
+            stop.hint = stop.hint.map(|hint| (hint - start) / span);
+        }
+        self.id = None;
+        if matches!(self.kind, GradientKind::Radial { .. }) && start < 0.0 {
+            start -= (start / span).floor() * span;
+        }
+        let end = start + span;
+        self.kind = match self.kind {
+            GradientKind::Linear {
+                start: start_point,
+                end: end_point,
+            } => GradientKind::Linear {
+                start: start_point.lerp(end_point, start.into()),
+                end: start_point.lerp(end_point, end.into()),
+            },
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => GradientKind::Radial {
+                start_center: start_center.lerp(end_center, start.into()),
+                start_radius: start_radius + (end_radius - start_radius) * start,
+                end_center: start_center.lerp(end_center, end.into()),
+                end_radius: start_radius + (end_radius - start_radius) * end,
+            },
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => GradientKind::Sweep {
+                center,
+                start_angle: start_angle + (end_angle - start_angle) * start,
+                end_angle: start_angle + (end_angle - start_angle) * end,
+            },
+        };
+        self
+    }
+
+    /// Returns the CSS [`<color-interpolation-method>`] equivalent to the
+    /// [interpolation color space](Self::interpolation_cs) and
+    /// [hue direction](Self::hue_direction) of this gradient, such as `"in oklch longer hue"`.
+    ///
+    /// The hue direction is only included for cylindrical color spaces, and only when it
+    /// differs from the default of [`HueDirection::Shorter`].
+    ///
+    /// Returns `None` if the color space has no CSS equivalent.
+    ///
+    /// [`<color-interpolation-method>`]: https://drafts.csswg.org/css-color-4/#color-interpolation-method
+    #[must_use]
+    pub fn interpolation_to_css(&self) -> Option<String> {
+        let name = css_color_space_name(self.interpolation_cs)?;
+        let mut css = String::from("in ");
+        css.push_str(name);
+        if is_polar(self.interpolation_cs) {
+            match self.hue_direction {
+                HueDirection::Longer => css.push_str(" longer hue"),
+                HueDirection::Increasing => css.push_str(" increasing hue"),
+                HueDirection::Decreasing => css.push_str(" decreasing hue"),
+                _ => {}
+            }
+        }
+        Some(css)
+    }
+
+    /// Parses a CSS [`<color-interpolation-method>`] such as `"in oklab"` or
+    /// `"in hsl longer hue"` into values for the
+    /// [interpolation color space](Self::interpolation_cs) and
+    /// [hue direction](Self::hue_direction) of a gradient.
+    ///
+    /// Keywords are matched ASCII case-insensitively, as in CSS. A hue interpolation
+    /// method is only accepted for cylindrical color spaces.
+    ///
+    /// Returns `None` if the string is not a valid interpolation method.
+    ///
+    /// [`<color-interpolation-method>`]: https://drafts.csswg.org/css-color-4/#color-interpolation-method
+    #[must_use]
+    pub fn from_css_interpolation(s: &str) -> Option<(ColorSpaceTag, HueDirection)> {
+        let mut words = s.split_ascii_whitespace();
+        if !words.next()?.eq_ignore_ascii_case("in") {
+            return None;
+        }
+        let cs = css_color_space_from_name(words.next()?)?;
+        let hue_direction = match words.next() {
+            None => HueDirection::Shorter,
+            Some(method) if is_polar(cs) => {
+                let direction = [
+                    ("shorter", HueDirection::Shorter),
+                    ("longer", HueDirection::Longer),
+                    ("increasing", HueDirection::Increasing),
+                    ("decreasing", HueDirection::Decreasing),
+                ]
+                .into_iter()
+                .find(|(name, _)| method.eq_ignore_ascii_case(name))?
+                .1;
+                if !words.next()?.eq_ignore_ascii_case("hue") {
+                    return None;
+                }
+                direction
+            }
+            Some(_) => return None,
+        };
+        if words.next().is_some() {
+            return None;
+        }
+        Some((cs, hue_direction))
+    }
+
+    /// Returns the gradient with the alpha component for all color stops set to `alpha`.
+    #[must_use]
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.stops
+            .iter_mut()
+            .for_each(|stop| *stop = stop.with_alpha(alpha));
+        self.id = None;
+        self
+    }
+
+    /// Returns the gradient with the alpha component for all color stops
+    /// multiplied by `alpha`.
+    #[must_use]
+    pub fn multiply_alpha(mut self, alpha: f32) -> Self {
+        self.stops
+            .iter_mut()
+            .for_each(|stop| *stop = stop.multiply_alpha(alpha));
+        self.id = None;
+        self
+    }
+
+    /// Returns the offsets of the stops and their colors with premultiplied alpha in
+    /// the [interpolation color space](Self::interpolation_cs).
+    ///
+    /// Interpolating these linearly, ignoring [hints](ColorStop::hint), gives the
+    /// colors along the gradient before un-premultiplying. See
+    /// [`ColorStop::to_premultiplied`] for details.
+    pub fn premultiplied_stops(&self) -> impl Iterator<Item = (f32, [f32; 4])> + '_ {
+        self.stops
+            .iter()
+            .map(|stop| (stop.offset, stop.to_premultiplied(self.interpolation_cs)))
+    }
+
+    /// Returns the average color of the gradient over the `[0, 1]` range of offsets.
+    ///
+    /// This accounts for the [interpolation color space](Self::interpolation_cs),
+    /// [hue direction](Self::hue_direction) and [hints](ColorStop::hint), and colors
+    /// are averaged with premultiplied alpha. The area covered by the gradient is not
+    /// taken into account, so this is exact for linear gradients drawn with
+    /// [`Extend::Pad`] and an approximation otherwise. When the color space has a hue
+    /// component, the average of different hues follows the shorter hue arc.
+    ///
+    /// This is suitable for picking a representative color, such as for a placeholder.
+    /// The stops are expected to be sorted. Returns `None` if there are no stops.
+    #[must_use]
+    pub fn average_color(&self) -> Option<DynamicColor> {
+        let (first, last) = (self.stops.first()?, self.stops.last()?);
+        let mut average = first.color;
+        let mut total = 0.0;
+        let mut add = |color: DynamicColor, weight: f32| {
+            if weight > 0.0 {
+                total += weight;
+                average = average
+                    .interpolate(color, self.interpolation_cs, HueDirection::Shorter)
+                    .eval(weight / total);
+            }
+        };
+        add(first.color, first.offset.min(1.0));
+        for pair in self.stops.windows(2) {
+            let (start, end) = (pair[0].offset.max(0.0), pair[1].offset.min(1.0));
+            if end > start {
+                let weight = pair[0].mean_interpolation_weight(&pair[1], start, end);
+                let color = pair[0]
+                    .color
+                    .interpolate(pair[1].color, self.interpolation_cs, self.hue_direction)
+                    .eval(weight);
+                add(color, end - start);
+            }
+        }
+        add(last.color, 1.0 - last.offset.max(0.0));
+        Some(average)
+    }
+
+    /// Splits the gradient into an opacity, which is the largest alpha of any stop,
+    /// and a gradient with the alpha of every stop divided by that opacity.
+    ///
+    /// The returned gradient has at least one opaque stop, and the relative alphas of
+    /// its stops are unchanged. Drawing the returned gradient with the returned opacity
+    /// applied (for example, with [`multiply_alpha`](Self::multiply_alpha) or as a layer
+    /// opacity) has the same appearance as drawing the original gradient, up to
+    /// rounding.
+    ///
+    /// This allows renderers to share a single cached color ramp between gradients whose
+    /// stop alphas only differ by a common factor, such as one gradient drawn at several
+    /// group opacities.
+    ///
+    /// If no stop has a positive alpha, the gradient is returned unchanged with an
+    /// opacity of `1.0`.
+    #[must_use]
+    pub fn split_opacity(mut self) -> (Self, f32) {
+        let opacity = self
+            .stops
+            .iter()
+            .map(|stop| stop.color.components[3])
+            .fold(0.0_f32, f32::max);
+        if opacity <= 0.0 || opacity == 1.0 {
+            return (self, 1.0);
+        }
+        for stop in self.stops.iter_mut() {
+            stop.color.components[3] = (stop.color.components[3] / opacity).min(1.0);
+        }
+        self.id = None;
+        (self, opacity)
+    }
+
+    /// Returns the gradient with its geometry mapped by `transform`.
+    ///
+    /// This is useful for applying the transform of a scene graph node to a brush,
+    /// when the brush can't be drawn with a separate brush transform.
+    ///
+    /// Linear gradients are mapped exactly. Radial gradients are only mapped exactly by
+    /// transforms which preserve circles (combinations of translation, rotation,
+    /// uniform scale and reflection); otherwise each radius is scaled by the square
+    /// root of the area scale factor of the transform. For sweep gradients, the start
+    /// angle is mapped and the angular span is preserved, which is exact for
+    /// transforms which preserve circles. A reflection reverses the direction of a
+    /// sweep, so the stops of a reflected sweep gradient are reversed.
+    ///
+    /// Use a brush transform instead where exact results are required for other
+    /// transforms.
+    #[cfg(feature = "kurbo")]
+    #[must_use]
+    pub fn transformed(&self, transform: Affine) -> Self {
+        let mut result = self.clone();
+        let [a, b, c, d, _, _] = transform.as_coeffs();
+        let det = transform.determinant();
+        result.kind = match self.kind {
+            GradientKind::Linear { start, end } => {
+                let dir = end - start;
+                let len2 = dir.hypot2();
+                let mapped_end = if det != 0.0 && len2 > 0.0 {
+                    // The offset is a linear function of the transformed position, whose
+                    // gradient is the inverse transpose of the transform applied to the
+                    // gradient before the transform.
+                    let grad =
+                        Vec2::new(d * dir.x - b * dir.y, a * dir.y - c * dir.x) / (det * len2);
+                    transform * start + grad / grad.hypot2()
+                } else {
+                    transform * end
+                };
+                GradientKind::Linear {
+                    start: transform * start,
+                    end: mapped_end,
+                }
+            }
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => {
+                let scale = det.abs().sqrt();
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    reason = "Gradient radii are stored as f32."
+                )]
+                let scale_radius = |radius: f32| (f64::from(radius) * scale) as f32;
+                GradientKind::Radial {
+                    start_center: transform * start_center,
+                    start_radius: scale_radius(start_radius),
+                    end_center: transform * end_center,
+                    end_radius: scale_radius(end_radius),
+                }
+            }
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                let map_angle = |angle: f32| {
+                    let v = Vec2::from_angle(angle.into());
+                    Vec2::new(a * v.x + c * v.y, b * v.x + d * v.y).atan2()
+                };
+                let span = end_angle - start_angle;
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    reason = "Gradient angles are stored as f32."
+                )]
+                let start_angle = if det < 0.0 {
+                    result.stops.reverse_offsets();
+                    result.id = None;
+                    map_angle(end_angle) as f32
+                } else {
+                    map_angle(start_angle) as f32
+                };
+                GradientKind::Sweep {
+                    center: transform * center,
+                    start_angle,
+                    end_angle: start_angle + span,
+                }
+            }
+        };
+        result
+    }
+
+    /// Returns a key for caching the color ramp of the gradient, with `resolution`
+    /// samples.
+    ///
+    /// The ramp is determined by the [stops](Self::stops), the
+    /// [interpolation color space](Self::interpolation_cs) and the
+    /// [hue direction](Self::hue_direction), so gradients which only differ in their
+    /// [kind](Self::kind) or [extend mode](Self::extend) share a key.
+    ///
+    /// Stop offsets, hints and color components are rounded to multiples of
+    /// `quantization` before hashing, so that nearly identical gradients (for example,
+    /// the frames of an animation) can share a ramp. Color components are quantized in
+    /// the units of the color space of each stop, which is `0` to `1` for the
+    /// channels of most color spaces. A `quantization` of `1.0 / resolution as f32` is
+    /// a reasonable choice, as smaller differences are rarely visible in the ramp. If
+    /// `quantization` is not positive, values are hashed exactly.
+    ///
+    /// As with any hash, different ramps can have the same key, so a cache can
+    /// compare the quantized gradients to rule out collisions. The key is
+    /// deterministic, but may differ between platforms and versions of this crate,
+    /// so it shouldn't be persisted.
+    #[must_use]
+    pub fn ramp_cache_key(&self, resolution: u32, quantization: f32) -> u64 {
+        let mut hasher = Fnv1aHasher::default();
+        let quantize = |state: &mut Fnv1aHasher, value: f32| {
+            if quantization > 0.0 {
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    reason = "Saturation only merges values which are far outside the useful range."
+                )]
+                state.write_i32((value / quantization).round() as i32);
+            } else {
+                state.write_u32(value.to_bits());
+            }
+        };
+        resolution.hash(&mut hasher);
+        self.interpolation_cs.hash(&mut hasher);
+        hasher.write_u8(self.hue_direction as u8);
+        hasher.write_usize(self.stops.len());
+        for stop in self.stops.iter() {
+            quantize(&mut hasher, stop.offset);
+            stop.color.cs.hash(&mut hasher);
+            stop.color.flags.hash(&mut hasher);
+            for component in stop.color.components {
+                quantize(&mut hasher, component);
+            }
+            hasher.write_u8(stop.hint.is_some().into());
+            if let Some(hint) = stop.hint {
+                quantize(&mut hasher, hint);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// Corner of the reference box which a CSS [linear gradient] is directed towards,
+/// as with `to top right`.
+///
+/// [linear gradient]: https://drafts.csswg.org/css-images-3/#linear-gradients
+#[cfg(feature = "kurbo")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinearGradientCorner {
+    /// The top left corner.
+    TopLeft,
+    /// The top right corner.
+    TopRight,
+    /// The bottom right corner.
+    BottomRight,
+    /// The bottom left corner.
+    BottomLeft,
+}
+
+#[cfg(feature = "kurbo")]
+impl_u8_conversions!(LinearGradientCorner {
+    TopLeft = 0,
+    TopRight = 1,
+    BottomRight = 2,
+    BottomLeft = 3,
+});
+
+/// Start and end points of a CSS [linear gradient], resolved against a reference
+/// box.
+///
+/// [linear gradient]: https://drafts.csswg.org/css-images-3/#linear-gradients
+#[cfg(feature = "kurbo")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearGradientPosition {
+    /// Start point of the gradient line.
+    pub start: Point,
+    /// End point of the gradient line.
+    pub end: Point,
+}
+
+#[cfg(feature = "kurbo")]
+impl LinearGradientPosition {
+    /// Resolves the gradient line of a CSS `linear-gradient(<angle>)` against the
+    /// reference box `rect`.
+    ///
+    /// The angle is in degrees, where `0` points up, towards the top of the box, and
+    /// positive angles turn clockwise, as in CSS. The gradient line passes through
+    /// the center of the box, and is just long enough that the corners of the box
+    /// in the direction of the line and opposite to it get the colors of the end and
+    /// start points.
+    #[must_use]
+    pub fn from_angle(rect: Rect, degrees: f64) -> Self {
+        let radians = degrees.to_radians();
+        Self::from_direction(rect, Vec2::new(radians.sin(), -radians.cos()))
+    }
+
+    /// Resolves the gradient line of a CSS `linear-gradient(to <corner>)` against the
+    /// reference box `rect`.
+    ///
+    /// The gradient line points into the quadrant of the corner, and is
+    /// perpendicular to the diagonal between the two neighboring corners, so that
+    /// they both get the color halfway along the gradient.
+    #[must_use]
+    pub fn from_corner(rect: Rect, corner: LinearGradientCorner) -> Self {
+        let rect = rect.abs();
+        let (w, h) = (rect.width(), rect.height());
+        let direction = match corner {
+            LinearGradientCorner::TopLeft => Vec2::new(-h, -w),
+            LinearGradientCorner::TopRight => Vec2::new(h, -w),
+            LinearGradientCorner::BottomRight => Vec2::new(h, w),
+            LinearGradientCorner::BottomLeft => Vec2::new(-h, w),
+        };
+        if direction == Vec2::ZERO {
+            let center = rect.center();
+            return Self {
+                start: center,
+                end: center,
+            };
+        }
+        Self::from_direction(rect, direction.normalize())
+    }
+
+    /// Returns the gradient line through the center of `rect` in the unit
+    /// `direction`, with the length given by CSS.
+    fn from_direction(rect: Rect, direction: Vec2) -> Self {
+        let rect = rect.abs();
+        let length = (rect.width() * direction.x).abs() + (rect.height() * direction.y).abs();
+        let center = rect.center();
+        let half = direction * (0.5 * length);
+        Self {
+            start: center - half,
+            end: center + half,
+        }
+    }
+
+    /// Returns a linear gradient along the gradient line.
+    ///
+    /// The gradient has no stops.
+    #[must_use]
+    pub fn gradient(&self) -> Gradient {
+        Gradient::new_linear(self.start, self.end)
+    }
+}
+
+/// Ending shape of a CSS [radial gradient].
+///
+/// [radial gradient]: https://drafts.csswg.org/css-images-3/#radial-gradients
+#[cfg(feature = "kurbo")]
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RadialGradientShape {
+    /// The ending shape is a circle.
+    Circle,
+    /// The ending shape is an axis-aligned ellipse.
+    #[default]
+    Ellipse,
+}
+
+#[cfg(feature = "kurbo")]
+impl_u8_conversions!(RadialGradientShape {
+    Circle = 0,
+    Ellipse = 1,
+});
+
+/// Size of the ending shape of a CSS [radial gradient].
+///
+/// The keywords are measured from the center of the gradient to the sides or corners
+/// of the reference box.
+///
+/// [radial gradient]: https://drafts.csswg.org/css-images-3/#radial-gradients
+#[cfg(feature = "kurbo")]
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RadialGradientSize {
+    /// The ending shape meets the side of the box closest to the center, or the
+    /// closest sides in each direction for an ellipse.
+    ClosestSide,
+    /// The ending shape passes through the corner of the box closest to the center.
+    ///
+    /// An ellipse has the same aspect ratio as with [`ClosestSide`](Self::ClosestSide).
+    ClosestCorner,
+    /// The ending shape meets the side of the box farthest from the center, or the
+    /// farthest sides in each direction for an ellipse.
+    FarthestSide,
+    /// The ending shape passes through the corner of the box farthest from the center.
+    ///
+    /// An ellipse has the same aspect ratio as with [`FarthestSide`](Self::FarthestSide).
+    #[default]
+    FarthestCorner,
+    /// Explicit horizontal and vertical radii. Only the horizontal radius is used for
+    /// a circle.
+    ///
+    /// Percentages should be resolved against the width and height of the box.
+    Explicit(Vec2),
+}
+
+/// Center and radii of a CSS [radial gradient], resolved against a reference box.
+///
+/// As [radial gradients](GradientKind::Radial) are circular, an elliptical gradient
+/// is drawn as a circular [`gradient`](Self::gradient), scaled vertically by the
+/// brush [`transform`](Self::transform).
+///
+/// [radial gradient]: https://drafts.csswg.org/css-images-3/#radial-gradients
+#[cfg(feature = "kurbo")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialGradientPosition {
+    /// Center of the gradient.
+    pub center: Point,
+    /// Horizontal and vertical radii of the ending shape, which are equal for a circle.
+    pub radii: Vec2,
+}
+
+#[cfg(feature = "kurbo")]
+impl RadialGradientPosition {
+    /// Scale used in place of zero or infinite radii, following the CSS rules for
+    /// degenerate ending shapes.
+    const DEGENERATE_SCALE: f64 = 1e-6;
+
+    /// Resolves the ending shape of a CSS `radial-gradient(<shape> <size> at <position>)`
+    /// against the reference box `rect`.
+    ///
+    /// The `position` is the center of the gradient, in the same coordinate space as
+    /// `rect`.
+    #[must_use]
+    pub fn from_css(
+        shape: RadialGradientShape,
+        size: RadialGradientSize,
+        position: Point,
+        rect: Rect,
+    ) -> Self {
+        let rect = rect.abs();
+        let left = (position.x - rect.x0).abs();
+        let right = (position.x - rect.x1).abs();
+        let top = (position.y - rect.y0).abs();
+        let bottom = (position.y - rect.y1).abs();
+        let closest = Vec2::new(left.min(right), top.min(bottom));
+        let farthest = Vec2::new(left.max(right), top.max(bottom));
+        let radii = if shape == RadialGradientShape::Circle {
+            let radius = match size {
+                RadialGradientSize::ClosestSide => closest.x.min(closest.y),
+                RadialGradientSize::FarthestSide => farthest.x.max(farthest.y),
+                RadialGradientSize::ClosestCorner => closest.hypot(),
+                RadialGradientSize::FarthestCorner => farthest.hypot(),
+                RadialGradientSize::Explicit(radii) => radii.x,
+            };
+            Vec2::new(radius, radius)
+        } else {
+            match size {
+                RadialGradientSize::ClosestSide => closest,
+                RadialGradientSize::FarthestSide => farthest,
+                // The closest corner is at the closest distance in both directions.
+                RadialGradientSize::ClosestCorner => Self::ellipse_through(closest),
+                RadialGradientSize::FarthestCorner => Self::ellipse_through(farthest),
+                RadialGradientSize::Explicit(radii) => radii,
+            }
+        };
+        Self {
+            center: position,
+            radii,
+        }
+    }
+
+    /// Returns the radii of the ellipse which passes through `corner` (relative to its
+    /// center), with the aspect ratio of the ellipse whose radii are `corner`.
+    fn ellipse_through(corner: Vec2) -> Vec2 {
+        if corner.x == 0.0 || corner.y == 0.0 {
+            return corner;
+        }
+        // With an aspect ratio of `x / y`, the ellipse passes through the corner when
+        // its radii are `√2` times larger.
+        corner * core::f64::consts::SQRT_2
+    }
+
+    /// Returns `true` if the ending shape is a circle.
+    #[must_use]
+    pub fn is_circle(&self) -> bool {
+        self.radii.x == self.radii.y
+    }
+
+    /// Returns the radii which are drawn, after replacing zero radii.
+    ///
+    /// As in CSS, a zero radius is treated as a very small one, and for an ellipse the
+    /// other radius is treated as very large.
+    fn drawn_radii(&self) -> Vec2 {
+        let Vec2 { x, y } = self.radii;
+        let small = Self::DEGENERATE_SCALE;
+        match (x > 0.0, y > 0.0) {
+            (true, true) => self.radii,
+            (false, false) => Vec2::new(small, small),
+            (false, true) => Vec2::new(small, 1.0 / small),
+            (true, false) => Vec2::new(1.0 / small, small),
+        }
+    }
+
+    /// Returns a radial gradient with the center and horizontal radius of the ending
+    /// shape, which should be drawn with the brush [`transform`](Self::transform).
+    ///
+    /// The gradient has no stops.
+    #[must_use]
+    pub fn gradient(&self) -> Gradient {
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Gradient radii are stored as f32."
+        )]
+        let radius = self.drawn_radii().x as f32;
+        Gradient::new_radial(self.center, radius)
+    }
+
+    /// Returns the brush transform which maps the circular [`gradient`](Self::gradient)
+    /// to the ending shape.
+    ///
+    /// This is the identity for a circle.
+    #[must_use]
+    pub fn transform(&self) -> Affine {
+        let radii = self.drawn_radii();
+        if radii.x == radii.y {
+            return Affine::IDENTITY;
+        }
+        Affine::scale_non_uniform(1.0, radii.y / radii.x)
+            .with_translation(Vec2::new(0.0, self.center.y * (1.0 - radii.y / radii.x)))
+    }
+}
+
+/// The 64-bit [FNV-1a] hash function, used for keys which should not depend on the
+/// random state of a `HashMap`.
+///
+/// [FNV-1a]: https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Borrowed form of a [gradient](Gradient), for use as the input of scene encoders.
+///
+/// This copies the geometry and settings of the gradient, and borrows its stops.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EncodeGradient<'a> {
+    /// Kind and geometry of the gradient.
+    pub kind: GradientKind,
+    /// Extend mode.
+    pub extend: Extend,
+    /// The color space to be used for interpolation.
+    pub interpolation_cs: ColorSpaceTag,
+    /// When interpolating within a cylindrical color space, the direction for the hue.
+    pub hue_direction: HueDirection,
+    /// Color stop collection.
+    pub stops: &'a [ColorStop],
+    /// Optional stable identifier of the gradient.
+    pub id: Option<u64>,
+}
+
+impl<'a> EncodeGradient<'a> {
+    /// Creates a borrowed gradient of the given kind, with the given stops and
+    /// default settings.
+    ///
+    /// This doesn't allocate, so immediate-mode callers can build a gradient on the
+    /// stack for each draw, with stops from a slice or an array, and pass it as a
+    /// [`BrushRef::BorrowedGradient`](crate::BrushRef::BorrowedGradient).
+    #[must_use]
+    pub const fn new(kind: GradientKind, stops: &'a [ColorStop]) -> Self {
+        Self {
+            kind,
+            extend: Extend::Pad,
+            interpolation_cs: DEFAULT_GRADIENT_COLOR_SPACE,
+            hue_direction: HueDirection::Shorter,
+            stops,
+            id: None,
+        }
+    }
+
+    /// Builder method for setting the gradient extend mode.
+    #[must_use]
+    pub const fn with_extend(mut self, mode: Extend) -> Self {
+        self.extend = mode;
+        self
+    }
+
+    /// Builder method for setting the interpolation color space.
+    #[must_use]
+    pub const fn with_interpolation_cs(mut self, interpolation_cs: ColorSpaceTag) -> Self {
+        self.interpolation_cs = interpolation_cs;
+        self
+    }
+
+    /// Builder method for setting the hue direction when interpolating within a cylindrical color space.
+    #[must_use]
+    pub const fn with_hue_direction(mut self, hue_direction: HueDirection) -> Self {
+        self.hue_direction = hue_direction;
+        self
+    }
+
+    /// Builder method for setting the stable [identifier](Gradient::id) of the gradient.
+    #[must_use]
+    pub const fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Converts the borrowed gradient to an owned gradient, copying the stops.
+    #[must_use]
+    pub fn to_gradient(&self) -> Gradient {
+        Gradient {
+            kind: self.kind,
+            extend: self.extend,
+            interpolation_cs: self.interpolation_cs,
+            hue_direction: self.hue_direction,
+            stops: self.stops.into(),
+            id: self.id,
+        }
+    }
+}
+
+impl<'a> From<&'a Gradient> for EncodeGradient<'a> {
+    fn from(gradient: &'a Gradient) -> Self {
+        Self {
+            kind: gradient.kind,
+            extend: gradient.extend,
+            interpolation_cs: gradient.interpolation_cs,
+            hue_direction: gradient.hue_direction,
+            stops: &gradient.stops,
+            id: gradient.id,
+        }
+    }
+}
+
+/// Returns the index of the hue component of the color space, if it has one.
+fn hue_channel(cs: ColorSpaceTag) -> Option<usize> {
+    match cs {
+        ColorSpaceTag::Hsl | ColorSpaceTag::Hwb => Some(0),
+        ColorSpaceTag::Lch | ColorSpaceTag::Oklch => Some(2),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the color space has a hue component.
+pub(crate) fn is_polar(cs: ColorSpaceTag) -> bool {
+    hue_channel(cs).is_some()
+}
+
+/// Trait for types that represent a source of color stops.
+pub trait ColorStopsSource {
+    /// Append the stops represented within `self` into `stops`.
+    fn collect_stops(self, stops: &mut ColorStops);
 }
 
 impl<T> ColorStopsSource for &'_ [T]
@@ -364,6 +2066,7 @@ impl<CS: ColorSpace> ColorStopsSource for &'_ [AlphaColor<CS>] {
             stops.extend(self.iter().enumerate().map(|(i, c)| ColorStop {
                 offset: (i as f32) / denom,
                 color: DynamicColor::from_alpha_color(*c),
+                hint: None,
             }));
         }
     }
@@ -376,6 +2079,7 @@ impl ColorStopsSource for &'_ [DynamicColor] {
             stops.extend(self.iter().enumerate().map(|(i, c)| ColorStop {
                 offset: (i as f32) / denom,
                 color: (*c),
+                hint: None,
             }));
         }
     }
@@ -388,11 +2092,29 @@ impl<CS: ColorSpace> ColorStopsSource for &'_ [OpaqueColor<CS>] {
             stops.extend(self.iter().enumerate().map(|(i, c)| ColorStop {
                 offset: (i as f32) / denom,
                 color: DynamicColor::from_alpha_color((*c).with_alpha(1.)),
+                hint: None,
             }));
         }
     }
 }
 
+impl ColorStopsSource for &'_ SharedColorStops {
+    fn collect_stops(self, stops: &mut ColorStops) {
+        self.clone().collect_stops(stops);
+    }
+}
+
+impl ColorStopsSource for SharedColorStops {
+    fn collect_stops(self, stops: &mut ColorStops) {
+        // Share the storage rather than copying it when there's nothing to append to.
+        if stops.is_empty() {
+            *stops = self.into();
+        } else {
+            stops.extend(self.iter().copied());
+        }
+    }
+}
+
 impl<const N: usize, CS: ColorSpace> ColorStopsSource for [AlphaColor<CS>; N] {
     fn collect_stops(self, stops: &mut ColorStops) {
         (&self[..]).collect_stops(stops);
@@ -411,10 +2133,150 @@ impl<const N: usize, CS: ColorSpace> ColorStopsSource for [OpaqueColor<CS>; N] {
 
 #[cfg(test)]
 mod tests {
-    use super::Gradient;
-    use color::{cache_key::CacheKey, palette, parse_color};
+    use super::{ColorStop, ColorStops, Gradient, GradientKind, SharedColorStops};
+    use crate::{Extend, Point};
+    use color::{
+        cache_key::CacheKey, palette, parse_color, ColorSpaceTag, DynamicColor, HueDirection,
+    };
+    use core::f32::consts::{FRAC_PI_2, TAU};
     use std::collections::HashSet;
 
+    /// Small deterministic generator so that the property tests below are reproducible.
+    struct XorShift(u32);
+
+    impl XorShift {
+        fn next(&mut self) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0
+        }
+    }
+
+    /// Generates a collection of stops with heavily repeated offsets, where the
+    /// red component of each color records the original position of the stop.
+    fn random_stops(rng: &mut XorShift) -> ColorStops {
+        const OFFSETS: [f32; 8] = [-0.5, -0.0, 0.0, 0.25, 0.5, 0.5, 1.0, 2.0];
+        let len = rng.next() % 12;
+        let mut stops = ColorStops::new();
+        for i in 0..len {
+            let offset = OFFSETS[(rng.next() % 8) as usize];
+            let color = DynamicColor::from_alpha_color(palette::css::BLACK.with_alpha(0.5));
+            let mut stop = ColorStop::from((offset, color));
+            stop.color.components[0] = i as f32;
+            stops.push(stop);
+            if rng.next() % 4 == 0 {
+                // Insert an exact duplicate.
+                stops.push(stop);
+            }
+        }
+        stops
+    }
+
+    #[test]
+    fn sort_is_stable_for_equal_offsets() {
+        let mut rng = XorShift(0x9e37_79b9);
+        for _ in 0..500 {
+            let mut stops = random_stops(&mut rng);
+            stops.sort_by_offset();
+            assert!(stops.is_sorted_by_offset(), "stops should be sorted");
+            for pair in stops.windows(2) {
+                if pair[0].offset == pair[1].offset {
+                    assert!(
+                        pair[0].color.components[0] <= pair[1].color.components[0],
+                        "stops at equal offsets must keep their original order"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let mut rng = XorShift(0x1234_5678);
+        for _ in 0..500 {
+            let mut stops = random_stops(&mut rng);
+            stops.push(ColorStop::from((f32::NAN, palette::css::RED)));
+            stops.normalize();
+            assert!(
+                stops.iter().all(
+                    |stop| (0.0..=1.0).contains(&stop.offset) && stop.offset.is_sign_positive()
+                ),
+                "normalized offsets should be within [0, 1]"
+            );
+            let once = stops.clone();
+            stops.normalize();
+            assert!(
+                CacheKey(once) == CacheKey(stops),
+                "normalizing twice should have no further effect"
+            );
+        }
+    }
+
+    #[test]
+    fn dedup_exact_keeps_order() {
+        let mut rng = XorShift(0xdead_beef);
+        for _ in 0..500 {
+            let mut stops = random_stops(&mut rng);
+            stops.normalize();
+            let mut deduped = stops.clone();
+            deduped.dedup_exact();
+            assert!(
+                deduped.windows(2).all(|pair| pair[0] != pair[1]),
+                "no identical adjacent stops should remain"
+            );
+            // Every remaining stop must appear in the original, in the same order.
+            let mut original = stops.iter();
+            assert!(
+                deduped
+                    .iter()
+                    .all(|stop| original.any(|other| other == stop)),
+                "deduplication must preserve the order of stops"
+            );
+        }
+    }
+
+    #[test]
+    fn gradient_id() {
+        use super::EncodeGradient;
+
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([palette::css::RED, palette::css::BLUE]);
+        assert_eq!(gradient.id, None);
+        let identified = gradient.clone().with_id(7);
+        assert_eq!(identified.id, Some(7));
+        assert_ne!(identified, gradient);
+        assert_eq!(EncodeGradient::from(&identified).id, Some(7));
+        // The identifier survives changes which keep the color ramp.
+        assert_eq!(identified.clone().with_extend(Extend::Repeat).id, Some(7));
+        // It is cleared by changes to the color ramp.
+        assert_eq!(identified.clone().multiply_alpha(0.5).id, None);
+        assert_eq!(identified.clone().with_alpha(0.5).id, None);
+        assert_eq!(
+            identified
+                .multiply_alpha(0.5)
+                .with_id(7)
+                .split_opacity()
+                .0
+                .id,
+            None
+        );
+    }
+
+    #[test]
+    fn hard_stop_order_survives_normalize() {
+        let mut stops = ColorStops::new();
+        stops.push((0.5, palette::css::BLUE).into());
+        stops.push((0.0, palette::css::RED).into());
+        stops.push((0.5, palette::css::RED).into());
+        stops.push((1.0, palette::css::BLUE).into());
+        stops.normalize();
+        let colors: Vec<_> = stops.iter().map(|stop| stop.color).collect();
+        let red = DynamicColor::from_alpha_color(palette::css::RED);
+        let blue = DynamicColor::from_alpha_color(palette::css::BLUE);
+        assert_eq!(colors, [red, blue, red, blue]);
+    }
+
     #[test]
     fn color_stops_cache() {
         let mut set = HashSet::new();
@@ -438,4 +2300,687 @@ mod tests {
         let new_grad = parsed_gradient.clone();
         assert!(set.contains(&CacheKey(new_grad.stops)));
     }
+
+    #[test]
+    fn css_interpolation_round_trip() {
+        for cs in [
+            ColorSpaceTag::Srgb,
+            ColorSpaceTag::LinearSrgb,
+            ColorSpaceTag::Oklab,
+            ColorSpaceTag::Oklch,
+            ColorSpaceTag::Hsl,
+            ColorSpaceTag::XyzD65,
+        ] {
+            for hue_direction in [
+                HueDirection::Shorter,
+                HueDirection::Longer,
+                HueDirection::Increasing,
+                HueDirection::Decreasing,
+            ] {
+                let gradient = Gradient::default()
+                    .with_interpolation_cs(cs)
+                    .with_hue_direction(hue_direction);
+                let css = gradient.interpolation_to_css().unwrap();
+                let (parsed_cs, parsed_hue) = Gradient::from_css_interpolation(&css).unwrap();
+                assert_eq!(parsed_cs, cs);
+                if matches!(cs, ColorSpaceTag::Oklch | ColorSpaceTag::Hsl) {
+                    assert_eq!(parsed_hue, hue_direction);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn css_interpolation_parsing() {
+        assert_eq!(
+            Gradient::from_css_interpolation("in oklab"),
+            Some((ColorSpaceTag::Oklab, HueDirection::Shorter))
+        );
+        assert_eq!(
+            Gradient::from_css_interpolation("IN  OkLch Longer Hue"),
+            Some((ColorSpaceTag::Oklch, HueDirection::Longer))
+        );
+        assert_eq!(
+            Gradient::from_css_interpolation("in xyz"),
+            Some((ColorSpaceTag::XyzD65, HueDirection::Shorter))
+        );
+        // Hue interpolation methods only apply to cylindrical spaces.
+        assert_eq!(
+            Gradient::from_css_interpolation("in oklab longer hue"),
+            None
+        );
+        assert_eq!(Gradient::from_css_interpolation("in oklch longer"), None);
+        assert_eq!(Gradient::from_css_interpolation("oklch"), None);
+        assert_eq!(Gradient::from_css_interpolation("in oklch hue"), None);
+        assert_eq!(Gradient::from_css_interpolation("in lab extra"), None);
+        assert_eq!(
+            Gradient::default()
+                .with_interpolation_cs(ColorSpaceTag::AcesCg)
+                .interpolation_to_css(),
+            None
+        );
+    }
+
+    #[test]
+    fn interpolation_hints() {
+        let start = ColorStop::from((0.25, palette::css::RED));
+        let end = ColorStop::from((0.75, palette::css::BLUE));
+        assert_eq!(start.interpolation_weight(&end, 0.5), 0.5);
+        assert_eq!(start.interpolation_weight(&end, 0.0), 0.0);
+        assert_eq!(start.interpolation_weight(&end, 1.0), 1.0);
+
+        let hinted = start.with_hint(0.375);
+        assert!((hinted.interpolation_weight(&end, 0.375) - 0.5).abs() < 1e-6);
+        assert!(hinted.interpolation_weight(&end, 0.3) < 0.5);
+        assert!(hinted.interpolation_weight(&end, 0.5) > 0.5);
+        assert_eq!(hinted.interpolation_weight(&end, 0.75), 1.0);
+
+        // Hints at either stop produce a hard transition.
+        let at_start = start.with_hint(0.25);
+        assert_eq!(at_start.interpolation_weight(&end, 0.3), 1.0);
+        let at_end = start.with_hint(0.75);
+        assert_eq!(at_end.interpolation_weight(&end, 0.7), 0.0);
+    }
+
+    #[test]
+    fn repeating_gradients() {
+        let stops = [
+            ColorStop::from((-0.25, palette::css::RED)).with_hint(0.0),
+            ColorStop::from((0.25, palette::css::BLUE)),
+        ];
+        let linear = Gradient::new_linear((0.0, 0.0), (100.0, 0.0))
+            .with_stops(stops.as_slice())
+            .into_repeating();
+        assert_eq!(linear.extend, Extend::Repeat);
+        assert_eq!(
+            linear.kind,
+            GradientKind::Linear {
+                start: (-25.0, 0.0).into(),
+                end: (25.0, 0.0).into(),
+            }
+        );
+        assert_eq!(linear.stops[0].offset, 0.0);
+        assert_eq!(linear.stops[0].hint, Some(0.5));
+        assert_eq!(linear.stops[1].offset, 1.0);
+
+        // Radial gradients are shifted by a whole period to avoid a negative radius.
+        let radial = Gradient::new_radial((0.0, 0.0), 100.0)
+            .with_stops(stops.as_slice())
+            .into_repeating();
+        let GradientKind::Radial {
+            start_radius,
+            end_radius,
+            ..
+        } = radial.kind
+        else {
+            panic!("expected a radial gradient");
+        };
+        assert_eq!((start_radius, end_radius), (25.0, 75.0));
+
+        // Stops which go backwards are raised to the previous offset.
+        let backwards = Gradient::new_linear((0.0, 0.0), (100.0, 0.0))
+            .with_stops([
+                (0.5, palette::css::RED),
+                (0.25, palette::css::LIME),
+                (1.0, palette::css::BLUE),
+            ])
+            .into_repeating();
+        let offsets: Vec<_> = backwards.stops.iter().map(|stop| stop.offset).collect();
+        assert_eq!(offsets, [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn split_opacity() {
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0)).with_stops([
+            palette::css::RED.with_alpha(0.5),
+            palette::css::BLUE.with_alpha(0.25),
+        ]);
+        let (opaque, opacity) = gradient.clone().split_opacity();
+        assert_eq!(opacity, 0.5);
+        assert_eq!(opaque.stops[0].color.components[3], 1.0);
+        assert_eq!(opaque.stops[1].color.components[3], 0.5);
+
+        // Applying the opacity again reproduces the original gradient.
+        let restored = opaque.clone().multiply_alpha(opacity);
+        for (a, b) in restored.stops.iter().zip(gradient.stops.iter()) {
+            assert_eq!(a.offset, b.offset);
+            for (x, y) in a.color.components.iter().zip(b.color.components.iter()) {
+                assert!((x - y).abs() < 1e-6);
+            }
+        }
+
+        // Gradients that differ only by opacity share the same opaque gradient.
+        let (faded, _) = gradient.multiply_alpha(0.5).split_opacity();
+        assert_eq!(faded, opaque);
+
+        let (unchanged, unchanged_opacity) = Gradient::default()
+            .with_stops([palette::css::RED, palette::css::TRANSPARENT])
+            .split_opacity();
+        assert_eq!(unchanged_opacity, 1.0);
+        assert_eq!(unchanged.stops[1].color.components[3], 0.0);
+    }
+
+    #[test]
+    fn premultiplied_stops() {
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_interpolation_cs(ColorSpaceTag::Oklch)
+            .with_stops([
+                ColorStop::from_premultiplied(0.0, ColorSpaceTag::Oklch, [0.3, 0.1, 120.0, 0.5]),
+                ColorStop::from_premultiplied(1.0, ColorSpaceTag::Oklch, [0.2, 0.1, 90.0, 0.0]),
+            ]);
+        // The hue isn't premultiplied, and transparent colors lose their components.
+        assert_eq!(gradient.stops[0].color.components, [0.6, 0.2, 120.0, 0.5]);
+        assert_eq!(gradient.stops[1].color.components, [0.0, 0.0, 90.0, 0.0]);
+        let stops = gradient.premultiplied_stops().collect::<Vec<_>>();
+        assert_eq!(
+            stops,
+            [(0.0, [0.3, 0.1, 120.0, 0.5]), (1.0, [0.0, 0.0, 90.0, 0.0])]
+        );
+
+        // Stops are converted to the interpolation color space.
+        let red = ColorStop::from((0.0, palette::css::RED.with_alpha(0.5)));
+        let [r, g, b, a] = red.to_premultiplied(ColorSpaceTag::LinearSrgb);
+        assert_eq!([r, g, b, a], [0.5, 0.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn clamp_focal() {
+        let radial = |start_center: Point, start_radius| GradientKind::Radial {
+            start_center,
+            start_radius,
+            end_center: Point::new(0.0, 0.0),
+            end_radius: 10.0,
+        };
+        // A focal point inside the end circle is unchanged.
+        let inside = radial(Point::new(5.0, 0.0), 0.0);
+        assert_eq!(inside.clamp_focal(), inside);
+        // Outside, it moves onto the boundary.
+        assert_eq!(
+            radial(Point::new(0.0, 20.0), 0.0).clamp_focal(),
+            radial(Point::new(0.0, 10.0), 0.0)
+        );
+        // A start circle moves until it touches the boundary from the inside.
+        assert_eq!(
+            radial(Point::new(-30.0, 0.0), 4.0).clamp_focal(),
+            radial(Point::new(-6.0, 0.0), 4.0)
+        );
+        let linear = GradientKind::Linear {
+            start: Point::new(0.0, 0.0),
+            end: Point::new(1.0, 0.0),
+        };
+        assert_eq!(linear.clamp_focal(), linear);
+    }
+
+    #[test]
+    fn degenerate_kinds() {
+        let point = Point::new(1.0, 2.0);
+        assert!(!Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .kind
+            .is_degenerate());
+        assert!(Gradient::new_linear(point, point).kind.is_degenerate());
+        assert!(Gradient::new_linear(point, (f64::NAN, 0.0))
+            .kind
+            .is_degenerate());
+        assert!(!Gradient::new_radial(point, 1.0).kind.is_degenerate());
+        assert!(Gradient::new_two_point_radial(point, 2.0, point, 2.0)
+            .kind
+            .is_degenerate());
+        assert!(Gradient::new_radial(point, -1.0).kind.is_degenerate());
+        assert!(!Gradient::new_sweep(point, 0.0, 1.0).kind.is_degenerate());
+        assert!(Gradient::new_sweep(point, 1.0, 1.0).kind.is_degenerate());
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn css_linear_position() {
+        use super::{LinearGradientCorner, LinearGradientPosition};
+        use kurbo::Rect;
+
+        let rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let close = |a: Point, b: Point| (a - b).hypot() < 1e-9;
+
+        // `to bottom` is the default, and `to right` is 90 degrees.
+        let down = LinearGradientPosition::from_angle(rect, 180.0);
+        assert!(close(down.start, Point::new(50.0, 0.0)));
+        assert!(close(down.end, Point::new(50.0, 50.0)));
+        let right = LinearGradientPosition::from_angle(rect, 90.0);
+        assert!(close(right.start, Point::new(0.0, 25.0)));
+        assert!(close(right.end, Point::new(100.0, 25.0)));
+
+        // At 45 degrees, the line is long enough for the corners to get the colors of
+        // the start and end points.
+        let diagonal = LinearGradientPosition::from_angle(rect, 45.0);
+        let length = |position: LinearGradientPosition| (position.end - position.start).hypot();
+        let along = |position: LinearGradientPosition, p: Point| {
+            (p - position.start).dot(position.end - position.start) / length(position)
+        };
+        assert!((length(diagonal) - 150.0 * core::f64::consts::FRAC_1_SQRT_2).abs() < 1e-9);
+        assert!(along(diagonal, Point::new(0.0, 50.0)).abs() < 1e-9);
+        assert!((along(diagonal, Point::new(100.0, 0.0)) - length(diagonal)).abs() < 1e-9);
+
+        // The neighboring corners are halfway along the line to a corner.
+        let corner = LinearGradientPosition::from_corner(rect, LinearGradientCorner::TopRight);
+        let half = 0.5 * length(corner);
+        assert!((along(corner, Point::new(0.0, 0.0)) - half).abs() < 1e-9);
+        assert!((along(corner, Point::new(100.0, 50.0)) - half).abs() < 1e-9);
+        assert!(along(corner, Point::new(100.0, 0.0)) > along(corner, Point::new(0.0, 50.0)));
+
+        let GradientKind::Linear { start, end } = corner.gradient().kind else {
+            panic!("expected a linear gradient");
+        };
+        assert_eq!((start, end), (corner.start, corner.end));
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn css_enums_u8() {
+        use super::{LinearGradientCorner, RadialGradientShape};
+
+        for corner in [
+            LinearGradientCorner::TopLeft,
+            LinearGradientCorner::TopRight,
+            LinearGradientCorner::BottomRight,
+            LinearGradientCorner::BottomLeft,
+        ] {
+            assert_eq!(LinearGradientCorner::from_u8(corner.to_u8()), Some(corner));
+        }
+        assert_eq!(LinearGradientCorner::from_u8(4), None);
+        for shape in [RadialGradientShape::Circle, RadialGradientShape::Ellipse] {
+            assert_eq!(RadialGradientShape::from_u8(shape.to_u8()), Some(shape));
+        }
+        assert_eq!(RadialGradientShape::from_u8(2), None);
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn css_radial_position() {
+        use super::{RadialGradientPosition, RadialGradientShape, RadialGradientSize};
+        use kurbo::{Rect, Vec2};
+
+        let rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let center = Point::new(25.0, 25.0);
+        let resolve = |shape, size| RadialGradientPosition::from_css(shape, size, center, rect);
+        let circle = |size| resolve(RadialGradientShape::Circle, size).radii;
+        let ellipse = |size| resolve(RadialGradientShape::Ellipse, size).radii;
+
+        assert_eq!(
+            circle(RadialGradientSize::ClosestSide),
+            Vec2::new(25.0, 25.0)
+        );
+        assert_eq!(
+            circle(RadialGradientSize::FarthestSide),
+            Vec2::new(75.0, 75.0)
+        );
+        let corner = Vec2::new(75.0, 25.0).hypot();
+        assert_eq!(
+            circle(RadialGradientSize::FarthestCorner),
+            Vec2::new(corner, corner)
+        );
+        assert_eq!(
+            ellipse(RadialGradientSize::ClosestSide),
+            Vec2::new(25.0, 25.0)
+        );
+        assert_eq!(
+            ellipse(RadialGradientSize::FarthestSide),
+            Vec2::new(75.0, 25.0)
+        );
+        assert_eq!(
+            circle(RadialGradientSize::Explicit(Vec2::new(10.0, 20.0))),
+            Vec2::new(10.0, 10.0)
+        );
+
+        // The farthest corner ellipse keeps the aspect ratio of the farthest sides and
+        // passes through the corner.
+        let position = resolve(RadialGradientShape::Ellipse, RadialGradientSize::default());
+        let Vec2 { x: rx, y: ry } = position.radii;
+        assert!((rx / ry - 3.0).abs() < 1e-9);
+        assert!(((75.0 / rx).powi(2) + (25.0 / ry).powi(2) - 1.0).abs() < 1e-9);
+
+        // The transform maps the circular gradient onto the ellipse.
+        assert!(!position.is_circle());
+        let GradientKind::Radial { end_radius, .. } = position.gradient().kind else {
+            panic!("expected a radial gradient");
+        };
+        assert!((f64::from(end_radius) - rx).abs() < 1e-4);
+        let top = position.transform() * Point::new(25.0, 25.0 - rx);
+        assert!((top - Point::new(25.0, 25.0 - ry)).hypot() < 1e-9);
+        assert!((position.transform() * center - center).hypot() < 1e-9);
+
+        // A zero width ellipse is treated as very thin and very tall.
+        let edge = RadialGradientPosition::from_css(
+            RadialGradientShape::Ellipse,
+            RadialGradientSize::ClosestSide,
+            Point::new(0.0, 25.0),
+            rect,
+        );
+        assert_eq!(edge.radii, Vec2::new(0.0, 25.0));
+        assert!(edge.gradient().kind != Gradient::new_radial(center, 0.0).kind);
+        assert!(edge.transform().as_coeffs()[3] > 1.0);
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn transformed() {
+        use kurbo::Affine;
+
+        // The offset of `point` along a linear gradient.
+        fn linear_offset(kind: GradientKind, point: Point) -> f64 {
+            let GradientKind::Linear { start, end } = kind else {
+                panic!("expected a linear gradient");
+            };
+            (point - start).dot(end - start) / (end - start).hypot2()
+        }
+
+        let linear = Gradient::new_linear((0.0, 0.0), (10.0, 10.0));
+        let skew = Affine::new([2.0, 0.5, 1.0, 3.0, 5.0, -2.0]);
+        let skewed = linear.transformed(skew);
+        for point in [Point::new(3.0, 1.0), Point::new(-4.0, 8.0)] {
+            let expected = linear_offset(linear.kind, point);
+            assert!((linear_offset(skewed.kind, skew * point) - expected).abs() < 1e-9);
+        }
+
+        let radial = Gradient::new_radial((1.0, 1.0), 2.0).transformed(Affine::scale(3.0));
+        let GradientKind::Radial {
+            end_center,
+            end_radius,
+            ..
+        } = radial.kind
+        else {
+            panic!("expected a radial gradient");
+        };
+        assert_eq!((end_center, end_radius), (Point::new(3.0, 3.0), 6.0));
+
+        let sweep = Gradient::new_sweep((0.0, 0.0), 0.0, FRAC_PI_2)
+            .with_id(3)
+            .with_stops([
+                ColorStop::from((0.0, palette::css::RED)).with_hint(0.25),
+                ColorStop::from((0.5, palette::css::LIME)),
+                ColorStop::from((1.0, palette::css::BLUE)),
+            ]);
+        let rotated = sweep.transformed(Affine::rotate(FRAC_PI_2.into()));
+        let GradientKind::Sweep { start_angle, .. } = rotated.kind else {
+            panic!("expected a sweep gradient");
+        };
+        assert!((start_angle - FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(rotated.stops, sweep.stops);
+        assert_eq!(rotated.id, Some(3));
+
+        // Reflecting in the x axis maps the end angle to the start angle, and reverses
+        // the stops.
+        let reflected = sweep.transformed(Affine::scale_non_uniform(1.0, -1.0));
+        let GradientKind::Sweep {
+            start_angle: reflected_start,
+            end_angle: reflected_end,
+            ..
+        } = reflected.kind
+        else {
+            panic!("expected a sweep gradient");
+        };
+        assert!((reflected_start + FRAC_PI_2).abs() < 1e-6);
+        assert!(reflected_end.abs() < 1e-6);
+        let offsets: Vec<_> = reflected.stops.iter().map(|s| s.offset).collect();
+        assert_eq!(offsets, [0.0, 0.5, 1.0]);
+        assert_eq!(reflected.stops[0].color, sweep.stops[2].color);
+        let hints: Vec<_> = reflected.stops.iter().map(|s| s.hint).collect();
+        assert_eq!(hints, [None, Some(0.75), None]);
+        assert_eq!(reflected.id, None);
+    }
+
+    #[test]
+    fn incremental_stops() {
+        let mut gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0));
+        gradient.add_stop(0.0, palette::css::RED);
+        gradient.extend_stops([(0.5, palette::css::LIME)]);
+        gradient.extend_stops(&[ColorStop::from((1.0, palette::css::BLUE))][..]);
+        let expected = Gradient::new_linear((0.0, 0.0), (1.0, 0.0)).with_stops([
+            (0.0, palette::css::RED),
+            (0.5, palette::css::LIME),
+            (1.0, palette::css::BLUE),
+        ]);
+        assert_eq!(gradient, expected);
+    }
+
+    #[test]
+    fn ramp_cache_key() {
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([(0.0, palette::css::RED), (1.0, palette::css::BLUE)]);
+        let key = gradient.ramp_cache_key(256, 1.0 / 256.0);
+        // The geometry doesn't affect the ramp.
+        let radial = Gradient {
+            kind: GradientKind::Radial {
+                start_center: Point::new(0.0, 0.0),
+                start_radius: 0.0,
+                end_center: Point::new(0.0, 0.0),
+                end_radius: 1.0,
+            },
+            ..gradient.clone()
+        };
+        assert_eq!(radial.ramp_cache_key(256, 1.0 / 256.0), key);
+
+        // Small differences are quantized away, unless hashing exactly.
+        let mut nudged = gradient.clone();
+        nudged.stops[1].offset = 0.999;
+        nudged.stops[0].color.components[1] = 0.001;
+        assert_eq!(nudged.ramp_cache_key(256, 1.0 / 256.0), key);
+        assert_ne!(
+            nudged.ramp_cache_key(256, 0.0),
+            gradient.ramp_cache_key(256, 0.0)
+        );
+
+        let mut moved = gradient.clone();
+        moved.stops[1].offset = 0.9;
+        assert_ne!(moved.ramp_cache_key(256, 1.0 / 256.0), key);
+        let hinted = Gradient::new_linear((0.0, 0.0), (1.0, 0.0)).with_stops([
+            ColorStop::from((0.0, palette::css::RED)).with_hint(0.25),
+            ColorStop::from((1.0, palette::css::BLUE)),
+        ]);
+        assert_ne!(hinted.ramp_cache_key(256, 1.0 / 256.0), key);
+        assert_ne!(gradient.ramp_cache_key(512, 1.0 / 256.0), key);
+    }
+
+    #[test]
+    fn average_and_dominant_colors() {
+        let gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([palette::css::RED, palette::css::BLUE]);
+        let average = gradient.average_color().unwrap();
+        for (x, y) in average.components.iter().zip([0.5, 0.0, 0.5, 1.0]) {
+            assert!((x - y).abs() < 1e-6);
+        }
+        // A stop that doesn't start at 0 covers the range before it.
+        let padded = gradient
+            .clone()
+            .with_stops([(0.5, palette::css::RED), (1.0, palette::css::BLUE)]);
+        assert!((padded.average_color().unwrap().components[0] - 0.75).abs() < 1e-6);
+        assert_eq!(Gradient::default().average_color(), None);
+
+        // The first color wins ties.
+        let hard = gradient.with_stops([
+            (0.0, palette::css::RED),
+            (0.5, palette::css::RED),
+            (0.5, palette::css::BLUE),
+            (1.0, palette::css::BLUE),
+        ]);
+        let red = DynamicColor::from_alpha_color(palette::css::RED);
+        let blue = DynamicColor::from_alpha_color(palette::css::BLUE);
+        assert_eq!(hard.stops.dominant_color(), Some(red));
+        let mostly_blue = ColorStops::from(
+            [
+                ColorStop::from((0.0, palette::css::RED)),
+                ColorStop::from((0.2, palette::css::BLUE)),
+                ColorStop::from((1.0, palette::css::BLUE)),
+            ]
+            .as_slice(),
+        );
+        assert_eq!(mostly_blue.dominant_color(), Some(blue));
+    }
+
+    #[test]
+    fn f32_parts() {
+        let radial = Gradient::new_two_point_radial((1.0, 2.0), 3.0, (4.0, 5.0), 6.0);
+        assert_eq!(radial.kind.to_f32_parts(), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let sweep = Gradient::new_sweep((1.0, 2.0), 0.5, 1.5);
+        assert_eq!(sweep.kind.to_f32_parts(), [1.0, 2.0, 0.5, 1.5, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn perceptual_midpoints() {
+        let mut stops = ColorStops::from(
+            [
+                ColorStop::from((0.0, palette::css::BLACK)),
+                ColorStop::from((0.5, palette::css::WHITE)),
+                ColorStop::from((0.5, palette::css::RED)),
+                ColorStop::from((1.0, palette::css::BLUE)),
+            ]
+            .as_slice(),
+        );
+        let hard_stop = stops[1..3].to_vec();
+        stops.insert_perceptual_midpoints(2);
+        let offsets: Vec<_> = stops.iter().map(|stop| stop.offset).collect();
+        assert_eq!(
+            offsets,
+            [0.0, 0.125, 0.25, 0.375, 0.5, 0.5, 0.625, 0.75, 0.875, 1.0]
+        );
+        assert_eq!(stops[4..6], hard_stop);
+        // The midpoint between black and white has an Oklab lightness of one half.
+        let middle = stops[2].color.convert(ColorSpaceTag::Oklab);
+        assert!((middle.components[0] - 0.5).abs() < 1e-4);
+
+        let mut unchanged = stops.clone();
+        unchanged.insert_perceptual_midpoints(0);
+        assert_eq!(unchanged, stops);
+    }
+
+    #[test]
+    fn sweep_angles() {
+        let conic = Gradient::new_css_conic((0.0, 0.0), 90.0);
+        assert_eq!(conic.kind.to_f32_parts()[2..4], [0.0, TAU]);
+
+        let up = Gradient::new_css_conic((0.0, 0.0), 0.0).kind.normalized();
+        let [_, _, start_angle, end_angle, ..] = up.to_f32_parts();
+        assert_eq!(start_angle, 3.0 * FRAC_PI_2);
+        assert!((end_angle - start_angle - TAU).abs() < 1e-6);
+        assert_eq!(up.normalized(), up);
+    }
+
+    #[test]
+    fn segments() {
+        let stops = ColorStops::from(
+            [
+                ColorStop::from((0.25, palette::css::RED)),
+                ColorStop::from((0.5, palette::css::LIME)),
+                ColorStop::from((0.5, palette::css::BLUE)),
+                ColorStop::from((1.0, palette::css::WHITE)),
+            ]
+            .as_slice(),
+        );
+        let segments: Vec<_> = stops
+            .segments()
+            .map(|(start, end, range)| (start.offset, end.offset, range))
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                (0.25, 0.25, 0.0..0.25),
+                (0.25, 0.5, 0.25..0.5),
+                (0.5, 1.0, 0.5..1.0)
+            ]
+        );
+        // The transition after a hard stop starts from the last stop at that offset.
+        assert_eq!(stops.segments().nth(2).unwrap().0, stops[2]);
+
+        let single = ColorStops::from([ColorStop::from((0.0, palette::css::RED))].as_slice());
+        assert_eq!(single.segments().count(), 1);
+        assert_eq!(ColorStops::new().segments().count(), 0);
+    }
+
+    #[test]
+    fn shared_stops() {
+        let colors = [palette::css::RED, palette::css::LIME, palette::css::BLUE];
+        let stops = Gradient::default().with_stops(colors.as_slice()).stops;
+        let shared = SharedColorStops::from(stops.clone());
+        let clone = shared.clone();
+        assert_eq!(clone.id(), shared.id());
+        assert_eq!(clone.as_ptr(), shared.as_ptr());
+        assert_eq!(shared.to_color_stops(), stops);
+
+        // Gradients built from shared stops share their storage until mutated.
+        let gradient = Gradient::default().with_stops(&shared);
+        assert_eq!(gradient.stops, stops);
+        let mut cloned = gradient.clone();
+        assert!(cloned.stops.is_shared());
+        assert_eq!(cloned.stops.as_ptr(), shared.as_ptr());
+        cloned.stops[0].offset = 0.5;
+        assert!(!cloned.stops.is_shared());
+        assert_eq!(gradient.stops[0].offset, 0.0);
+        assert_eq!(
+            SharedColorStops::from(gradient.stops).as_ptr(),
+            shared.as_ptr()
+        );
+
+        let copy = SharedColorStops::from(&stops[..]);
+        assert_ne!(copy.id(), shared.id());
+        assert_eq!(copy, shared);
+        assert_ne!(SharedColorStops::default(), shared);
+    }
+
+    #[test]
+    fn stops_and_offsets() {
+        use super::StopOffsetsError;
+
+        let colors = [palette::css::RED, palette::css::LIME, palette::css::BLUE];
+        let even = Gradient::default().with_evenly_spaced_stops(&colors);
+        assert_eq!(even.stops, Gradient::default().with_stops(colors).stops);
+        assert_eq!(even.stops[1].offset, 0.5);
+
+        let hard = Gradient::default()
+            .with_stops_and_offsets(&colors, &[0.0, 0.25, 0.25])
+            .unwrap();
+        let hard_offsets: Vec<_> = hard.stops.iter().map(|stop| stop.offset).collect();
+        assert_eq!(hard_offsets, [0.0, 0.25, 0.25]);
+        assert_eq!(hard.stops[2].color, even.stops[2].color);
+
+        let with_offsets = |offsets: &[f32]| {
+            Gradient::default()
+                .with_stops_and_offsets(&colors, offsets)
+                .map(|_| ())
+        };
+        assert_eq!(
+            with_offsets(&[0.0, 1.0]),
+            Err(StopOffsetsError::LengthMismatch {
+                colors: 3,
+                offsets: 2
+            })
+        );
+        assert_eq!(
+            with_offsets(&[0.0, f32::NAN, 1.0]),
+            Err(StopOffsetsError::NonFinite(1))
+        );
+        assert_eq!(
+            with_offsets(&[0.0, 0.75, 0.5]),
+            Err(StopOffsetsError::Unsorted(2))
+        );
+    }
+
+    #[test]
+    fn convert_stops_in_place() {
+        use color::cache_key::BitEq;
+
+        let mut stops = Gradient::default()
+            .with_stops([
+                DynamicColor::from_alpha_color(palette::css::WHITE.with_alpha(0.25)),
+                parse_color("oklch(0.5 0.1 120)").unwrap(),
+            ])
+            .stops;
+        let expected: Vec<_> = stops
+            .iter()
+            .map(|stop| stop.color.convert(ColorSpaceTag::LinearSrgb))
+            .collect();
+        stops.convert_colorspace_in_place(ColorSpaceTag::LinearSrgb);
+        for (stop, converted) in stops.iter().zip(&expected) {
+            assert_eq!(stop.color.cs, ColorSpaceTag::LinearSrgb);
+            assert!(stop.color.bit_eq(converted));
+        }
+        assert_eq!(stops[0].color.components, [1.0, 1.0, 1.0, 0.25]);
+        assert_eq!(stops[1].offset, 1.0);
+    }
 }
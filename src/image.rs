@@ -1,18 +1,35 @@
 // Copyright 2022 the Peniko Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::{Blob, Extend};
+use super::{Blob, Color, Extend, Point, Vec2, WeakBlob};
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use crate::floatfuncs::FloatFuncs as _;
 
 /// Defines the pixel format of an [image](Image).
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum ImageFormat {
     /// 32-bit RGBA with 8-bit channels.
     Rgba8,
 }
 
+impl_u8_conversions!(ImageFormat {
+    Rgba8 = 0,
+});
+
 impl ImageFormat {
+    /// All supported image formats.
+    ///
+    /// As this type is non-exhaustive, formats may be added to this list in minor
+    /// releases.
+    pub const ALL: &'static [Self] = &[Self::Rgba8];
+
     /// Returns the required size in bytes for an image in this format
     /// of the given dimensions.
     ///
@@ -28,8 +45,9 @@ impl ImageFormat {
 }
 
 /// Defines the desired quality for sampling an [image](Image).
-#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ImageQuality {
     /// Lowest quality with best performance characteristics.
     ///
@@ -46,6 +64,201 @@ pub enum ImageQuality {
     High,
 }
 
+impl_u8_conversions!(ImageQuality {
+    Low = 0,
+    Medium = 1,
+    High = 2,
+});
+
+impl ImageQuality {
+    /// All image qualities, from lowest to highest.
+    pub const ALL: [Self; 3] = [Self::Low, Self::Medium, Self::High];
+}
+
+/// Defines the desired filtering for sampling an [image](Image) when it is scaled
+/// down.
+///
+/// This is separate from the [`ImageQuality`], which describes the filter used
+/// within a level of detail, so that minification can be controlled without giving
+/// [`ImageQuality::High`] a different meaning in each renderer.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MinificationQuality {
+    /// The renderer chooses the filtering, typically based on the [`ImageQuality`].
+    #[default]
+    Auto,
+    /// The image is sampled without mipmaps.
+    Base,
+    /// The image is sampled from the nearest mipmap level.
+    Mipmap,
+    /// The image is sampled from the two nearest mipmap levels, and the samples are
+    /// blended.
+    Trilinear,
+    /// As with [`Trilinear`](Self::Trilinear), but with anisotropic filtering for
+    /// images which are scaled down more along one axis than the other.
+    Anisotropic,
+}
+
+impl_u8_conversions!(MinificationQuality {
+    Auto = 0,
+    Base = 1,
+    Mipmap = 2,
+    Trilinear = 3,
+    Anisotropic = 4,
+});
+
+impl MinificationQuality {
+    /// All minification qualities, with [`Auto`](Self::Auto) first and the others
+    /// from lowest to highest.
+    pub const ALL: [Self; 5] = [
+        Self::Auto,
+        Self::Base,
+        Self::Mipmap,
+        Self::Trilinear,
+        Self::Anisotropic,
+    ];
+
+    /// Returns whether sampling uses mipmaps, or `None` if the renderer chooses.
+    #[must_use]
+    pub fn uses_mipmaps(self) -> Option<bool> {
+        match self {
+            Self::Auto => None,
+            Self::Base => Some(false),
+            Self::Mipmap | Self::Trilinear | Self::Anisotropic => Some(true),
+        }
+    }
+}
+
+/// Defines how repeated tiles of an [image](Image) are fitted to the painted area
+/// along one axis.
+///
+/// This corresponds to the values of the CSS [`background-repeat`] property, and only
+/// applies when the [extend mode](Extend) for the axis is not [`Extend::Pad`].
+///
+/// [`background-repeat`]: https://drafts.csswg.org/css-backgrounds/#background-repeat
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileMode {
+    /// Tiles are repeated at their natural size.
+    #[default]
+    Repeat,
+    /// As many whole tiles as fit in the area are placed, and the remaining space
+    /// is distributed between them.
+    Space,
+    /// Tiles are scaled so that a whole number of them fills the area.
+    Round,
+}
+
+impl_u8_conversions!(TileMode {
+    Repeat = 0,
+    Space = 1,
+    Round = 2,
+});
+
+impl TileMode {
+    /// All tile modes.
+    pub const ALL: [Self; 3] = [Self::Repeat, Self::Space, Self::Round];
+
+    /// Resolves the tile layout along one axis.
+    ///
+    /// Given the `area` length to be painted, the natural `tile` length and the
+    /// minimum `spacing` between tiles, returns the length of each tile and the
+    /// distance between the starts of adjacent tiles.
+    ///
+    /// For [`TileMode::Space`], if fewer than two tiles fit in the area, a single tile
+    /// is placed and the returned distance is infinite. This is also the result for
+    /// both [`TileMode::Space`] and [`TileMode::Round`] when the tile length plus the
+    /// spacing is zero, as the number of tiles which fit is undefined.
+    #[must_use]
+    pub fn resolve(self, area: f64, tile: f64, spacing: f64) -> (f64, f64) {
+        match self {
+            Self::Repeat => (tile, tile + spacing),
+            Self::Space | Self::Round if tile + spacing == 0.0 => (tile, f64::INFINITY),
+            Self::Space => {
+                let count = ((area + spacing) / (tile + spacing)).floor();
+                if count < 2.0 {
+                    (tile, f64::INFINITY)
+                } else {
+                    (tile, tile + (area - count * tile) / (count - 1.0))
+                }
+            }
+            Self::Round => {
+                let count = ((area + spacing) / (tile + spacing)).round().max(1.0);
+                let step = (area + spacing) / count;
+                (step - spacing, step)
+            }
+        }
+    }
+}
+
+/// Defines how an [image](Image) is tiled when it is repeated.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageTiling {
+    /// Tile mode in the horizontal direction.
+    pub x_mode: TileMode,
+    /// Tile mode in the vertical direction.
+    pub y_mode: TileMode,
+    /// Minimum gap between adjacent tiles, in image pixels.
+    pub spacing: Vec2,
+    /// Position of the top left corner of one of the tiles, relative to the origin
+    /// of the brush.
+    ///
+    /// All other tiles are placed relative to this one, so this controls the phase
+    /// of the tiling.
+    pub origin: Point,
+}
+
+impl ImageTiling {
+    /// Builder method for setting the [tile mode](TileMode) in both directions.
+    #[must_use]
+    pub fn with_mode(mut self, mode: TileMode) -> Self {
+        self.x_mode = mode;
+        self.y_mode = mode;
+        self
+    }
+
+    /// Builder method for setting the minimum gap between adjacent tiles.
+    #[must_use]
+    pub fn with_spacing(mut self, spacing: impl Into<Vec2>) -> Self {
+        self.spacing = spacing.into();
+        self
+    }
+
+    /// Builder method for setting the position of the tiling origin.
+    #[must_use]
+    pub fn with_origin(mut self, origin: impl Into<Point>) -> Self {
+        self.origin = origin.into();
+        self
+    }
+}
+
+/// Texels and interpolation weights for sampling an [image](Image) at a point.
+///
+/// This is produced by [`Image::resolve_uv`]. The sampled color is the bilinear
+/// interpolation of the four texels at the combinations of `x0`/`x1` and `y0`/`y1`,
+/// where `fx` and `fy` are the weights of `x1` and `y1` respectively.
+///
+/// A column or row is `None` when it lies in the border of an axis with
+/// [`Extend::Border`], in which case each texel using it has the
+/// [border color](Image::border_color) of the image.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TexelCoords {
+    /// Column of the first texel, or `None` in the border.
+    pub x0: Option<u32>,
+    /// Row of the first texel, or `None` in the border.
+    pub y0: Option<u32>,
+    /// Column of the second texel, or `None` in the border.
+    pub x1: Option<u32>,
+    /// Row of the second texel, or `None` in the border.
+    pub y1: Option<u32>,
+    /// Horizontal interpolation weight of the second texel, in the range `[0, 1)`.
+    pub fx: f32,
+    /// Vertical interpolation weight of the second texel, in the range `[0, 1)`.
+    pub fy: f32,
+}
+
 /// Owned shareable image resource.
 #[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -64,8 +277,22 @@ pub struct Image {
     pub y_extend: Extend,
     /// Hint for desired rendering quality.
     pub quality: ImageQuality,
+    /// Hint for desired filtering when the image is scaled down.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub minification: MinificationQuality,
+    /// Tiling parameters used when the image is repeated.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tiling: ImageTiling,
     /// An additional alpha multiplier to use with the image.
     pub alpha: f32,
+    /// Color sampled outside of the image along axes with [`Extend::Border`].
+    #[cfg_attr(feature = "serde", serde(default = "transparent"))]
+    pub border_color: Color,
+}
+
+#[cfg(feature = "serde")]
+fn transparent() -> Color {
+    Color::TRANSPARENT
 }
 
 impl Image {
@@ -80,11 +307,39 @@ impl Image {
             x_extend: Extend::Pad,
             y_extend: Extend::Pad,
             quality: ImageQuality::Medium,
+            minification: MinificationQuality::Auto,
+            tiling: ImageTiling::default(),
             // Opaque
             alpha: 1.,
+            border_color: Color::TRANSPARENT,
         }
     }
 
+    /// Creates an image of the given [format](ImageFormat) and dimensions, with every
+    /// pixel set to `color`.
+    ///
+    /// This is useful for placeholders and tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the image in bytes overflows `usize`.
+    #[must_use]
+    pub fn from_color(color: Color, width: u32, height: u32, format: ImageFormat) -> Self {
+        let size = format
+            .size_in_bytes(width, height)
+            .expect("image size overflows usize");
+        let pixels = match format {
+            ImageFormat::Rgba8 => color.to_rgba8().to_u8_array().repeat(size / 4),
+        };
+        Self::new(Blob::from(pixels), format, width, height)
+    }
+
+    /// Creates an image with no pixels.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::new(Blob::from(Vec::new()), ImageFormat::Rgba8, 0, 0)
+    }
+
     /// Builder method for setting the image [extend mode](Extend) in both
     /// directions.
     #[must_use]
@@ -118,6 +373,62 @@ impl Image {
         self
     }
 
+    /// Builder method for setting a hint for the desired
+    /// [filtering](MinificationQuality) when the image is scaled down.
+    #[must_use]
+    pub fn with_minification(mut self, minification: MinificationQuality) -> Self {
+        self.minification = minification;
+        self
+    }
+
+    /// Builder method for setting the [tiling parameters](ImageTiling) used when the
+    /// image is repeated.
+    #[must_use]
+    pub fn with_tiling(mut self, tiling: ImageTiling) -> Self {
+        self.tiling = tiling;
+        self
+    }
+
+    /// Builder method for setting the [border color](Self::border_color), sampled
+    /// outside of the image along axes with [`Extend::Border`].
+    #[must_use]
+    pub fn with_border_color(mut self, color: impl Into<Color>) -> Self {
+        self.border_color = color.into();
+        self
+    }
+
+    /// Resolves normalized texture coordinates to the texels which are sampled for
+    /// them, according to the extend modes and quality of this image.
+    ///
+    /// The coordinates `(0, 0)` and `(1, 1)` are the top left and bottom right corners
+    /// of the image, so the center of the texel at `(x, y)` is at
+    /// `((x + 0.5) / width, (y + 0.5) / height)`.
+    ///
+    /// For [`ImageQuality::Low`], this selects the nearest texel and both weights are
+    /// zero. Otherwise, this selects the four texels surrounding the point for bilinear
+    /// filtering. Higher order filters for [`ImageQuality::High`] should be centered on
+    /// the same texels. Texels in the border of an axis with [`Extend::Border`] are
+    /// `None`, and have the [border color](Self::border_color).
+    ///
+    /// Returns `None` if the image has no texels.
+    #[must_use]
+    pub fn resolve_uv(&self, u: f32, v: f32) -> Option<TexelCoords> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let nearest = self.quality == ImageQuality::Low;
+        let (x0, x1, fx) = resolve_axis(u, self.width, self.x_extend, nearest);
+        let (y0, y1, fy) = resolve_axis(v, self.height, self.y_extend, nearest);
+        Some(TexelCoords {
+            x0,
+            y0,
+            x1,
+            y1,
+            fx,
+            fy,
+        })
+    }
+
     /// Returns the image with the alpha multiplier set to `alpha`.
     #[must_use]
     #[track_caller]
@@ -142,4 +453,666 @@ impl Image {
         self.alpha *= alpha;
         self
     }
+
+    /// Returns a borrowed view of the pixel data of the image.
+    #[must_use]
+    pub fn as_data_ref(&self) -> ImageDataRef<'_> {
+        ImageDataRef::new(self.data.data(), self.format, self.width, self.height)
+    }
+
+    /// Returns the average color of the image, including the alpha multiplier.
+    ///
+    /// See [`ImageDataRef::average_color`] for details.
+    #[must_use]
+    pub fn average_color(&self) -> Option<Color> {
+        self.as_data_ref()
+            .average_color()
+            .map(|color| color.multiply_alpha(self.alpha))
+    }
+
+    /// Creates a weak reference to the image.
+    ///
+    /// The weak reference doesn't keep the pixel data alive, so caches keyed by
+    /// [blob id](Blob::id) can hold one and evict their entry once the data is dropped.
+    #[must_use]
+    pub fn downgrade(&self) -> WeakImage {
+        WeakImage {
+            data: self.data.downgrade(),
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            x_extend: self.x_extend,
+            y_extend: self.y_extend,
+            quality: self.quality,
+            minification: self.minification,
+            tiling: self.tiling,
+            alpha: self.alpha,
+            border_color: self.border_color,
+        }
+    }
+
+    /// Returns a key which identifies the sampler state of the image.
+    ///
+    /// See [`ImageSamplerKey`] for details.
+    #[must_use]
+    pub fn sampler_key(&self) -> ImageSamplerKey {
+        ImageSamplerKey::new(self)
+    }
+}
+
+/// Weak reference to an [image](Image).
+///
+/// This is created by [`Image::downgrade`], and holds a [weak reference](WeakBlob) to
+/// the pixel data along with the other properties of the image.
+#[derive(Clone, Debug)]
+pub struct WeakImage {
+    data: WeakBlob<u8>,
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    x_extend: Extend,
+    y_extend: Extend,
+    quality: ImageQuality,
+    minification: MinificationQuality,
+    tiling: ImageTiling,
+    alpha: f32,
+    border_color: Color,
+}
+
+impl WeakImage {
+    /// Returns the unique identifier of the pixel data.
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        self.data.id()
+    }
+
+    /// Upgrades the weak reference. Returns `None` if the pixel data has been dropped.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Image> {
+        Some(Image {
+            data: self.data.upgrade()?,
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            x_extend: self.x_extend,
+            y_extend: self.y_extend,
+            quality: self.quality,
+            minification: self.minification,
+            tiling: self.tiling,
+            alpha: self.alpha,
+            border_color: self.border_color,
+        })
+    }
+}
+
+/// Hashable summary of the state used to sample an [image](Image).
+///
+/// This includes the extend modes, qualities and tiling of an image, but not its
+/// pixel data, so that renderers can share sampler state (such as GPU bind groups)
+/// between images which are sampled in the same way.
+///
+/// The tiling parameters are compared exactly, by their bit patterns. The alpha
+/// multiplier is quantized to multiples of [`ALPHA_STEP`](Self::ALPHA_STEP), so
+/// images whose alpha multipliers differ by less than half of a step (which is
+/// below the precision of an 8-bit color channel) usually share a key. The
+/// [border color](Image::border_color) is only included when either extend mode is
+/// [`Extend::Border`], as it isn't sampled otherwise.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ImageSamplerKey {
+    x_extend: Extend,
+    y_extend: Extend,
+    quality: ImageQuality,
+    minification: MinificationQuality,
+    x_mode: TileMode,
+    y_mode: TileMode,
+    tiling: [u64; 4],
+    alpha: u32,
+    border_color: [u32; 4],
+}
+
+impl ImageSamplerKey {
+    /// The step to which the alpha multiplier is quantized.
+    pub const ALPHA_STEP: f32 = 1.0 / 256.0;
+
+    fn new(image: &Image) -> Self {
+        let tiling = &image.tiling;
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Alpha multipliers are non-negative, and saturation is fine for large values."
+        )]
+        let alpha = (image.alpha / Self::ALPHA_STEP).round() as u32;
+        Self {
+            x_extend: image.x_extend,
+            y_extend: image.y_extend,
+            quality: image.quality,
+            minification: image.minification,
+            x_mode: tiling.x_mode,
+            y_mode: tiling.y_mode,
+            tiling: Self::tiling_bits(tiling),
+            alpha,
+            border_color: if image.x_extend == Extend::Border || image.y_extend == Extend::Border {
+                image.border_color.components.map(f32::to_bits)
+            } else {
+                [0; 4]
+            },
+        }
+    }
+
+    fn tiling_bits(tiling: &ImageTiling) -> [u64; 4] {
+        [
+            tiling.spacing.x.to_bits(),
+            tiling.spacing.y.to_bits(),
+            tiling.origin.x.to_bits(),
+            tiling.origin.y.to_bits(),
+        ]
+    }
+
+    /// Returns the alpha multiplier after quantization.
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        self.alpha as f32 * Self::ALPHA_STEP
+    }
+
+    /// Packs the extend modes, qualities and quantized alpha multiplier into a `u32`.
+    ///
+    /// This is useful for renderers which key texture and sampler caches on pairs
+    /// of a [blob id](Blob::id) and a small integer. The layout is stable across
+    /// versions, with the lowest bits first:
+    ///
+    /// | Bits   | Field                                                      |
+    /// |--------|------------------------------------------------------------|
+    /// | 0..3   | Horizontal [`Extend::to_u8`]                               |
+    /// | 3..6   | Vertical [`Extend::to_u8`]                                 |
+    /// | 6..8   | [`ImageQuality::to_u8`]                                    |
+    /// | 8..11  | [`MinificationQuality::to_u8`]                             |
+    /// | 11..20 | Alpha multiplier in [steps](Self::ALPHA_STEP), at most 256 |
+    /// | 20..32 | Zero, reserved for future fields                           |
+    ///
+    /// The extend fields are wider than the current modes need, to leave room for
+    /// new modes. Alpha multipliers above one are saturated.
+    ///
+    /// Returns `None` if either extend mode is [`Extend::Border`], or the
+    /// [tiling](Image::tiling) isn't the default, as the border color and tiling
+    /// parameters don't fit in the packed key. Renderers which support them should
+    /// use the full key.
+    #[must_use]
+    pub fn to_u32(&self) -> Option<u32> {
+        if self.x_extend == Extend::Border || self.y_extend == Extend::Border {
+            return None;
+        }
+        let tiling = ImageTiling::default();
+        if (self.x_mode, self.y_mode, self.tiling)
+            != (tiling.x_mode, tiling.y_mode, Self::tiling_bits(&tiling))
+        {
+            return None;
+        }
+        Some(
+            u32::from(self.x_extend.to_u8())
+                | u32::from(self.y_extend.to_u8()) << 3
+                | u32::from(self.quality.to_u8()) << 6
+                | u32::from(self.minification.to_u8()) << 8
+                | self.alpha.min(256) << 11,
+        )
+    }
+}
+
+/// Borrowed pixel data of an image.
+///
+/// This describes pixels in a buffer owned by the caller, such as a transient buffer
+/// which should not be copied into a [`Blob`]. It can be converted into an owned
+/// [`Image`] with [`to_image`](Self::to_image) when the pixels need to be retained.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ImageDataRef<'a> {
+    /// The pixel data, in rows from top to bottom without padding.
+    pub data: &'a [u8],
+    /// Pixel format of the data.
+    pub format: ImageFormat,
+    /// Width of the image.
+    pub width: u32,
+    /// Height of the image.
+    pub height: u32,
+}
+
+impl<'a> ImageDataRef<'a> {
+    /// Creates a new view of the given data with the given [format](ImageFormat) and
+    /// dimensions.
+    #[must_use]
+    pub fn new(data: &'a [u8], format: ImageFormat, width: u32, height: u32) -> Self {
+        Self {
+            data,
+            format,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the bytes of all pixels, or `None` if the data is too short for the
+    /// dimensions of the image.
+    #[must_use]
+    pub fn pixels(&self) -> Option<&'a [u8]> {
+        let len = self.format.size_in_bytes(self.width, self.height)?;
+        self.data.get(..len)
+    }
+
+    /// Returns the components of the pixel at the given position, or `None` if the
+    /// position is outside of the image or the data is too short.
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = self.format.size_in_bytes(self.width, y)? + self.format.size_in_bytes(x, 1)?;
+        let pixel = self.data.get(offset..offset + 4)?;
+        Some([pixel[0], pixel[1], pixel[2], pixel[3]])
+    }
+
+    /// Returns the average color of the pixels.
+    ///
+    /// The pixels are treated as sRGB with separate (not premultiplied) alpha, and are
+    /// averaged with premultiplied alpha without linearizing, as a renderer which
+    /// downsamples the image would. Every pixel is visited, so this is intended for
+    /// computing placeholder or theme colors ahead of time rather than per frame.
+    ///
+    /// Returns `None` if the image has no pixels or its data is too short for its
+    /// dimensions.
+    #[must_use]
+    pub fn average_color(&self) -> Option<Color> {
+        let pixels = self.pixels()?;
+        if pixels.is_empty() {
+            return None;
+        }
+        let mut sums = [0_u64; 4];
+        for pixel in pixels.chunks_exact(4) {
+            let alpha = u64::from(pixel[3]);
+            for (sum, channel) in sums.iter_mut().zip(&pixel[..3]) {
+                *sum += u64::from(*channel) * alpha;
+            }
+            sums[3] += alpha;
+        }
+        if sums[3] == 0 {
+            return Some(Color::TRANSPARENT);
+        }
+        let count = (pixels.len() / 4) as f64;
+        let alpha = sums[3] as f64;
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "The averages are in the range `[0, 1]`."
+        )]
+        Some(Color::new([
+            (sums[0] as f64 / (alpha * 255.0)) as f32,
+            (sums[1] as f64 / (alpha * 255.0)) as f32,
+            (sums[2] as f64 / (alpha * 255.0)) as f32,
+            (alpha / (count * 255.0)) as f32,
+        ]))
+    }
+
+    /// Copies the pixels into a new owned [`Image`].
+    ///
+    /// Returns `None` if the data is too short for the dimensions of the image.
+    #[must_use]
+    pub fn to_image(&self) -> Option<Image> {
+        let pixels = self.pixels()?;
+        Some(Image::new(
+            Blob::from(pixels.to_vec()),
+            self.format,
+            self.width,
+            self.height,
+        ))
+    }
+}
+
+/// Borrowed form of an [image](Image), for use as the input of scene encoders.
+///
+/// This copies the sampling parameters of the image, and borrows its pixel data.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EncodeImage<'a> {
+    /// The pixel data of the image.
+    pub data: ImageDataRef<'a>,
+    /// Extend mode in the horizontal direction.
+    pub x_extend: Extend,
+    /// Extend mode in the vertical direction.
+    pub y_extend: Extend,
+    /// Hint for desired rendering quality.
+    pub quality: ImageQuality,
+    /// Hint for desired filtering when the image is scaled down.
+    pub minification: MinificationQuality,
+    /// Tiling parameters used when the image is repeated.
+    pub tiling: ImageTiling,
+    /// An additional alpha multiplier to use with the image.
+    pub alpha: f32,
+    /// Color sampled outside of the image along axes with [`Extend::Border`].
+    pub border_color: Color,
+}
+
+impl<'a> From<&'a Image> for EncodeImage<'a> {
+    fn from(image: &'a Image) -> Self {
+        Self {
+            data: image.as_data_ref(),
+            x_extend: image.x_extend,
+            y_extend: image.y_extend,
+            quality: image.quality,
+            minification: image.minification,
+            tiling: image.tiling,
+            alpha: image.alpha,
+            border_color: image.border_color,
+        }
+    }
+}
+
+/// Resolves a normalized coordinate along one axis to a pair of texels, which are
+/// `None` in the border, and the weight of the second.
+fn resolve_axis(
+    coord: f32,
+    size: u32,
+    extend: Extend,
+    nearest: bool,
+) -> (Option<u32>, Option<u32>, f32) {
+    let scaled = coord * size as f32;
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Saturation is acceptable for coordinates which are far outside the image."
+    )]
+    if nearest {
+        let texel = extend.resolve_texel_or_border(scaled.floor() as i64, size);
+        (texel, texel, 0.0)
+    } else {
+        let centered = scaled - 0.5;
+        let floor = centered.floor();
+        let index = floor as i64;
+        (
+            extend.resolve_texel_or_border(index, size),
+            extend.resolve_texel_or_border(index.saturating_add(1), size),
+            centered - floor,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Image, ImageDataRef, ImageFormat, ImageQuality, ImageSamplerKey, ImageTiling,
+        MinificationQuality, TexelCoords, TileMode,
+    };
+    use crate::{Blob, Extend};
+    use color::palette;
+
+    #[test]
+    fn solid_color_images() {
+        let image = Image::from_color(palette::css::RED.with_alpha(0.5), 3, 2, ImageFormat::Rgba8);
+        assert_eq!(image.data.len(), 24);
+        let data = image.as_data_ref();
+        assert_eq!(data.pixel(2, 1), Some([255, 0, 0, 128]));
+        assert_eq!(data.pixel(3, 0), None);
+
+        let empty = Image::empty();
+        assert_eq!((empty.width, empty.height), (0, 0));
+        assert!(empty.data.is_empty());
+        assert_eq!(empty.average_color(), None);
+    }
+
+    #[test]
+    fn weak_image() {
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1).with_alpha(0.5);
+        let weak = image.downgrade();
+        assert_eq!(weak.id(), image.data.id());
+        assert_eq!(weak.upgrade(), Some(image.clone()));
+        drop(image);
+        assert_eq!(weak.upgrade(), None);
+    }
+
+    #[test]
+    fn sampler_key() {
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1).with_alpha(0.5);
+        let other = Image::new(Blob::from(vec![255; 16]), ImageFormat::Rgba8, 2, 2)
+            .with_alpha(0.5 + ImageSamplerKey::ALPHA_STEP / 4.0);
+        // The pixel data doesn't matter, and alpha is quantized.
+        assert_eq!(image.sampler_key(), other.sampler_key());
+        assert_eq!(image.sampler_key().alpha(), 0.5);
+        assert_ne!(
+            image.sampler_key(),
+            other
+                .clone()
+                .with_alpha(0.5 + ImageSamplerKey::ALPHA_STEP)
+                .sampler_key()
+        );
+        assert_ne!(
+            image.sampler_key(),
+            other.clone().with_quality(ImageQuality::High).sampler_key()
+        );
+        assert_ne!(
+            image.sampler_key(),
+            other
+                .with_tiling(ImageTiling::default().with_spacing((1.0, 0.0)))
+                .sampler_key()
+        );
+    }
+
+    #[test]
+    fn minification() {
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        assert_eq!(image.minification, MinificationQuality::Auto);
+        assert_eq!(image.minification.uses_mipmaps(), None);
+        let trilinear = image
+            .clone()
+            .with_minification(MinificationQuality::Trilinear);
+        assert_eq!(trilinear.minification.uses_mipmaps(), Some(true));
+        assert_eq!(MinificationQuality::Base.uses_mipmaps(), Some(false));
+        assert_ne!(image.sampler_key(), trilinear.sampler_key());
+        assert_eq!(
+            trilinear.downgrade().upgrade().unwrap().minification,
+            MinificationQuality::Trilinear
+        );
+    }
+
+    #[test]
+    fn packed_sampler_key() {
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        assert_eq!(image.sampler_key().to_u32(), Some(256 << 11 | 1 << 6));
+        let packed = image
+            .clone()
+            .with_x_extend(Extend::Reflect)
+            .with_y_extend(Extend::Repeat)
+            .with_quality(ImageQuality::High)
+            .with_minification(MinificationQuality::Anisotropic)
+            .with_alpha(0.5)
+            .sampler_key()
+            .to_u32();
+        assert_eq!(packed, Some(128 << 11 | 4 << 8 | 2 << 6 | 1 << 3 | 2));
+        // Alpha multipliers above one are saturated.
+        assert_eq!(
+            image.clone().with_alpha(2.0).sampler_key().to_u32(),
+            image.sampler_key().to_u32()
+        );
+        // The border color and tiling parameters can't be packed.
+        assert_eq!(
+            image
+                .clone()
+                .with_y_extend(Extend::Border)
+                .sampler_key()
+                .to_u32(),
+            None
+        );
+        assert_eq!(
+            image
+                .clone()
+                .with_tiling(ImageTiling::default().with_mode(TileMode::Space))
+                .sampler_key()
+                .to_u32(),
+            None
+        );
+        assert_eq!(
+            image
+                .with_tiling(ImageTiling::default().with_origin((0.5, 0.0)))
+                .sampler_key()
+                .to_u32(),
+            None
+        );
+    }
+
+    #[test]
+    fn border_sampler_key() {
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        // The border color is only part of the key when it can be sampled.
+        assert_eq!(
+            image.sampler_key(),
+            image
+                .clone()
+                .with_border_color(palette::css::RED)
+                .sampler_key()
+        );
+        let border = image.with_x_extend(Extend::Border);
+        assert_ne!(
+            border.sampler_key(),
+            border
+                .clone()
+                .with_border_color(palette::css::RED)
+                .sampler_key()
+        );
+    }
+
+    #[test]
+    fn resolve_tile_modes() {
+        assert_eq!(TileMode::Repeat.resolve(100.0, 30.0, 5.0), (30.0, 35.0));
+        // Three tiles fit, leaving 10 units to distribute between two gaps.
+        assert_eq!(TileMode::Space.resolve(100.0, 30.0, 0.0), (30.0, 35.0));
+        assert_eq!(TileMode::Space.resolve(50.0, 30.0, 0.0).1, f64::INFINITY);
+        // 100 / 30 rounds to three tiles.
+        let (tile, step) = TileMode::Round.resolve(100.0, 30.0, 0.0);
+        assert_eq!(step, 100.0 / 3.0);
+        assert_eq!(tile, step);
+        // With spacing, three tiles and two gaps fill the area exactly.
+        let (spaced_tile, spaced_step) = TileMode::Round.resolve(100.0, 30.0, 5.0);
+        assert!((3.0 * spaced_tile + 2.0 * 5.0 - 100.0).abs() < 1e-9);
+        assert_eq!(spaced_step, spaced_tile + 5.0);
+        // Without a tile length or spacing, a single tile is placed.
+        for mode in [TileMode::Space, TileMode::Round] {
+            assert_eq!(mode.resolve(100.0, 0.0, 0.0), (0.0, f64::INFINITY));
+            assert_eq!(mode.resolve(100.0, 5.0, -5.0), (5.0, f64::INFINITY));
+            assert_eq!(mode.resolve(0.0, 0.0, 0.0), (0.0, f64::INFINITY));
+        }
+    }
+
+    #[test]
+    fn resolve_uv_extends() {
+        let image = Image::new(Blob::from(vec![0; 4 * 4 * 2]), ImageFormat::Rgba8, 4, 2);
+        // Center of the first texel.
+        assert_eq!(
+            image.resolve_uv(0.125, 0.25),
+            Some(TexelCoords {
+                x0: Some(0),
+                y0: Some(0),
+                x1: Some(1),
+                y1: Some(1),
+                fx: 0.0,
+                fy: 0.0
+            })
+        );
+        // Halfway between the last texel and the one after it.
+        let pad = image.resolve_uv(1.0, 0.5).unwrap();
+        assert_eq!((pad.x0, pad.x1, pad.fx), (Some(3), Some(3), 0.5));
+        let repeat = image
+            .clone()
+            .with_x_extend(Extend::Repeat)
+            .resolve_uv(1.0, 0.5)
+            .unwrap();
+        assert_eq!((repeat.x0, repeat.x1), (Some(3), Some(0)));
+        let reflect = image
+            .clone()
+            .with_x_extend(Extend::Reflect)
+            .resolve_uv(-0.375, 0.5)
+            .unwrap();
+        // Texel -1 reflects to texel 0 and texel -2 to texel 1.
+        assert_eq!(
+            (reflect.x0, reflect.x1, reflect.fx),
+            (Some(1), Some(0), 0.0)
+        );
+        // Texels after the last one are in the border.
+        let border = image
+            .clone()
+            .with_x_extend(Extend::Border)
+            .resolve_uv(1.0, 0.5)
+            .unwrap();
+        assert_eq!((border.x0, border.x1, border.fx), (Some(3), None, 0.5));
+        assert_eq!((border.y0, border.y1), (Some(0), Some(1)));
+        let nearest = image
+            .with_quality(ImageQuality::Low)
+            .resolve_uv(0.99, 0.49)
+            .unwrap();
+        assert_eq!(
+            (nearest.x0, nearest.y0, nearest.fx),
+            (Some(3), Some(0), 0.0)
+        );
+    }
+
+    #[test]
+    fn average_color() {
+        let pixels = vec![
+            255, 0, 0, 255, //
+            0, 0, 255, 255, //
+            0, 255, 0, 0, //
+            0, 255, 0, 0,
+        ];
+        let image = Image::new(Blob::from(pixels), ImageFormat::Rgba8, 2, 2);
+        let average = image.average_color().unwrap();
+        // Transparent pixels only contribute to the alpha.
+        assert_eq!(average.components, [0.5, 0.0, 0.5, 0.5]);
+        let empty = Image::new(Blob::from(vec![]), ImageFormat::Rgba8, 0, 0);
+        assert_eq!(empty.average_color(), None);
+    }
+
+    #[test]
+    fn borrowed_image_data() {
+        let pixels = [
+            1, 2, 3, 4, 5, 6, 7, 8, //
+            9, 10, 11, 12, 13, 14, 15, 16,
+        ];
+        let data = ImageDataRef::new(&pixels, ImageFormat::Rgba8, 2, 2);
+        assert_eq!(data.pixel(1, 1), Some([13, 14, 15, 16]));
+        assert_eq!(data.pixel(2, 0), None);
+        let image = data.to_image().unwrap();
+        assert_eq!(image.as_data_ref(), data);
+        // Data which is too short for the dimensions is rejected.
+        let short = ImageDataRef::new(&pixels[..12], ImageFormat::Rgba8, 2, 2);
+        assert_eq!(short.pixel(1, 1), None);
+        assert!(short.to_image().is_none());
+    }
+
+    #[test]
+    fn u8_conversions() {
+        // The representations of enums without explicit discriminants are their
+        // indices in `ALL`.
+        for (index, quality) in (0_u8..).zip(ImageQuality::ALL) {
+            assert_eq!(quality.to_u8(), index);
+            assert_eq!(ImageQuality::from_u8(index), Some(quality));
+        }
+        for (index, minification) in (0_u8..).zip(MinificationQuality::ALL) {
+            assert_eq!(minification.to_u8(), index);
+            assert_eq!(MinificationQuality::from_u8(index), Some(minification));
+        }
+        for (index, mode) in (0_u8..).zip(TileMode::ALL) {
+            assert_eq!(mode.to_u8(), index);
+            assert_eq!(TileMode::from_u8(index), Some(mode));
+        }
+        assert_eq!(
+            ImageFormat::from_u8(ImageFormat::Rgba8.to_u8()),
+            Some(ImageFormat::Rgba8)
+        );
+        assert_eq!(ImageFormat::from_u8(1), None);
+        assert_eq!(MinificationQuality::from_u8(5), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn deserialize_without_newer_fields() {
+        let image = Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1);
+        let mut value = serde_json::to_value(&image).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        for field in ["minification", "tiling", "border_color"] {
+            assert!(fields.remove(field).is_some());
+        }
+        let deserialized: Image = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.tiling, ImageTiling::default());
+        assert_eq!(deserialized.minification, MinificationQuality::default());
+        assert_eq!(deserialized.border_color, image.border_color);
+    }
 }
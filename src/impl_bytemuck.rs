@@ -52,7 +52,7 @@ unsafe impl bytemuck::checked::CheckedBitPattern for Extend {
 unsafe impl bytemuck::Contiguous for Extend {
     type Int = u8;
     const MIN_VALUE: u8 = Self::Pad as u8;
-    const MAX_VALUE: u8 = Self::Reflect as u8;
+    const MAX_VALUE: u8 = Self::Border as u8;
 }
 
 // Safety: The enum is `repr(u8)` and has only fieldless variants.
@@ -140,6 +140,25 @@ mod tests {
         assert_eq!(None, Fill::from_integer(255));
     }
 
+    #[test]
+    fn all_values_round_trip() {
+        fn check<T: Contiguous<Int = u8> + Copy + PartialEq + core::fmt::Debug>(all: &[T]) {
+            for value in all {
+                assert_eq!(T::from_integer(value.into_integer()), Some(*value));
+            }
+            assert_eq!(all.first().map(|v| v.into_integer()), Some(T::MIN_VALUE));
+            assert_eq!(all.last().map(|v| v.into_integer()), Some(T::MAX_VALUE));
+        }
+        check(&Compose::ALL);
+        check(&Extend::ALL);
+        check(&Fill::ALL);
+        // Mix is not contiguous, but every valid bit pattern is listed.
+        let valid = (0..=u8::MAX)
+            .filter(|bits| try_from_bytes::<Mix>(bytemuck::bytes_of(bits)).is_ok())
+            .count();
+        assert_eq!(valid, Mix::ALL.len());
+    }
+
     #[test]
     fn zeroable() {
         let compose = Compose::zeroed();
@@ -0,0 +1,629 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Runtime introspection of styles, for editors and debugging tools.
+//!
+//! The [`Inspect`] trait lists the editable fields of a value, and gets and sets
+//! them as [`Value`]s, so that a user interface can expose the state of brushes,
+//! gradients, styles and blend modes without code specific to each type.
+//!
+//! The fields of an enum depend on its current variant. For example, a solid
+//! [`Brush`] has a `color` field, while a gradient brush has the fields of the
+//! [`Gradient`]. Changing the variant itself, or editing the color stops of a
+//! gradient, is done through the types directly.
+
+use core::fmt;
+
+use color::DynamicColor;
+
+#[cfg(feature = "gradient")]
+use crate::GradientKind;
+use crate::{
+    BlendMode, Brush, Color, Compose, Extend, Gradient, Image, ImageQuality, MinificationQuality,
+    Mix, Point,
+};
+#[cfg(feature = "kurbo")]
+use crate::{Fill, Style};
+#[cfg(feature = "kurbo")]
+use kurbo::{Cap, Join};
+
+/// The value of a field.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum Value {
+    /// A 32-bit floating point number.
+    F32(f32),
+    /// A 64-bit floating point number.
+    F64(f64),
+    /// A point.
+    Point(Point),
+    /// An sRGB color.
+    Color(Color),
+    /// A color in any color space.
+    DynamicColor(DynamicColor),
+    /// A variant of a fieldless enum, by name.
+    Enum(&'static str),
+}
+
+impl Value {
+    /// Returns true if the value is of the given kind.
+    fn matches(&self, kind: ValueKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::F32(_), ValueKind::F32)
+                | (Self::F64(_), ValueKind::F64)
+                | (Self::Point(_), ValueKind::Point)
+                | (Self::Color(_), ValueKind::Color)
+                | (Self::DynamicColor(_), ValueKind::DynamicColor)
+                | (Self::Enum(_), ValueKind::Enum(_))
+        )
+    }
+}
+
+/// The kind of value held by a field.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ValueKind {
+    /// A 32-bit floating point number.
+    F32,
+    /// A 64-bit floating point number.
+    F64,
+    /// A point.
+    Point,
+    /// An sRGB color.
+    Color,
+    /// A color in any color space.
+    DynamicColor,
+    /// A fieldless enum, with the names of its variants.
+    Enum(&'static [&'static str]),
+}
+
+/// Description of a field of an inspectable value.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Field {
+    /// Name of the field.
+    pub name: &'static str,
+    /// Kind of value held by the field.
+    pub kind: ValueKind,
+}
+
+impl Field {
+    const fn new(name: &'static str, kind: ValueKind) -> Self {
+        Self { name, kind }
+    }
+}
+
+/// Errors which can occur when setting a field.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum InspectError {
+    /// The value doesn't have a field with the given name.
+    UnknownField,
+    /// The value has a different kind from the field.
+    KindMismatch,
+    /// The name isn't a variant of the enum held by the field.
+    UnknownVariant,
+}
+
+impl fmt::Display for InspectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnknownField => "unknown field",
+            Self::KindMismatch => "value has a different kind from the field",
+            Self::UnknownVariant => "unknown enum variant",
+        })
+    }
+}
+
+impl core::error::Error for InspectError {}
+
+/// Types whose fields can be listed, read and written at runtime.
+pub trait Inspect {
+    /// Returns the fields of the value.
+    fn fields(&self) -> &'static [Field];
+
+    /// Returns the value of the field with the given name, if there is one.
+    fn get(&self, name: &str) -> Option<Value>;
+
+    /// Sets the value of the field with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such field, or the value doesn't fit it.
+    fn set(&mut self, name: &str, value: Value) -> Result<(), InspectError>;
+}
+
+/// Names of the variants of a fieldless enum, in the order of its `ALL` constant.
+struct EnumNames<T: 'static, const N: usize> {
+    all: [T; N],
+    names: &'static [&'static str; N],
+}
+
+impl<T: Copy + PartialEq, const N: usize> EnumNames<T, N> {
+    const fn kind(&self) -> ValueKind {
+        ValueKind::Enum(self.names)
+    }
+
+    fn get(&self, value: T) -> Value {
+        let index = self.all.iter().position(|v| *v == value).unwrap_or(0);
+        Value::Enum(self.names[index])
+    }
+
+    fn parse(&self, value: Value) -> Result<T, InspectError> {
+        let Value::Enum(name) = value else {
+            return Err(InspectError::KindMismatch);
+        };
+        self.names
+            .iter()
+            .position(|n| *n == name)
+            .map(|index| self.all[index])
+            .ok_or(InspectError::UnknownVariant)
+    }
+}
+
+const MIX_NAMES: [&str; 17] = {
+    let mut names = [""; 17];
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = Mix::ALL[i].name();
+        i += 1;
+    }
+    names
+};
+
+const COMPOSE_NAMES: [&str; 14] = {
+    let mut names = [""; 14];
+    let mut i = 0;
+    while i < names.len() {
+        names[i] = Compose::ALL[i].name();
+        i += 1;
+    }
+    names
+};
+
+const MIX: EnumNames<Mix, 17> = EnumNames {
+    all: Mix::ALL,
+    names: &MIX_NAMES,
+};
+const COMPOSE: EnumNames<Compose, 14> = EnumNames {
+    all: Compose::ALL,
+    names: &COMPOSE_NAMES,
+};
+const EXTEND: EnumNames<Extend, 4> = EnumNames {
+    all: Extend::ALL,
+    names: &["pad", "repeat", "reflect", "border"],
+};
+const QUALITY: EnumNames<ImageQuality, 3> = EnumNames {
+    all: ImageQuality::ALL,
+    names: &["low", "medium", "high"],
+};
+const MINIFICATION: EnumNames<MinificationQuality, 5> = EnumNames {
+    all: MinificationQuality::ALL,
+    names: &["auto", "base", "mipmap", "trilinear", "anisotropic"],
+};
+#[cfg(feature = "kurbo")]
+const FILL: EnumNames<Fill, 2> = EnumNames {
+    all: Fill::ALL,
+    names: &["non-zero", "even-odd"],
+};
+#[cfg(feature = "kurbo")]
+const JOIN: EnumNames<Join, 3> = EnumNames {
+    all: [Join::Bevel, Join::Miter, Join::Round],
+    names: &["bevel", "miter", "round"],
+};
+#[cfg(feature = "kurbo")]
+const CAP: EnumNames<Cap, 3> = EnumNames {
+    all: [Cap::Butt, Cap::Square, Cap::Round],
+    names: &["butt", "square", "round"],
+};
+
+/// Checks that there is a field with the given name, and that the value fits it.
+fn check(fields: &[Field], name: &str, value: &Value) -> Result<(), InspectError> {
+    let field = fields
+        .iter()
+        .find(|field| field.name == name)
+        .ok_or(InspectError::UnknownField)?;
+    if value.matches(field.kind) {
+        Ok(())
+    } else {
+        Err(InspectError::KindMismatch)
+    }
+}
+
+impl Inspect for BlendMode {
+    fn fields(&self) -> &'static [Field] {
+        const FIELDS: &[Field] = &[
+            Field::new("mix", MIX.kind()),
+            Field::new("compose", COMPOSE.kind()),
+        ];
+        FIELDS
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match name {
+            "mix" => Some(MIX.get(self.mix)),
+            "compose" => Some(COMPOSE.get(self.compose)),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<(), InspectError> {
+        check(self.fields(), name, &value)?;
+        match name {
+            "mix" => self.mix = MIX.parse(value)?,
+            _ => self.compose = COMPOSE.parse(value)?,
+        }
+        Ok(())
+    }
+}
+
+/// The sampling parameters of the image are inspectable; the pixel data isn't.
+impl Inspect for Image {
+    fn fields(&self) -> &'static [Field] {
+        const FIELDS: &[Field] = &[
+            Field::new("x_extend", EXTEND.kind()),
+            Field::new("y_extend", EXTEND.kind()),
+            Field::new("quality", QUALITY.kind()),
+            Field::new("minification", MINIFICATION.kind()),
+            Field::new("alpha", ValueKind::F32),
+            Field::new("border_color", ValueKind::Color),
+        ];
+        FIELDS
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match name {
+            "x_extend" => Some(EXTEND.get(self.x_extend)),
+            "y_extend" => Some(EXTEND.get(self.y_extend)),
+            "quality" => Some(QUALITY.get(self.quality)),
+            "minification" => Some(MINIFICATION.get(self.minification)),
+            "alpha" => Some(Value::F32(self.alpha)),
+            "border_color" => Some(Value::Color(self.border_color)),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<(), InspectError> {
+        check(self.fields(), name, &value)?;
+        match (name, value) {
+            ("x_extend", _) => self.x_extend = EXTEND.parse(value)?,
+            ("y_extend", _) => self.y_extend = EXTEND.parse(value)?,
+            ("quality", _) => self.quality = QUALITY.parse(value)?,
+            ("minification", _) => self.minification = MINIFICATION.parse(value)?,
+            (_, Value::F32(alpha)) => self.alpha = alpha,
+            (_, Value::Color(color)) => self.border_color = color,
+            _ => return Err(InspectError::KindMismatch),
+        }
+        Ok(())
+    }
+}
+
+/// The extend mode and the position of the gradient are inspectable.
+#[cfg(feature = "gradient")]
+impl Inspect for Gradient {
+    fn fields(&self) -> &'static [Field] {
+        const LINEAR: &[Field] = &[
+            Field::new("extend", EXTEND.kind()),
+            Field::new("start", ValueKind::Point),
+            Field::new("end", ValueKind::Point),
+        ];
+        const RADIAL: &[Field] = &[
+            Field::new("extend", EXTEND.kind()),
+            Field::new("start_center", ValueKind::Point),
+            Field::new("start_radius", ValueKind::F32),
+            Field::new("end_center", ValueKind::Point),
+            Field::new("end_radius", ValueKind::F32),
+        ];
+        const SWEEP: &[Field] = &[
+            Field::new("extend", EXTEND.kind()),
+            Field::new("center", ValueKind::Point),
+            Field::new("start_angle", ValueKind::F32),
+            Field::new("end_angle", ValueKind::F32),
+        ];
+        match self.kind {
+            GradientKind::Linear { .. } => LINEAR,
+            GradientKind::Radial { .. } => RADIAL,
+            GradientKind::Sweep { .. } => SWEEP,
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        if name == "extend" {
+            return Some(EXTEND.get(self.extend));
+        }
+        match (self.kind, name) {
+            (GradientKind::Linear { start, .. }, "start") => Some(Value::Point(start)),
+            (GradientKind::Linear { end, .. }, "end") => Some(Value::Point(end)),
+            (GradientKind::Radial { start_center, .. }, "start_center") => {
+                Some(Value::Point(start_center))
+            }
+            (GradientKind::Radial { start_radius, .. }, "start_radius") => {
+                Some(Value::F32(start_radius))
+            }
+            (GradientKind::Radial { end_center, .. }, "end_center") => {
+                Some(Value::Point(end_center))
+            }
+            (GradientKind::Radial { end_radius, .. }, "end_radius") => Some(Value::F32(end_radius)),
+            (GradientKind::Sweep { center, .. }, "center") => Some(Value::Point(center)),
+            (GradientKind::Sweep { start_angle, .. }, "start_angle") => {
+                Some(Value::F32(start_angle))
+            }
+            (GradientKind::Sweep { end_angle, .. }, "end_angle") => Some(Value::F32(end_angle)),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<(), InspectError> {
+        check(self.fields(), name, &value)?;
+        if name == "extend" {
+            self.extend = EXTEND.parse(value)?;
+            return Ok(());
+        }
+        match (&mut self.kind, name, value) {
+            (GradientKind::Linear { start: field, .. }, "start", Value::Point(point))
+            | (GradientKind::Linear { end: field, .. }, "end", Value::Point(point))
+            | (
+                GradientKind::Radial {
+                    start_center: field,
+                    ..
+                },
+                "start_center",
+                Value::Point(point),
+            )
+            | (
+                GradientKind::Radial {
+                    end_center: field, ..
+                },
+                "end_center",
+                Value::Point(point),
+            )
+            | (GradientKind::Sweep { center: field, .. }, "center", Value::Point(point)) => {
+                *field = point;
+            }
+            (
+                GradientKind::Radial {
+                    start_radius: field,
+                    ..
+                },
+                "start_radius",
+                Value::F32(x),
+            )
+            | (
+                GradientKind::Radial {
+                    end_radius: field, ..
+                },
+                "end_radius",
+                Value::F32(x),
+            )
+            | (
+                GradientKind::Sweep {
+                    start_angle: field, ..
+                },
+                "start_angle",
+                Value::F32(x),
+            )
+            | (
+                GradientKind::Sweep {
+                    end_angle: field, ..
+                },
+                "end_angle",
+                Value::F32(x),
+            ) => {
+                *field = x;
+            }
+            _ => return Err(InspectError::KindMismatch),
+        }
+        Ok(())
+    }
+}
+
+/// A gradient can't be constructed without the `gradient` feature.
+#[cfg(not(feature = "gradient"))]
+impl Inspect for Gradient {
+    fn fields(&self) -> &'static [Field] {
+        &[]
+    }
+
+    fn get(&self, _name: &str) -> Option<Value> {
+        None
+    }
+
+    fn set(&mut self, _name: &str, _value: Value) -> Result<(), InspectError> {
+        Err(InspectError::UnknownField)
+    }
+}
+
+/// Solid brushes have a `color` field, while gradient and image brushes have
+/// the fields of the [`Gradient`] or [`Image`]. Procedural brushes don't have
+/// any inspectable fields.
+impl Inspect for Brush {
+    fn fields(&self) -> &'static [Field] {
+        const SOLID: &[Field] = &[Field::new("color", ValueKind::Color)];
+        const DYNAMIC_SOLID: &[Field] = &[Field::new("color", ValueKind::DynamicColor)];
+        match self {
+            Self::Solid(_) => SOLID,
+            Self::DynamicSolid(_) => DYNAMIC_SOLID,
+            Self::Gradient(gradient) => gradient.fields(),
+            Self::Image(image) => image.fields(),
+            Self::Procedural(_) => &[],
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match (self, name) {
+            (Self::Solid(color), "color") => Some(Value::Color(*color)),
+            (Self::DynamicSolid(color), "color") => Some(Value::DynamicColor(*color)),
+            (Self::Gradient(gradient), _) => gradient.get(name),
+            (Self::Image(image), _) => image.get(name),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<(), InspectError> {
+        match (self, name, value) {
+            (Self::Solid(color), "color", Value::Color(value)) => *color = value,
+            (Self::DynamicSolid(color), "color", Value::DynamicColor(value)) => *color = value,
+            (Self::Solid(_) | Self::DynamicSolid(_), "color", _) => {
+                return Err(InspectError::KindMismatch);
+            }
+            (Self::Gradient(gradient), _, _) => return gradient.set(name, value),
+            (Self::Image(image), _, _) => return image.set(name, value),
+            _ => return Err(InspectError::UnknownField),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl Inspect for Style {
+    fn fields(&self) -> &'static [Field] {
+        const FILL_FIELDS: &[Field] = &[Field::new("fill", FILL.kind())];
+        const STROKE_FIELDS: &[Field] = &[
+            Field::new("width", ValueKind::F64),
+            Field::new("join", JOIN.kind()),
+            Field::new("miter_limit", ValueKind::F64),
+            Field::new("start_cap", CAP.kind()),
+            Field::new("end_cap", CAP.kind()),
+            Field::new("dash_offset", ValueKind::F64),
+        ];
+        match self {
+            Self::Fill(_) => FILL_FIELDS,
+            Self::Stroke(_) => STROKE_FIELDS,
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match (self, name) {
+            (Self::Fill(fill), "fill") => Some(FILL.get(*fill)),
+            (Self::Stroke(stroke), "width") => Some(Value::F64(stroke.width)),
+            (Self::Stroke(stroke), "join") => Some(JOIN.get(stroke.join)),
+            (Self::Stroke(stroke), "miter_limit") => Some(Value::F64(stroke.miter_limit)),
+            (Self::Stroke(stroke), "start_cap") => Some(CAP.get(stroke.start_cap)),
+            (Self::Stroke(stroke), "end_cap") => Some(CAP.get(stroke.end_cap)),
+            (Self::Stroke(stroke), "dash_offset") => Some(Value::F64(stroke.dash_offset)),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<(), InspectError> {
+        check(self.fields(), name, &value)?;
+        match (self, name, value) {
+            (Self::Fill(fill), _, _) => *fill = FILL.parse(value)?,
+            (Self::Stroke(stroke), "join", _) => stroke.join = JOIN.parse(value)?,
+            (Self::Stroke(stroke), "start_cap", _) => stroke.start_cap = CAP.parse(value)?,
+            (Self::Stroke(stroke), "end_cap", _) => stroke.end_cap = CAP.parse(value)?,
+            (Self::Stroke(stroke), "width", Value::F64(x)) => stroke.width = x,
+            (Self::Stroke(stroke), "miter_limit", Value::F64(x)) => stroke.miter_limit = x,
+            (Self::Stroke(stroke), "dash_offset", Value::F64(x)) => stroke.dash_offset = x,
+            _ => return Err(InspectError::KindMismatch),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Inspect, InspectError, Value, ValueKind};
+    use crate::{BlendMode, Blob, Brush, Color, Compose, Image, ImageFormat, ImageQuality, Mix};
+
+    /// Every listed field can be read, and written back with its own value.
+    fn check_fields(value: &mut impl Inspect) {
+        for field in value.fields() {
+            let current = value.get(field.name).expect("listed fields are readable");
+            value.set(field.name, current).unwrap();
+            assert_eq!(value.get(field.name), Some(current));
+        }
+    }
+
+    #[test]
+    fn blend_mode() {
+        let mut mode = BlendMode::default();
+        check_fields(&mut mode);
+        assert_eq!(mode.get("mix"), Some(Value::Enum("clip")));
+        let ValueKind::Enum(names) = mode.fields()[1].kind else {
+            panic!("compose is an enum");
+        };
+        assert_eq!(names.len(), Compose::ALL.len());
+        mode.set("mix", Value::Enum("multiply")).unwrap();
+        assert_eq!(mode.mix, Mix::Multiply);
+        assert_eq!(
+            mode.set("mix", Value::Enum("sparkle")),
+            Err(InspectError::UnknownVariant)
+        );
+        assert_eq!(
+            mode.set("mix", Value::F32(1.0)),
+            Err(InspectError::KindMismatch)
+        );
+        assert_eq!(
+            mode.set("alpha", Value::F32(1.0)),
+            Err(InspectError::UnknownField)
+        );
+    }
+
+    #[test]
+    fn brushes() {
+        let mut solid = Brush::from(Color::WHITE);
+        check_fields(&mut solid);
+        solid.set("color", Value::Color(Color::BLACK)).unwrap();
+        assert_eq!(solid, Brush::from(Color::BLACK));
+
+        let mut image = Brush::Image(Image::new(Blob::from(vec![0; 4]), ImageFormat::Rgba8, 1, 1));
+        check_fields(&mut image);
+        image.set("quality", Value::Enum("high")).unwrap();
+        image.set("alpha", Value::F32(0.5)).unwrap();
+        let Brush::Image(image) = image else {
+            unreachable!()
+        };
+        assert_eq!(image.quality, ImageQuality::High);
+        assert_eq!(image.alpha, 0.5);
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn gradients() {
+        use crate::{Extend, Gradient, Point};
+
+        let mut gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0));
+        check_fields(&mut gradient);
+        gradient
+            .set("end", Value::Point(Point::new(2.0, 0.0)))
+            .unwrap();
+        gradient.set("extend", Value::Enum("reflect")).unwrap();
+        assert_eq!(
+            gradient.get("end"),
+            Some(Value::Point(Point::new(2.0, 0.0)))
+        );
+        assert_eq!(gradient.extend, Extend::Reflect);
+        assert_eq!(gradient.get("center"), None);
+
+        let mut sweep = Gradient::new_sweep((0.0, 0.0), 0.0, 1.0);
+        check_fields(&mut sweep);
+        sweep.set("end_angle", Value::F32(2.0)).unwrap();
+        assert_eq!(sweep.get("end_angle"), Some(Value::F32(2.0)));
+
+        let mut radial = Gradient::new_two_point_radial((0.0, 0.0), 1.0, (1.0, 1.0), 2.0);
+        check_fields(&mut radial);
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn styles() {
+        use crate::{Fill, Style};
+
+        let mut fill = Style::Fill(Fill::NonZero);
+        check_fields(&mut fill);
+        fill.set("fill", Value::Enum("even-odd")).unwrap();
+        assert!(matches!(fill, Style::Fill(Fill::EvenOdd)));
+
+        let mut stroke = Style::stroke(2.0);
+        check_fields(&mut stroke);
+        stroke.set("width", Value::F64(4.0)).unwrap();
+        stroke.set("join", Value::Enum("round")).unwrap();
+        assert_eq!(stroke.get("width"), Some(Value::F64(4.0)));
+        assert_eq!(stroke.get("join"), Some(Value::Enum("round")));
+        assert_eq!(
+            stroke.set("width", Value::F32(4.0)),
+            Err(InspectError::KindMismatch)
+        );
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! [Proptest] strategies and invariant checkers for gradients.
+//!
+//! Crates which process gradients, such as renderers building color ramps, can use
+//! these to run the same property tests as peniko against their own code:
+//!
+//! ```
+//! use peniko::invariants::{check_normalize_idempotent, color_stops};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // In a test suite, this would be marked with `#[test]`.
+//!     fn normalize_is_idempotent(stops in color_stops()) {
+//!         check_normalize_idempotent(&stops)?;
+//!     }
+//! }
+//! # normalize_is_idempotent();
+//! ```
+//!
+//! [Proptest]: https://crates.io/crates/proptest
+
+use color::cache_key::BitEq;
+use color::{ColorSpaceTag, DynamicColor, HueDirection};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use crate::gradient::is_polar;
+use crate::{ColorStop, ColorStops, Extend, Gradient, GradientKind, Point};
+
+/// Interpolation color spaces used by the [`gradient`] strategy.
+const INTERPOLATION_SPACES: [ColorSpaceTag; 6] = [
+    ColorSpaceTag::Srgb,
+    ColorSpaceTag::LinearSrgb,
+    ColorSpaceTag::DisplayP3,
+    ColorSpaceTag::Oklab,
+    ColorSpaceTag::Oklch,
+    ColorSpaceTag::Hsl,
+];
+
+/// Tolerance for comparisons of interpolated components.
+const EPSILON: f32 = 1e-4;
+
+/// Generates stop offsets, including offsets outside of `[0, 1]`, negative zero and
+/// NaN, with repeated values being likely.
+pub fn offset() -> impl Strategy<Value = f32> {
+    prop_oneof![
+        4 => 0.0_f32..=1.0,
+        2 => prop::sample::select(vec![0.0, -0.0, 0.25, 0.5, 1.0]),
+        1 => -2.0_f32..3.0,
+        1 => Just(f32::NAN),
+    ]
+}
+
+/// Generates non-premultiplied sRGB colors with components in `[0, 1]`.
+pub fn color() -> impl Strategy<Value = DynamicColor> {
+    prop::array::uniform4(0.0_f32..=1.0)
+        .prop_map(|components| DynamicColor::from_alpha_color(crate::Color::new(components)))
+}
+
+/// Generates color stops with [`offset`]s and [`color()`]s, some of which have an
+/// interpolation hint.
+pub fn color_stop() -> impl Strategy<Value = ColorStop> {
+    (
+        offset(),
+        color(),
+        prop::option::weighted(0.25, 0.0_f32..=1.0),
+    )
+        .prop_map(|(offset, color, hint)| ColorStop {
+            offset,
+            color,
+            hint,
+        })
+}
+
+/// Generates collections of up to eight stops, which may be unsorted.
+pub fn color_stops() -> impl Strategy<Value = ColorStops> {
+    prop::collection::vec(color_stop(), 0..=8).prop_map(ColorStops::from)
+}
+
+fn point() -> impl Strategy<Value = Point> {
+    (-100.0..100.0, -100.0..100.0).prop_map(|(x, y)| Point::new(x, y))
+}
+
+/// Generates gradient kinds with finite geometry.
+pub fn gradient_kind() -> impl Strategy<Value = GradientKind> {
+    prop_oneof![
+        (point(), point()).prop_map(|(start, end)| GradientKind::Linear { start, end }),
+        (point(), 0.0_f32..50.0, point(), 0.0_f32..50.0).prop_map(
+            |(start_center, start_radius, end_center, end_radius)| GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            }
+        ),
+        (point(), -7.0_f32..7.0, -7.0_f32..7.0).prop_map(|(center, start_angle, end_angle)| {
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            }
+        }),
+    ]
+}
+
+/// Generates gradients with [normalized](ColorStops::normalize) stops, in a variety of
+/// interpolation color spaces and hue directions.
+pub fn gradient() -> impl Strategy<Value = Gradient> {
+    (
+        gradient_kind(),
+        prop::sample::select(Extend::ALL.to_vec()),
+        prop::sample::select(INTERPOLATION_SPACES.to_vec()),
+        prop::sample::select(vec![
+            HueDirection::Shorter,
+            HueDirection::Longer,
+            HueDirection::Increasing,
+            HueDirection::Decreasing,
+        ]),
+        color_stops(),
+    )
+        .prop_map(
+            |(kind, extend, interpolation_cs, hue_direction, mut stops)| {
+                stops.normalize();
+                Gradient {
+                    kind,
+                    extend,
+                    interpolation_cs,
+                    hue_direction,
+                    stops,
+                    id: None,
+                }
+            },
+        )
+}
+
+/// Checks that [normalizing](ColorStops::normalize) the stops twice has the same
+/// result as normalizing them once.
+///
+/// # Errors
+///
+/// Returns a test failure if the invariant doesn't hold.
+pub fn check_normalize_idempotent(stops: &ColorStops) -> Result<(), TestCaseError> {
+    let mut once = stops.clone();
+    once.normalize();
+    let mut twice = once.clone();
+    twice.normalize();
+    prop_assert!(
+        once.len() == twice.len() && once.iter().zip(twice.iter()).all(|(a, b)| a.bit_eq(b)),
+        "normalizing again changed {once:?} to {twice:?}"
+    );
+    prop_assert!(once.is_sorted_by_offset(), "normalized stops aren't sorted");
+    Ok(())
+}
+
+/// Checks that [`Gradient::multiply_alpha`] is monotonic: multiplying by a smaller
+/// alpha never produces a more opaque stop, and no factor in `[0, 1]` makes a stop
+/// more opaque than it was.
+///
+/// # Errors
+///
+/// Returns a test failure if the invariant doesn't hold.
+pub fn check_multiply_alpha_monotonic(
+    gradient: &Gradient,
+    a: f32,
+    b: f32,
+) -> Result<(), TestCaseError> {
+    let (low, high) = (a.min(b).clamp(0.0, 1.0), a.max(b).clamp(0.0, 1.0));
+    let low = gradient.clone().multiply_alpha(low);
+    let high = gradient.clone().multiply_alpha(high);
+    for ((original, lower), higher) in gradient.stops.iter().zip(&*low.stops).zip(&*high.stops) {
+        let alpha = |stop: &ColorStop| stop.color.components[3];
+        prop_assert!(
+            alpha(lower) <= alpha(higher) && alpha(higher) <= alpha(original),
+            "alpha of {original:?} isn't monotonic in the multiplier"
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the color of the gradient at offset `t` lies within the hull of the
+/// colors of the stops around it.
+///
+/// The color is computed by interpolating between the stops of the segment
+/// containing `t`, as given by [`ColorStops::segments`], taking
+/// [hints](ColorStop::hint) into account. Its alpha must lie between the alphas of
+/// the stops. For rectangular interpolation color spaces, its premultiplied
+/// components in that space must also lie between those of the stops.
+///
+/// # Errors
+///
+/// Returns a test failure if the invariant doesn't hold.
+pub fn check_ramp_within_hull(gradient: &Gradient, t: f32) -> Result<(), TestCaseError> {
+    let t = t.clamp(0.0, 1.0);
+    let Some((start, end, _)) = gradient
+        .stops
+        .segments()
+        .find(|(_, _, range)| range.contains(&t) || range.end == t)
+    else {
+        return Ok(());
+    };
+    let cs = gradient.interpolation_cs;
+    let weight = start.interpolation_weight(&end, t);
+    let sample = start
+        .color
+        .interpolate(end.color, cs, gradient.hue_direction)
+        .eval(weight)
+        .convert(cs);
+    let premultiplied = |color: DynamicColor| {
+        let [c0, c1, c2, alpha] = color.convert(cs).components;
+        [c0 * alpha, c1 * alpha, c2 * alpha, alpha]
+    };
+    let (start, end, sample) = (
+        premultiplied(start.color),
+        premultiplied(end.color),
+        premultiplied(sample),
+    );
+    // Interpolated hues aren't bounded by the hues of the endpoints.
+    let checked = if is_polar(cs) { 3..4 } else { 0..4 };
+    for i in checked {
+        let (low, high) = (start[i].min(end[i]), start[i].max(end[i]));
+        prop_assert!(
+            sample[i] >= low - EPSILON && sample[i] <= high + EPSILON,
+            "component {i} of {sample:?} at {t} is outside of [{low}, {high}]"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_multiply_alpha_monotonic, check_normalize_idempotent, check_ramp_within_hull,
+        color_stops, gradient,
+    };
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn normalize_is_idempotent(stops in color_stops()) {
+            check_normalize_idempotent(&stops)?;
+        }
+
+        #[test]
+        fn multiply_alpha_is_monotonic(gradient in gradient(), a in 0.0_f32..=1.0, b in 0.0_f32..=1.0) {
+            check_multiply_alpha_monotonic(&gradient, a, b)?;
+        }
+
+        #[test]
+        fn ramp_is_within_hull(gradient in gradient(), t in 0.0_f32..=1.0) {
+            check_ramp_within_hull(&gradient, t)?;
+        }
+    }
+}
@@ -9,9 +9,50 @@
 //! The name "peniko" is Esperanto for "brush" which is one family of types that the library
 //! contains.
 //!
+//! ## Features
+//!
+//! - `std` (enabled by default): Get floating point functions from the standard library
+//!   (likely using your target's libc).
+//! - `libm`: Use floating point implementations from [libm][].
+//! - `gradient` (enabled by default): Enable gradient brushes. Without this feature,
+//!   [`Brush::Gradient`] can't be constructed, and the `smallvec` dependency is not used.
+//! - `image-codecs`: Enable decoding and encoding images in the PNG format, using the
+//!   [`png`] crate. This requires `std`.
+//! - `inspect`: Enable the `inspect` module, for listing and editing the fields of
+//!   brushes and styles at runtime, such as in editors.
+//! - `kurbo` (enabled by default): Use the [`kurbo`] geometry types, and enable
+//!   `Style` and the other APIs which depend on kurbo. Without this feature, brushes
+//!   use minimal stand-ins for the [`Point`] and [`Vec2`] types.
+//! - `bytemuck`: Implement traits from `bytemuck` on the fieldless enums.
+//! - `compat`: Enable the `compat` module, with deprecated shims for the `Color` API
+//!   of peniko 0.2, for migrating incrementally.
+//! - `defmt`: Implement `defmt::Format` on the small style types, for logging on embedded
+//!   targets.
+//! - `ffi`: Enable the `ffi` module, containing C-compatible mirrors of the vocabulary
+//!   types for bindings to other languages.
+//! - `label`: Enable the `Label` type for annotating layers and draws when debugging.
+//! - `mint`: Enable `mint` support in kurbo.
+//! - `proptest`: Enable the `invariants` module, containing [proptest][] strategies and
+//!   invariant checkers for gradients. This requires `std` and `gradient`.
+//! - `serde`: Implement `serde::Deserialize` and `serde::Serialize` on various types, and
+//!   enable the versioned formats in the `schema` module.
+//! - `serde_json`: Enable the `ValueConvert` trait, for converting styles to and from
+//!   `serde_json::Value`, such as for scripting and bindings.
+//! - `test-patterns`: Enable constructors for deterministic test images, such as
+//!   `Image::checkerboard`, for use in renderer test suites.
+//! - `testkit`: Enable the `testkit` module, containing canonical test vectors for the
+//!   semantics of gradients, blend modes and extend modes, for renderer test suites.
+//! - `wasm-bindgen`: Enable conversions to and from web canvas concepts, such as
+//!   CSS color strings, canvas gradients and `ImageData`. This requires `std`.
+//!
+//! At least one of `std` and `libm` is required; `std` overrides `libm`.
+//!
 //! [`kurbo`]: https://crates.io/crates/kurbo
 //! [`color`]: https://crates.io/crates/color
-
+//! [`png`]: https://crates.io/crates/png
+//! [proptest]: https://crates.io/crates/proptest
+#![cfg_attr(feature = "libm", doc = "[libm]: libm")]
+#![cfg_attr(not(feature = "libm"), doc = "[libm]: https://crates.io/crates/libm")]
 // LINEBENDER LINT SET - lib.rs - v1
 // See https://linebender.org/wiki/canonical-lints/
 // These lints aren't included in Cargo.toml because they
@@ -26,13 +67,100 @@
     reason = "Most of the enums are correctly exhaustive as this is a vocabulary crate."
 )]
 
+/// Implements `to_u8` and `from_u8` for a fieldless enum, with the given `u8`
+/// representation of each variant.
+///
+/// The representation must be the discriminant of each variant, which is checked
+/// at compile time so that the two can't drift apart.
+///
+/// This is defined before the modules so that they can all use it.
+macro_rules! impl_u8_conversions {
+    ($ty:ident { $($variant:ident = $value:literal),+ $(,)? }) => {
+        const _: () = {
+            $(assert!(
+                $ty::$variant as u8 == $value,
+                concat!("`", stringify!($ty), "::", stringify!($variant), "` has a different discriminant"),
+            );)+
+        };
+
+        impl $ty {
+            /// Returns the `u8` representation of the value.
+            ///
+            /// This is stable across releases, so it can be stored in scene buffers and
+            /// serialized data.
+            #[must_use]
+            pub const fn to_u8(self) -> u8 {
+                match self {
+                    $(Self::$variant => $value,)+
+                }
+            }
+
+            /// Returns the value with the given `u8` representation, or `None` if no
+            /// variant has that representation.
+            #[must_use]
+            pub const fn from_u8(value: u8) -> Option<Self> {
+                match value {
+                    $($value => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
 mod blend;
 mod blob;
 mod brush;
+#[cfg(feature = "image-codecs")]
+mod codecs;
+#[cfg(any(feature = "gradient", feature = "serde"))]
+mod color_space;
+#[cfg(feature = "gradient")]
+mod compact;
+#[cfg(feature = "compat")]
+pub mod compat;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod floatfuncs;
 mod font;
+mod geom;
+mod glyph;
+#[cfg(feature = "gradient")]
 mod gradient;
 mod image;
+#[cfg(feature = "inspect")]
+pub mod inspect;
+#[cfg(feature = "proptest")]
+pub mod invariants;
+#[cfg(feature = "label")]
+mod label;
+#[cfg(not(feature = "gradient"))]
+mod no_gradient;
+mod packed;
+#[cfg(feature = "gradient")]
+mod pdf;
+mod procedural;
+mod registry;
+#[cfg(feature = "serde")]
+pub mod schema;
+#[cfg(feature = "kurbo")]
+mod shape;
 mod style;
+#[cfg(feature = "gradient")]
+mod svg;
+mod template;
+#[cfg(feature = "test-patterns")]
+mod test_patterns;
+#[cfg(feature = "testkit")]
+pub mod testkit;
+mod theme;
+#[cfg(feature = "kurbo")]
+mod transform;
+mod units;
+#[cfg(feature = "serde_json")]
+mod value;
+#[cfg(feature = "wasm-bindgen")]
+mod web;
 
 #[cfg(feature = "bytemuck")]
 mod impl_bytemuck;
@@ -41,15 +169,69 @@ mod impl_bytemuck;
 pub use color;
 
 /// Re-export of the kurbo 2D curve library.
+#[cfg(feature = "kurbo")]
 pub use kurbo;
 
-pub use blend::{BlendMode, Compose, Mix};
+pub use blend::{
+    BackendBlendError, BlendBackend, BlendMode, Compose, Mix, SimpleBlend, UnsupportedMix,
+};
 pub use blob::{Blob, WeakBlob};
-pub use brush::{Brush, BrushRef, Extend};
-pub use font::Font;
-pub use gradient::{ColorStop, ColorStops, ColorStopsSource, Gradient, GradientKind};
-pub use image::{Image, ImageFormat, ImageQuality};
-pub use style::{Fill, Style, StyleRef};
+pub use brush::{Brush, BrushRef, EncodeBrush, Extend, OrdBrushKey};
+#[cfg(feature = "image-codecs")]
+pub use codecs::PngError;
+#[cfg(feature = "gradient")]
+pub use compact::{CompactGradient, CompactGradientError};
+pub use font::{Font, FontStack, FontStackEntry, UnicodeRange};
+pub use geom::{Point, Vec2};
+pub use glyph::{GlyphAntialiasing, GlyphRenderingOptions, Hinting, SubpixelPositioning};
+#[cfg(feature = "gradient")]
+pub use gradient::{
+    ColorStop, ColorStops, ColorStopsSource, EncodeGradient, Gradient, GradientKind,
+    SharedColorStops, StopOffsetsError,
+};
+#[cfg(all(feature = "gradient", feature = "kurbo"))]
+pub use gradient::{
+    LinearGradientCorner, LinearGradientPosition, RadialGradientPosition, RadialGradientShape,
+    RadialGradientSize,
+};
+pub use image::{
+    EncodeImage, Image, ImageDataRef, ImageFormat, ImageQuality, ImageSamplerKey, ImageTiling,
+    MinificationQuality, TexelCoords, TileMode, WeakImage,
+};
+#[cfg(feature = "label")]
+pub use label::Label;
+#[cfg(not(feature = "gradient"))]
+pub use no_gradient::{EncodeGradient, Gradient};
+pub use packed::{ChannelOrder, PackedColor};
+#[cfg(feature = "gradient")]
+pub use pdf::{PdfFunction, PdfShading, PdfShadingCoords, PdfShadingError};
+pub use procedural::{NoiseBrush, NoiseType, ProceduralBrush};
+pub use registry::{BrushHandle, BrushRegistry, BrushUpdate};
+#[cfg(feature = "kurbo")]
+pub use shape::{CornerRadii, Geometry, GeometryPathIter, SmoothRoundedRect};
+pub use style::{AntialiasingMode, Fill, RenderHints};
+#[cfg(feature = "kurbo")]
+pub use style::{DashPatternError, Style, StyleRef};
+#[cfg(feature = "gradient")]
+pub use svg::{SvgGradient, SvgGradientElement, SvgGradientError, SvgStop};
+#[cfg(feature = "gradient")]
+pub use template::TemplateStop;
+pub use template::{BrushTemplate, ColorSlot, Palette, ResolveError, TemplateColor};
+pub use theme::{Theme, Themed};
+#[cfg(feature = "kurbo")]
+pub use transform::AffineDecomposition;
+pub use units::{Dip, Px, ScaleFactor};
+#[cfg(feature = "serde_json")]
+pub use value::ValueConvert;
+#[cfg(feature = "wasm-bindgen")]
+pub use web::{CanvasGradientDescriptor, CanvasGradientKind, WebError};
 
 /// A convenient alias for the color type used for [`Brush`].
 pub type Color = color::AlphaColor<color::Srgb>;
+
+// Keep clippy from complaining about unused libm in nostd test case.
+#[cfg(feature = "libm")]
+#[expect(unused, reason = "keep clippy happy")]
+fn ensure_libm_dependency_used() -> f32 {
+    libm::sqrtf(4_f32)
+}
@@ -0,0 +1,73 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+/// Stand-in for the gradient type when the `gradient` feature is disabled.
+///
+/// This type can't be constructed, so neither can [`Brush::Gradient`](crate::Brush::Gradient).
+/// The variant is kept so that enabling the feature doesn't add a variant to
+/// [`Brush`](crate::Brush), which would break exhaustive matches in other crates.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Gradient {
+    _private: (),
+}
+
+impl Gradient {
+    /// Returns the gradient with the alpha component for all color stops set to `alpha`.
+    #[must_use]
+    pub fn with_alpha(self, _alpha: f32) -> Self {
+        self
+    }
+
+    /// Returns the gradient with the alpha component for all color stops
+    /// multiplied by `alpha`.
+    #[must_use]
+    pub fn multiply_alpha(self, _alpha: f32) -> Self {
+        self
+    }
+}
+
+/// Stand-in for the borrowed gradient type when the `gradient` feature is disabled.
+///
+/// Like [`Gradient`], this can't be constructed.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EncodeGradient<'a> {
+    _gradient: &'a Gradient,
+}
+
+impl EncodeGradient<'_> {
+    /// Converts the borrowed gradient to an owned gradient.
+    #[must_use]
+    pub fn to_gradient(&self) -> Gradient {
+        self._gradient.clone()
+    }
+}
+
+impl<'a> From<&'a Gradient> for EncodeGradient<'a> {
+    fn from(gradient: &'a Gradient) -> Self {
+        Self {
+            _gradient: gradient,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Gradient {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_unit()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Gradient {
+    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Err(serde::de::Error::custom(
+            "gradients require the `gradient` feature of peniko",
+        ))
+    }
+}
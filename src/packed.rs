@@ -0,0 +1,131 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use color::{PremulColor, PremulRgba8, Srgb};
+
+use crate::Color;
+
+/// The order of the channels of a packed 8-bit color.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub enum ChannelOrder {
+    /// Red, green, blue, then alpha.
+    #[default]
+    Rgba,
+    /// Blue, green, red, then alpha, as used by many windowing systems and
+    /// software rasterizers.
+    Bgra,
+}
+
+impl_u8_conversions!(ChannelOrder {
+    Rgba = 0,
+    Bgra = 1,
+});
+
+/// Conversions of [`Color`] to and from packed 8-bit colors with premultiplied
+/// alpha, as used by software rasterizers.
+///
+/// The packing delegates to [`PremulRgba8`]. Values are rounded to the nearest
+/// 8-bit value, so they round-trip exactly only for colors that were themselves
+/// made from 8-bit premultiplied values.
+///
+/// Integers are packed with an explicit byte order, so that the first channel in
+/// the [`ChannelOrder`] is the most significant byte for the big-endian variants,
+/// and the least significant byte for the little-endian variants. The little-endian
+/// variants have the channels in memory order on common platforms.
+pub trait PackedColor: Sized {
+    /// Returns the premultiplied color as bytes in the given order.
+    fn to_premul_bytes(self, order: ChannelOrder) -> [u8; 4];
+
+    /// Creates a color from premultiplied bytes in the given order.
+    fn from_premul_bytes(bytes: [u8; 4], order: ChannelOrder) -> Self;
+
+    /// Returns the premultiplied color packed as `0xRRGGBBAA`.
+    ///
+    /// This is the same as [`to_premul_be_u32`](Self::to_premul_be_u32) with
+    /// [`ChannelOrder::Rgba`], and matches `Color::to_premul_u32` of peniko 0.2.
+    fn to_premul_u32(self) -> u32 {
+        self.to_premul_be_u32(ChannelOrder::Rgba)
+    }
+
+    /// Creates a color from a premultiplied color packed as `0xRRGGBBAA`.
+    fn from_premul_u32(packed: u32) -> Self {
+        Self::from_premul_be_u32(packed, ChannelOrder::Rgba)
+    }
+
+    /// Returns the premultiplied color packed big-endian, so that `Bgra` is
+    /// `0xBBGGRRAA`.
+    fn to_premul_be_u32(self, order: ChannelOrder) -> u32 {
+        u32::from_be_bytes(self.to_premul_bytes(order))
+    }
+
+    /// Creates a color from a premultiplied color packed big-endian.
+    fn from_premul_be_u32(packed: u32, order: ChannelOrder) -> Self {
+        Self::from_premul_bytes(packed.to_be_bytes(), order)
+    }
+
+    /// Returns the premultiplied color packed little-endian, so that `Bgra` is
+    /// `0xAARRGGBB`.
+    fn to_premul_le_u32(self, order: ChannelOrder) -> u32 {
+        u32::from_le_bytes(self.to_premul_bytes(order))
+    }
+
+    /// Creates a color from a premultiplied color packed little-endian.
+    fn from_premul_le_u32(packed: u32, order: ChannelOrder) -> Self {
+        Self::from_premul_bytes(packed.to_le_bytes(), order)
+    }
+}
+
+impl PackedColor for Color {
+    fn to_premul_bytes(self, order: ChannelOrder) -> [u8; 4] {
+        let [r, g, b, a] = self.premultiply().to_rgba8().to_u8_array();
+        match order {
+            ChannelOrder::Rgba => [r, g, b, a],
+            ChannelOrder::Bgra => [b, g, r, a],
+        }
+    }
+
+    fn from_premul_bytes(bytes: [u8; 4], order: ChannelOrder) -> Self {
+        let [c0, g, c2, a] = bytes;
+        let rgba = match order {
+            ChannelOrder::Rgba => [c0, g, c2, a],
+            ChannelOrder::Bgra => [c2, g, c0, a],
+        };
+        PremulColor::<Srgb>::from(PremulRgba8::from_u8_array(rgba)).un_premultiply()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelOrder, PackedColor};
+    use crate::Color;
+
+    #[test]
+    fn packed_premul() {
+        let color = Color::from_rgba8(255, 128, 0, 128);
+        assert_eq!(color.to_premul_u32(), 0x8040_0080);
+        assert_eq!(color.to_premul_bytes(ChannelOrder::Bgra), [0, 64, 128, 128]);
+        assert_eq!(color.to_premul_be_u32(ChannelOrder::Bgra), 0x0040_8080);
+        assert_eq!(color.to_premul_le_u32(ChannelOrder::Bgra), 0x8080_4000);
+        assert_eq!(color.to_premul_le_u32(ChannelOrder::Rgba), 0x8000_4080);
+
+        let packed = 0x4020_1080;
+        let unpacked = Color::from_premul_u32(packed);
+        assert_eq!(unpacked.to_premul_u32(), packed);
+        for order in [ChannelOrder::Rgba, ChannelOrder::Bgra] {
+            let le = unpacked.to_premul_le_u32(order);
+            let be = unpacked.to_premul_be_u32(order);
+            assert_eq!(le, be.swap_bytes());
+            assert_eq!(Color::from_premul_le_u32(le, order).to_premul_u32(), packed);
+            assert_eq!(Color::from_premul_be_u32(be, order).to_premul_u32(), packed);
+        }
+        assert_eq!(Color::from_premul_u32(0), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn channel_order_u8() {
+        for order in [ChannelOrder::Rgba, ChannelOrder::Bgra] {
+            assert_eq!(ChannelOrder::from_u8(order.to_u8()), Some(order));
+        }
+        assert_eq!(ChannelOrder::from_u8(2), None);
+    }
+}
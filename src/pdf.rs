@@ -0,0 +1,340 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Decomposition of gradients into the parameters of PDF shadings.
+
+use core::fmt;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use color::ColorSpaceTag;
+
+use crate::{ColorStop, Extend, Gradient, GradientKind};
+
+#[cfg(not(feature = "std"))]
+use crate::floatfuncs::FloatFuncs as _;
+
+/// Errors which can occur when converting a [`Gradient`] to a [`PdfShading`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PdfShadingError {
+    /// The gradient is a sweep gradient, which has no equivalent PDF shading type.
+    UnsupportedKind,
+    /// The extend mode can't be expressed by the `Extend` entry of a shading, which
+    /// only supports [`Extend::Pad`] and [`Extend::Border`].
+    UnsupportedExtend(Extend),
+    /// The gradient has no color stops.
+    NoStops,
+}
+
+impl fmt::Display for PdfShadingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedKind => f.write_str("sweep gradients have no PDF shading type"),
+            Self::UnsupportedExtend(extend) => {
+                write!(
+                    f,
+                    "extend mode {extend:?} can't be expressed in a PDF shading"
+                )
+            }
+            Self::NoStops => f.write_str("gradient has no color stops"),
+        }
+    }
+}
+
+impl core::error::Error for PdfShadingError {}
+
+/// The type and `Coords` entry of a PDF shading dictionary.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PdfShadingCoords {
+    /// An axial shading (type 2), with coordinates `[x0 y0 x1 y1]`.
+    Axial([f64; 4]),
+    /// A radial shading (type 3), with coordinates `[x0 y0 r0 x1 y1 r1]`.
+    Radial([f64; 6]),
+}
+
+impl PdfShadingCoords {
+    /// Returns the `ShadingType` of the shading.
+    #[must_use]
+    pub const fn shading_type(&self) -> i32 {
+        match self {
+            Self::Axial(_) => 2,
+            Self::Radial(_) => 3,
+        }
+    }
+}
+
+/// An exponential interpolation function (type 2) between two color stops.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PdfFunction {
+    /// The `C0` entry, which is the color at the start of the function.
+    pub c0: [f32; 3],
+    /// The `C1` entry, which is the color at the end of the function.
+    pub c1: [f32; 3],
+    /// The `N` entry, which is the interpolation exponent.
+    ///
+    /// This is `1` for linear interpolation, and follows from the
+    /// [interpolation hint](ColorStop::hint) of the first stop otherwise.
+    pub exponent: f32,
+    /// The alpha at the start and end of the function, for building a soft mask.
+    pub alpha: [f32; 2],
+}
+
+/// The parameters of a PDF shading dictionary for a [`Gradient`].
+///
+/// This is structured data for PDF writers, rather than PDF syntax. The color of
+/// the shading is a stitching function (type 3) with a `Domain` and each `Encode`
+/// pair of `[0 1]`, the given [`bounds`](Self::bounds), and the given
+/// [`functions`](Self::functions). With a single function, it can be used
+/// directly instead.
+///
+/// PDF shadings are opaque, so when the gradient isn't, writers should also
+/// build a soft mask from a shading with the same geometry and the
+/// [alphas](PdfFunction::alpha) of the functions. PDF then interpolates the colors
+/// and the alpha of the soft mask separately, which is interpolation with
+/// unpremultiplied alpha, while peniko interpolates with premultiplied alpha. So
+/// between stops with different alphas, the colors differ slightly from those of
+/// other renderers.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PdfShading {
+    /// The shading type and coordinates.
+    pub coords: PdfShadingCoords,
+    /// The `Extend` entry, which is whether the shading extends beyond the start and
+    /// the end of the gradient.
+    pub extend: [bool; 2],
+    /// The `Bounds` entry of the stitching function.
+    pub bounds: Vec<f32>,
+    /// The `Functions` entry of the stitching function, one for each pair of
+    /// consecutive stops.
+    ///
+    /// When the first stop is after `0` or the last stop is before `1`, there is also a
+    /// constant function before or after them, so that the color stays flat there.
+    pub functions: Vec<PdfFunction>,
+}
+
+impl PdfShading {
+    /// Returns `true` if any of the functions has an alpha other than one, in
+    /// which case a soft mask is needed.
+    #[must_use]
+    pub fn has_alpha(&self) -> bool {
+        self.functions
+            .iter()
+            .any(|function| function.alpha != [1.0, 1.0])
+    }
+}
+
+impl Gradient {
+    /// Decomposes the gradient into the parameters of a PDF shading, with colors
+    /// in the color space `cs`.
+    ///
+    /// PDF interpolates colors in the color space of the shading, so `cs` should
+    /// usually be the [interpolation color space](Self::interpolation_cs) of the
+    /// gradient, or one close to it such as sRGB for `DeviceRGB`. The
+    /// [hue direction](Self::hue_direction) isn't represented.
+    ///
+    /// The geometry of linear and two-point radial gradients maps directly to the
+    /// coordinates of axial and radial shadings, which follow the same conventions.
+    /// [`Extend::Pad`] extends both ends, and [`Extend::Border`] neither. The stops
+    /// are [normalized](crate::ColorStops::normalize) first, and padded with constant
+    /// functions when they don't span the whole gradient.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for sweep gradients, gradients with a [`Extend::Repeat`] or
+    /// [`Extend::Reflect`] extend mode, and gradients without stops. See
+    /// [`PdfShadingError`].
+    pub fn to_pdf_shading(&self, cs: ColorSpaceTag) -> Result<PdfShading, PdfShadingError> {
+        let coords = match self.kind {
+            GradientKind::Linear { start, end } => {
+                PdfShadingCoords::Axial([start.x, start.y, end.x, end.y])
+            }
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => PdfShadingCoords::Radial([
+                start_center.x,
+                start_center.y,
+                start_radius.into(),
+                end_center.x,
+                end_center.y,
+                end_radius.into(),
+            ]),
+            GradientKind::Sweep { .. } => return Err(PdfShadingError::UnsupportedKind),
+        };
+        let extend = match self.extend {
+            Extend::Pad => [true, true],
+            Extend::Border => [false, false],
+            extend => return Err(PdfShadingError::UnsupportedExtend(extend)),
+        };
+        let mut stops = self.stops.clone();
+        stops.normalize();
+        let color = |stop: &ColorStop| {
+            let [r, g, b, a] = stop.color.convert(cs).components;
+            ([r, g, b], a)
+        };
+        let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+            return Err(PdfShadingError::NoStops);
+        };
+        let constant = |stop: &ColorStop| {
+            let (c, a) = color(stop);
+            PdfFunction {
+                c0: c,
+                c1: c,
+                exponent: 1.0,
+                alpha: [a, a],
+            }
+        };
+        let mut functions = Vec::new();
+        let mut bounds = Vec::new();
+        // Each function of the stitching function spans the whole interval between
+        // its bounds, so stops which don't start at 0 or end at 1 are padded with
+        // constant functions to keep the colors flat outside of them.
+        if stops.len() == 1 {
+            functions.push(constant(first));
+        } else if first.offset > 0.0 {
+            functions.push(constant(first));
+            bounds.push(first.offset);
+        }
+        for (index, pair) in stops.windows(2).enumerate() {
+            if index > 0 {
+                bounds.push(pair[0].offset);
+            }
+            let (mut c0, mut a0) = color(&pair[0]);
+            let (mut c1, mut a1) = color(&pair[1]);
+            let mut exponent = 1.0;
+            let span = pair[1].offset - pair[0].offset;
+            if let Some(hint) = pair[0].hint.filter(|_| span > 0.0) {
+                let hint = (hint - pair[0].offset) / span;
+                if hint <= 0.0 {
+                    (c0, a0) = (c1, a1);
+                } else if hint >= 1.0 {
+                    (c1, a1) = (c0, a0);
+                } else {
+                    exponent = 0.5_f32.ln() / hint.ln();
+                }
+            }
+            functions.push(PdfFunction {
+                c0,
+                c1,
+                exponent,
+                alpha: [a0, a1],
+            });
+        }
+        if stops.len() > 1 && last.offset < 1.0 {
+            bounds.push(last.offset);
+            functions.push(constant(last));
+        }
+        Ok(PdfShading {
+            coords,
+            extend,
+            bounds,
+            functions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PdfShadingCoords, PdfShadingError};
+    use crate::{ColorStop, Extend, Gradient};
+    use color::{palette::css, ColorSpaceTag};
+
+    #[test]
+    fn axial_shading() {
+        let gradient = Gradient::new_linear((0.0, 0.0), (10.0, 0.0)).with_stops([
+            ColorStop::from((0.0, css::RED)).with_hint(0.25),
+            (0.5, css::LIME).into(),
+            (1.0, css::BLUE.with_alpha(0.5)).into(),
+        ]);
+        let shading = gradient.to_pdf_shading(ColorSpaceTag::Srgb).unwrap();
+        assert_eq!(
+            shading.coords,
+            PdfShadingCoords::Axial([0.0, 0.0, 10.0, 0.0])
+        );
+        assert_eq!(shading.coords.shading_type(), 2);
+        assert_eq!(shading.extend, [true, true]);
+        assert_eq!(shading.bounds, [0.5]);
+        assert_eq!(shading.functions.len(), 2);
+        let [first, second] = [shading.functions[0], shading.functions[1]];
+        assert_eq!(first.c0, [1.0, 0.0, 0.0]);
+        assert_eq!(first.c1, [0.0, 1.0, 0.0]);
+        // The hint is halfway to the middle stop, so the weight is 0.5 at 0.5.
+        assert!((0.5_f32.powf(first.exponent) - 0.5).abs() < 1e-6);
+        assert_eq!(second.exponent, 1.0);
+        assert_eq!(second.alpha, [1.0, 0.5]);
+        assert!(shading.has_alpha());
+    }
+
+    #[test]
+    fn radial_shading() {
+        let gradient = Gradient::new_two_point_radial((1.0, 2.0), 0.5, (3.0, 4.0), 5.0)
+            .with_extend(Extend::Border)
+            .with_stops([css::WHITE]);
+        let shading = gradient.to_pdf_shading(ColorSpaceTag::Srgb).unwrap();
+        assert_eq!(
+            shading.coords,
+            PdfShadingCoords::Radial([1.0, 2.0, 0.5, 3.0, 4.0, 5.0])
+        );
+        assert_eq!(shading.extend, [false, false]);
+        assert!(shading.bounds.is_empty());
+        assert_eq!(shading.functions[0].c0, shading.functions[0].c1);
+        assert!(!shading.has_alpha());
+    }
+
+    #[test]
+    fn unsupported_shadings() {
+        let stops = [css::RED, css::BLUE];
+        let sweep = Gradient::new_sweep((0.0, 0.0), 0.0, 1.0).with_stops(stops);
+        assert_eq!(
+            sweep.to_pdf_shading(ColorSpaceTag::Srgb),
+            Err(PdfShadingError::UnsupportedKind)
+        );
+        let repeat = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_extend(Extend::Repeat)
+            .with_stops(stops);
+        assert_eq!(
+            repeat.to_pdf_shading(ColorSpaceTag::Srgb),
+            Err(PdfShadingError::UnsupportedExtend(Extend::Repeat))
+        );
+        assert_eq!(
+            Gradient::new_linear((0.0, 0.0), (1.0, 0.0)).to_pdf_shading(ColorSpaceTag::Srgb),
+            Err(PdfShadingError::NoStops)
+        );
+    }
+
+    #[test]
+    fn inset_stops() {
+        let gradient = Gradient::new_linear((0.0, 0.0), (10.0, 0.0))
+            .with_stops_and_offsets(&[css::RED, css::LIME, css::BLUE], &[0.2, 0.5, 0.8])
+            .unwrap();
+        let shading = gradient.to_pdf_shading(ColorSpaceTag::Srgb).unwrap();
+        assert_eq!(shading.bounds, [0.2, 0.5, 0.8]);
+        let functions = &shading.functions;
+        assert_eq!(functions.len(), 4);
+        // The color is flat before the first stop and after the last.
+        assert_eq!(functions[0].c0, [1.0, 0.0, 0.0]);
+        assert_eq!(functions[0].c1, functions[0].c0);
+        assert_eq!(functions[1].c0, [1.0, 0.0, 0.0]);
+        assert_eq!(functions[1].c1, [0.0, 1.0, 0.0]);
+        assert_eq!(functions[2].c1, [0.0, 0.0, 1.0]);
+        assert_eq!(functions[3].c0, [0.0, 0.0, 1.0]);
+        assert_eq!(functions[3].c1, functions[3].c0);
+
+        let two = Gradient::new_linear((0.0, 0.0), (10.0, 0.0))
+            .with_stops_and_offsets(&[css::RED, css::BLUE], &[0.2, 0.8])
+            .unwrap();
+        let two_shading = two.to_pdf_shading(ColorSpaceTag::Srgb).unwrap();
+        assert_eq!(two_shading.bounds, [0.2, 0.8]);
+        assert_eq!(two_shading.functions.len(), 3);
+
+        let single = Gradient::new_linear((0.0, 0.0), (10.0, 0.0))
+            .with_stops_and_offsets(&[css::RED], &[0.5])
+            .unwrap();
+        let single_shading = single.to_pdf_shading(ColorSpaceTag::Srgb).unwrap();
+        assert!(single_shading.bounds.is_empty());
+        assert_eq!(single_shading.functions.len(), 1);
+    }
+}
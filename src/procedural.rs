@@ -0,0 +1,157 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::Vec2;
+
+/// Type of noise generated by a [noise brush](NoiseBrush).
+///
+/// This corresponds to the `type` attribute of the SVG [`feTurbulence`] filter primitive.
+///
+/// [`feTurbulence`]: https://drafts.fxtf.org/filter-effects/#feTurbulenceElement
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NoiseType {
+    /// Sums of signed noise, which produces smooth, cloud-like patterns.
+    FractalNoise,
+    /// Sums of the absolute value of noise, which produces sharper, marbled patterns.
+    #[default]
+    Turbulence,
+}
+
+impl_u8_conversions!(NoiseType {
+    FractalNoise = 0,
+    Turbulence = 1,
+});
+
+/// Brush which paints Perlin noise, as generated by the SVG [`feTurbulence`] filter
+/// primitive.
+///
+/// The noise is evaluated in the brush coordinate space, so the brush transform
+/// controls its scale and orientation in addition to the
+/// [base frequency](Self::base_frequency).
+///
+/// [`feTurbulence`]: https://drafts.fxtf.org/filter-effects/#feTurbulenceElement
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoiseBrush {
+    /// Type of noise.
+    pub noise_type: NoiseType,
+    /// Base frequency of the noise in the horizontal and vertical directions.
+    pub base_frequency: Vec2,
+    /// Number of octaves of noise which are summed.
+    pub octaves: u32,
+    /// Seed for the pseudo-random number generator.
+    pub seed: i32,
+    /// Whether the frequencies are adjusted so that the noise tiles seamlessly over
+    /// the painted area.
+    pub stitch_tiles: bool,
+    /// An additional alpha multiplier to use with the noise.
+    pub alpha: f32,
+}
+
+impl NoiseBrush {
+    /// Creates a new noise brush with the given type, base frequency and number of
+    /// octaves, and a seed of zero.
+    #[must_use]
+    pub fn new(noise_type: NoiseType, base_frequency: impl Into<Vec2>, octaves: u32) -> Self {
+        Self {
+            noise_type,
+            base_frequency: base_frequency.into(),
+            octaves,
+            seed: 0,
+            stitch_tiles: false,
+            alpha: 1.0,
+        }
+    }
+
+    /// Builder method for setting the seed.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: i32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builder method for setting whether the noise tiles seamlessly.
+    #[must_use]
+    pub const fn with_stitch_tiles(mut self, stitch_tiles: bool) -> Self {
+        self.stitch_tiles = stitch_tiles;
+        self
+    }
+
+    /// Returns the brush with the alpha multiplier set to `alpha`.
+    #[must_use]
+    #[track_caller]
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        debug_assert!(
+            alpha.is_finite() && alpha >= 0.0,
+            "A non-finite or negative alpha ({alpha}) is meaningless."
+        );
+        self.alpha = alpha;
+        self
+    }
+
+    /// Returns the brush with the alpha multiplier multiplied again by `alpha`.
+    /// The behaviour of this transformation is undefined if `alpha` is negative.
+    #[must_use]
+    #[track_caller]
+    pub fn multiply_alpha(mut self, alpha: f32) -> Self {
+        debug_assert!(
+            alpha.is_finite() && alpha >= 0.0,
+            "A non-finite or negative alpha ({alpha}) is meaningless."
+        );
+        self.alpha *= alpha;
+        self
+    }
+}
+
+/// Brush whose colors are generated by a procedure, rather than stored.
+///
+/// This allows paints such as SVG noise to be passed to renderers which can generate
+/// them directly, rather than rasterizing them into images up front. Renderers which
+/// don't support a kind of procedural brush can fall back to rasterizing it, or to a
+/// solid color.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ProceduralBrush {
+    /// Perlin noise.
+    Noise(NoiseBrush),
+}
+
+impl ProceduralBrush {
+    /// Returns a stable identifier for the kind of procedure.
+    ///
+    /// Tags are never reused, so they are suitable for serialization and for
+    /// selecting shaders. Noise is `1`.
+    #[must_use]
+    pub const fn kind_tag(&self) -> u32 {
+        match self {
+            Self::Noise(_) => 1,
+        }
+    }
+
+    /// Returns the brush with the alpha multiplier set to `alpha`.
+    #[must_use]
+    #[track_caller]
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        match self {
+            Self::Noise(noise) => Self::Noise(noise.with_alpha(alpha)),
+        }
+    }
+
+    /// Returns the brush with the alpha multiplier multiplied again by `alpha`.
+    /// The behaviour of this transformation is undefined if `alpha` is negative.
+    #[must_use]
+    #[track_caller]
+    pub fn multiply_alpha(self, alpha: f32) -> Self {
+        match self {
+            Self::Noise(noise) => Self::Noise(noise.multiply_alpha(alpha)),
+        }
+    }
+}
+
+impl From<NoiseBrush> for ProceduralBrush {
+    fn from(noise: NoiseBrush) -> Self {
+        Self::Noise(noise)
+    }
+}
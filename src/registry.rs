@@ -0,0 +1,248 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+extern crate alloc;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use super::Brush;
+
+/// Opaque handle referring to a brush in a [`BrushRegistry`].
+///
+/// Handles are never reused by a registry, so a handle that has been
+/// [removed](BrushRegistry::remove) will never refer to a different brush.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct BrushHandle(u64);
+
+impl BrushHandle {
+    /// Creates a handle from its raw value.
+    ///
+    /// This is intended for decoding handles received from a [`BrushRegistry`]
+    /// in another process.
+    #[must_use]
+    pub const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the raw value of the handle.
+    #[must_use]
+    pub const fn to_raw(self) -> u64 {
+        self.0
+    }
+}
+
+/// A change to the contents of a [`BrushRegistry`].
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "gradient",
+    expect(
+        clippy::large_enum_variant,
+        reason = "Updates are short-lived, and invalidations are rare compared to definitions."
+    )
+)]
+pub enum BrushUpdate {
+    /// The brush for a handle was defined or replaced.
+    Define(BrushHandle, Brush),
+    /// The handle was removed and will no longer be referenced.
+    Invalidate(BrushHandle),
+}
+
+/// Registry which assigns [handles](BrushHandle) to brushes.
+///
+/// This supports protocols where brushes are sent to a remote renderer once and
+/// then referenced by handle in subsequent frames:
+///
+/// - The sending side [inserts](Self::insert) brushes into a registry and refers
+///   to them by handle.
+/// - Before each frame, the sending side [takes the updates](Self::take_updates) since
+///   the previous frame and transmits them. Only brushes which were inserted or
+///   replaced since then are included.
+/// - The receiving side [applies](Self::apply) the updates to its own registry, and
+///   resolves handles with [`get`](Self::get).
+#[derive(Clone, Debug, Default)]
+pub struct BrushRegistry {
+    brushes: BTreeMap<BrushHandle, Brush>,
+    /// The largest handle which has been assigned or applied.
+    last_assigned: Option<u64>,
+    /// Handles above this value have not been reported by `take_updates`.
+    last_sent: Option<u64>,
+    defined: BTreeSet<BrushHandle>,
+    invalidated: Vec<BrushHandle>,
+}
+
+impl BrushRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of brushes in the registry.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.brushes.len()
+    }
+
+    /// Returns `true` if the registry contains no brushes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.brushes.is_empty()
+    }
+
+    /// Adds a brush to the registry and returns a new handle for it.
+    ///
+    /// Returns `None` and leaves the registry unchanged if every handle has been
+    /// assigned, which can only happen after [applying](Self::apply) a definition
+    /// for the largest raw handle.
+    pub fn insert(&mut self, brush: impl Into<Brush>) -> Option<BrushHandle> {
+        let raw = match self.last_assigned {
+            Some(last) => last.checked_add(1)?,
+            None => 0,
+        };
+        self.last_assigned = Some(raw);
+        let handle = BrushHandle(raw);
+        self.brushes.insert(handle, brush.into());
+        self.defined.insert(handle);
+        Some(handle)
+    }
+
+    /// Returns the brush for the given handle.
+    #[must_use]
+    pub fn get(&self, handle: BrushHandle) -> Option<&Brush> {
+        self.brushes.get(&handle)
+    }
+
+    /// Replaces the brush for an existing handle, returning the previous brush.
+    ///
+    /// Returns `None` and leaves the registry unchanged if the handle is not
+    /// in the registry.
+    pub fn replace(&mut self, handle: BrushHandle, brush: impl Into<Brush>) -> Option<Brush> {
+        let slot = self.brushes.get_mut(&handle)?;
+        self.defined.insert(handle);
+        Some(core::mem::replace(slot, brush.into()))
+    }
+
+    /// Removes a brush from the registry, invalidating its handle.
+    pub fn remove(&mut self, handle: BrushHandle) -> Option<Brush> {
+        let brush = self.brushes.remove(&handle)?;
+        self.defined.remove(&handle);
+        // If the brush was never sent, there is no need to send anything.
+        if self.last_sent.is_some_and(|last| handle.0 <= last) {
+            self.invalidated.push(handle);
+        }
+        Some(brush)
+    }
+
+    /// Removes all brushes from the registry, invalidating their handles.
+    pub fn clear(&mut self) {
+        let handles: Vec<_> = self.brushes.keys().copied().collect();
+        for handle in handles {
+            self.remove(handle);
+        }
+    }
+
+    /// Returns an iterator over the handles and brushes in the registry, ordered
+    /// by handle.
+    pub fn iter(&self) -> impl Iterator<Item = (BrushHandle, &Brush)> + '_ {
+        self.brushes.iter().map(|(handle, brush)| (*handle, brush))
+    }
+
+    /// Returns the changes made to the registry since the last call to this method.
+    ///
+    /// Invalidations are listed before definitions.
+    pub fn take_updates(&mut self) -> Vec<BrushUpdate> {
+        self.last_sent = self.last_assigned;
+        let mut updates: Vec<_> = self
+            .invalidated
+            .drain(..)
+            .map(BrushUpdate::Invalidate)
+            .collect();
+        updates.extend(
+            core::mem::take(&mut self.defined)
+                .into_iter()
+                .filter_map(|handle| Some(BrushUpdate::Define(handle, self.get(handle)?.clone()))),
+        );
+        updates
+    }
+
+    /// Applies an update produced by [`take_updates`](Self::take_updates) on
+    /// another registry.
+    ///
+    /// Applied updates are not themselves reported by `take_updates`.
+    pub fn apply(&mut self, update: BrushUpdate) {
+        match update {
+            BrushUpdate::Define(handle, brush) => {
+                self.last_assigned = self.last_assigned.max(Some(handle.0));
+                self.brushes.insert(handle, brush);
+            }
+            BrushUpdate::Invalidate(handle) => {
+                self.brushes.remove(&handle);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "gradient"))]
+mod tests {
+    use super::{BrushHandle, BrushRegistry, BrushUpdate};
+    use crate::{Brush, Gradient};
+    use color::palette;
+
+    #[test]
+    fn mirror_registry() {
+        let mut local = BrushRegistry::new();
+        let mut remote = BrushRegistry::new();
+
+        let red = local.insert(palette::css::RED).unwrap();
+        let gradient = local
+            .insert(
+                Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+                    .with_stops([palette::css::RED, palette::css::BLUE]),
+            )
+            .unwrap();
+        let initial = local.take_updates();
+        assert_eq!(initial.len(), 2);
+        initial.into_iter().for_each(|update| remote.apply(update));
+        assert_eq!(remote.get(red), local.get(red));
+        assert_eq!(remote.get(gradient), local.get(gradient));
+
+        // Nothing changed, so nothing needs to be sent.
+        assert!(local.take_updates().is_empty());
+
+        local.replace(red, palette::css::LIME);
+        local.remove(gradient);
+        let changes = local.take_updates();
+        assert_eq!(
+            changes,
+            [
+                BrushUpdate::Invalidate(gradient),
+                BrushUpdate::Define(red, Brush::Solid(palette::css::LIME)),
+            ]
+        );
+        changes.into_iter().for_each(|update| remote.apply(update));
+        assert_eq!(remote.get(red), local.get(red));
+        assert!(remote.get(gradient).is_none());
+
+        // A brush which is removed before it is sent doesn't produce any updates.
+        let blue = local.insert(palette::css::BLUE).unwrap();
+        local.remove(blue);
+        assert!(local.take_updates().is_empty());
+    }
+
+    #[test]
+    fn exhausted_handles() {
+        let mut registry = BrushRegistry::new();
+        let first = registry.insert(palette::css::RED).unwrap();
+        let last = BrushHandle::from_raw(u64::MAX);
+        registry.apply(BrushUpdate::Define(last, Brush::Solid(palette::css::BLUE)));
+        assert_eq!(registry.get(last), Some(&Brush::Solid(palette::css::BLUE)));
+
+        // Assigning a handle would collide with one which is in use.
+        assert_eq!(registry.insert(palette::css::LIME), None);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get(first), Some(&Brush::Solid(palette::css::RED)));
+    }
+}
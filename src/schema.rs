@@ -0,0 +1,1097 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Versioned serialization formats for the vocabulary types.
+//!
+//! The serde implementations on the types themselves follow their in-memory
+//! representation, and so can change whenever those types do. The types in this
+//! module are plain data transfer objects whose serialized form is fixed for each
+//! version, so documents written with one version of peniko can be read by later
+//! versions.
+//!
+//! Each version lives in its own module, such as [`v1`], and converts to and from
+//! the current types. Documents should be wrapped in a [`Versioned`] envelope,
+//! which records the version of the format they were written with, so that a
+//! reader knows which module to read them with. When a new version is introduced,
+//! a `migrate_v1_to_v2` style function will be added alongside it.
+
+use core::fmt;
+
+extern crate alloc;
+use alloc::string::String;
+
+/// The latest version of the schema.
+pub const LATEST_VERSION: u32 = v1::VERSION;
+
+/// Errors which can occur when converting between a schema type and the current types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaError {
+    /// A name, such as that of a blend mode, wasn't recognized.
+    UnknownName(String),
+    /// A value can't be represented in this version of the schema, such as an extend
+    /// mode which was added after it was frozen.
+    Unrepresentable(&'static str),
+    /// A [versioned](Versioned) document was written with a different version of the
+    /// schema than the one it was read with.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownName(name) => write!(f, "unknown name `{name}`"),
+            Self::Unrepresentable(name) => {
+                write!(f, "`{name}` can't be represented in this schema version")
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "schema version {version} isn't supported")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SchemaError {}
+
+/// A document tagged with the version of the schema it was written with.
+///
+/// Use [`v1::versioned`] to wrap a value when writing, and
+/// [`v1::unversioned`] to check the version when reading. To find the version of a
+/// document before choosing how to read it, deserialize it as
+/// `Versioned<serde::de::IgnoredAny>`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Versioned<T> {
+    /// The [version](v1::VERSION) of the schema.
+    pub version: u32,
+    /// The document.
+    pub value: T,
+}
+
+/// Version 1 of the schema.
+pub mod v1 {
+    use super::{SchemaError, Versioned};
+    use crate::color_space::{css_color_space_from_name, css_color_space_name};
+    use crate::{Compose, Mix};
+
+    extern crate alloc;
+    use alloc::borrow::ToOwned;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    /// The version number of this schema.
+    pub const VERSION: u32 = 1;
+
+    /// Wraps a value in an envelope tagged with this version.
+    pub fn versioned<T>(value: T) -> Versioned<T> {
+        Versioned {
+            version: VERSION,
+            value,
+        }
+    }
+
+    /// Unwraps a value from an envelope, checking that it was written with this
+    /// version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SchemaError::UnsupportedVersion`] if the document was written with a
+    /// different version.
+    pub fn unversioned<T>(document: Versioned<T>) -> Result<T, SchemaError> {
+        if document.version == VERSION {
+            Ok(document.value)
+        } else {
+            Err(SchemaError::UnsupportedVersion(document.version))
+        }
+    }
+
+    /// Serialized form of a [blend mode](crate::BlendMode).
+    ///
+    /// The functions are stored by their [`Mix::name`] and [`Compose::name`].
+    #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct BlendMode {
+        /// Name of the color mixing function.
+        pub mix: String,
+        /// Name of the layer composition function.
+        pub compose: String,
+    }
+
+    impl From<crate::BlendMode> for BlendMode {
+        fn from(mode: crate::BlendMode) -> Self {
+            Self {
+                mix: mode.mix.name().to_owned(),
+                compose: mode.compose.name().to_owned(),
+            }
+        }
+    }
+
+    impl TryFrom<BlendMode> for crate::BlendMode {
+        type Error = SchemaError;
+
+        fn try_from(mode: BlendMode) -> Result<Self, SchemaError> {
+            let mix = Mix::ALL
+                .into_iter()
+                .find(|mix| mix.name() == mode.mix)
+                .ok_or(SchemaError::UnknownName(mode.mix))?;
+            let compose = Compose::ALL
+                .into_iter()
+                .find(|compose| compose.name() == mode.compose)
+                .ok_or(SchemaError::UnknownName(mode.compose))?;
+            Ok(Self::new(mix, compose))
+        }
+    }
+
+    /// Serialized form of a [fill rule](crate::Fill).
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum Fill {
+        /// Non-zero fill rule.
+        NonZero,
+        /// Even-odd fill rule.
+        EvenOdd,
+    }
+
+    impl From<crate::Fill> for Fill {
+        fn from(fill: crate::Fill) -> Self {
+            match fill {
+                crate::Fill::NonZero => Self::NonZero,
+                crate::Fill::EvenOdd => Self::EvenOdd,
+            }
+        }
+    }
+
+    impl From<Fill> for crate::Fill {
+        fn from(fill: Fill) -> Self {
+            match fill {
+                Fill::NonZero => Self::NonZero,
+                Fill::EvenOdd => Self::EvenOdd,
+            }
+        }
+    }
+
+    /// Serialized form of an [extend mode](crate::Extend).
+    ///
+    /// [`Extend::Border`](crate::Extend::Border) was added after this version, and
+    /// fails to convert with [`SchemaError::Unrepresentable`].
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum Extend {
+        /// Extends the image by repeating the edge color of the brush.
+        Pad,
+        /// Extends the image by repeating the brush.
+        Repeat,
+        /// Extends the image by reflecting the brush.
+        Reflect,
+    }
+
+    impl TryFrom<crate::Extend> for Extend {
+        type Error = SchemaError;
+
+        fn try_from(extend: crate::Extend) -> Result<Self, SchemaError> {
+            match extend {
+                crate::Extend::Pad => Ok(Self::Pad),
+                crate::Extend::Repeat => Ok(Self::Repeat),
+                crate::Extend::Reflect => Ok(Self::Reflect),
+                crate::Extend::Border => Err(SchemaError::Unrepresentable("border")),
+            }
+        }
+    }
+
+    impl From<Extend> for crate::Extend {
+        fn from(extend: Extend) -> Self {
+            match extend {
+                Extend::Pad => Self::Pad,
+                Extend::Repeat => Self::Repeat,
+                Extend::Reflect => Self::Reflect,
+            }
+        }
+    }
+
+    /// Serialized form of a stroke [join](kurbo::Join).
+    #[cfg(feature = "kurbo")]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum Join {
+        /// A straight line connecting the segments.
+        Bevel,
+        /// The segments are extended to their natural intersection point.
+        Miter,
+        /// An arc between the segments.
+        Round,
+    }
+
+    /// Serialized form of a stroke [cap](kurbo::Cap).
+    #[cfg(feature = "kurbo")]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum Cap {
+        /// Flat cap.
+        Butt,
+        /// Square cap with dimensions equal to half the stroke width.
+        Square,
+        /// Rounded cap with radius equal to half the stroke width.
+        Round,
+    }
+
+    /// Serialized form of a [stroke](kurbo::Stroke).
+    #[cfg(feature = "kurbo")]
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Stroke {
+        /// Width of the stroke.
+        pub width: f64,
+        /// Style for connecting segments of the stroke.
+        pub join: Join,
+        /// Limit for miter joins.
+        pub miter_limit: f64,
+        /// Style for capping the beginning of an open subpath.
+        pub start_cap: Cap,
+        /// Style for capping the end of an open subpath.
+        pub end_cap: Cap,
+        /// Lengths of dashes in alternating on/off order.
+        pub dash_pattern: Vec<f64>,
+        /// Offset of the first dash.
+        pub dash_offset: f64,
+    }
+
+    /// Serialized form of a [style](crate::Style).
+    #[cfg(feature = "kurbo")]
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "type", rename_all = "kebab-case")]
+    pub enum Style {
+        /// Filled with the given fill rule.
+        Fill {
+            /// The fill rule.
+            rule: Fill,
+        },
+        /// Stroked with the given parameters.
+        Stroke(Stroke),
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<kurbo::Join> for Join {
+        fn from(join: kurbo::Join) -> Self {
+            match join {
+                kurbo::Join::Bevel => Self::Bevel,
+                kurbo::Join::Miter => Self::Miter,
+                kurbo::Join::Round => Self::Round,
+            }
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<Join> for kurbo::Join {
+        fn from(join: Join) -> Self {
+            match join {
+                Join::Bevel => Self::Bevel,
+                Join::Miter => Self::Miter,
+                Join::Round => Self::Round,
+            }
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<kurbo::Cap> for Cap {
+        fn from(cap: kurbo::Cap) -> Self {
+            match cap {
+                kurbo::Cap::Butt => Self::Butt,
+                kurbo::Cap::Square => Self::Square,
+                kurbo::Cap::Round => Self::Round,
+            }
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<Cap> for kurbo::Cap {
+        fn from(cap: Cap) -> Self {
+            match cap {
+                Cap::Butt => Self::Butt,
+                Cap::Square => Self::Square,
+                Cap::Round => Self::Round,
+            }
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<&kurbo::Stroke> for Stroke {
+        fn from(stroke: &kurbo::Stroke) -> Self {
+            Self {
+                width: stroke.width,
+                join: stroke.join.into(),
+                miter_limit: stroke.miter_limit,
+                start_cap: stroke.start_cap.into(),
+                end_cap: stroke.end_cap.into(),
+                dash_pattern: stroke.dash_pattern.to_vec(),
+                dash_offset: stroke.dash_offset,
+            }
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<Stroke> for kurbo::Stroke {
+        fn from(stroke: Stroke) -> Self {
+            Self::new(stroke.width)
+                .with_join(stroke.join.into())
+                .with_miter_limit(stroke.miter_limit)
+                .with_start_cap(stroke.start_cap.into())
+                .with_end_cap(stroke.end_cap.into())
+                .with_dashes(stroke.dash_offset, stroke.dash_pattern)
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<crate::Style> for Style {
+        fn from(style: crate::Style) -> Self {
+            match style {
+                crate::Style::Fill(fill) => Self::Fill { rule: fill.into() },
+                crate::Style::Stroke(stroke) => Self::Stroke((&stroke).into()),
+            }
+        }
+    }
+
+    #[cfg(feature = "kurbo")]
+    impl From<Style> for crate::Style {
+        fn from(style: Style) -> Self {
+            match style {
+                Style::Fill { rule } => Self::Fill(rule.into()),
+                Style::Stroke(stroke) => Self::Stroke(stroke.into()),
+            }
+        }
+    }
+
+    /// Serialized form of a [color](color::DynamicColor) in any color space.
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Color {
+        /// CSS name of the color space, such as `srgb` or `oklch`.
+        pub space: String,
+        /// The components, with `None` for a [missing](color::Missing) component.
+        pub components: [Option<f32>; 4],
+    }
+
+    impl From<crate::Color> for Color {
+        fn from(color: crate::Color) -> Self {
+            let [r, g, b, a] = color.components;
+            Self {
+                space: "srgb".to_owned(),
+                components: [Some(r), Some(g), Some(b), Some(a)],
+            }
+        }
+    }
+
+    impl TryFrom<color::DynamicColor> for Color {
+        type Error = SchemaError;
+
+        fn try_from(color: color::DynamicColor) -> Result<Self, SchemaError> {
+            let space = css_color_space_name(color.cs)
+                .ok_or(SchemaError::Unrepresentable("color space"))?;
+            let missing = color.flags.missing();
+            let mut components = [None; 4];
+            for (ix, component) in components.iter_mut().enumerate() {
+                if !missing.contains(ix) {
+                    *component = Some(color.components[ix]);
+                }
+            }
+            Ok(Self {
+                space: space.to_owned(),
+                components,
+            })
+        }
+    }
+
+    impl TryFrom<Color> for color::DynamicColor {
+        type Error = SchemaError;
+
+        fn try_from(color: Color) -> Result<Self, SchemaError> {
+            let cs = css_color_space_from_name(&color.space)
+                .ok_or(SchemaError::UnknownName(color.space))?;
+            let mut missing = color::Missing::default();
+            let mut components = [0.0; 4];
+            for (ix, component) in color.components.into_iter().enumerate() {
+                match component {
+                    Some(value) => components[ix] = value,
+                    None => missing.insert(ix),
+                }
+            }
+            Ok(Self {
+                cs,
+                flags: color::Flags::from_missing(missing),
+                components,
+            })
+        }
+    }
+
+    /// Serialized form of a [hue direction](color::HueDirection).
+    #[cfg(feature = "gradient")]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum HueDirection {
+        /// Hue angles take the shorter of the two arcs between starting and ending values.
+        Shorter,
+        /// Hue angles take the longer of the two arcs between starting and ending values.
+        Longer,
+        /// Hue angles increase as they are interpolated.
+        Increasing,
+        /// Hue angles decrease as they are interpolated.
+        Decreasing,
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<color::HueDirection> for HueDirection {
+        type Error = SchemaError;
+
+        fn try_from(direction: color::HueDirection) -> Result<Self, SchemaError> {
+            match direction {
+                color::HueDirection::Shorter => Ok(Self::Shorter),
+                color::HueDirection::Longer => Ok(Self::Longer),
+                color::HueDirection::Increasing => Ok(Self::Increasing),
+                color::HueDirection::Decreasing => Ok(Self::Decreasing),
+                _ => Err(SchemaError::Unrepresentable("hue direction")),
+            }
+        }
+    }
+
+    #[cfg(feature = "gradient")]
+    impl From<HueDirection> for color::HueDirection {
+        fn from(direction: HueDirection) -> Self {
+            match direction {
+                HueDirection::Shorter => Self::Shorter,
+                HueDirection::Longer => Self::Longer,
+                HueDirection::Increasing => Self::Increasing,
+                HueDirection::Decreasing => Self::Decreasing,
+            }
+        }
+    }
+
+    /// Serialized form of a [color stop](crate::ColorStop).
+    #[cfg(feature = "gradient")]
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct ColorStop {
+        /// Normalized offset of the stop.
+        pub offset: f32,
+        /// Color at the offset.
+        pub color: Color,
+        /// Offset of the interpolation hint between this stop and the next one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub hint: Option<f32>,
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<crate::ColorStop> for ColorStop {
+        type Error = SchemaError;
+
+        fn try_from(stop: crate::ColorStop) -> Result<Self, SchemaError> {
+            Ok(Self {
+                offset: stop.offset,
+                color: stop.color.try_into()?,
+                hint: stop.hint,
+            })
+        }
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<ColorStop> for crate::ColorStop {
+        type Error = SchemaError;
+
+        fn try_from(stop: ColorStop) -> Result<Self, SchemaError> {
+            Ok(Self {
+                offset: stop.offset,
+                color: stop.color.try_into()?,
+                hint: stop.hint,
+            })
+        }
+    }
+
+    /// Serialized form of a [gradient kind](crate::GradientKind).
+    ///
+    /// Points are stored as `[x, y]`.
+    #[cfg(feature = "gradient")]
+    #[derive(Copy, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "type", rename_all = "kebab-case")]
+    pub enum GradientKind {
+        /// Gradient that transitions between two or more colors along a line.
+        Linear {
+            /// Starting point.
+            start: [f64; 2],
+            /// Ending point.
+            end: [f64; 2],
+        },
+        /// Gradient that transitions between two or more colors that radiate from an
+        /// origin.
+        Radial {
+            /// Center of start circle.
+            start_center: [f64; 2],
+            /// Radius of start circle.
+            start_radius: f32,
+            /// Center of end circle.
+            end_center: [f64; 2],
+            /// Radius of end circle.
+            end_radius: f32,
+        },
+        /// Gradient that transitions between two or more colors that rotate around a
+        /// center point.
+        Sweep {
+            /// Center point.
+            center: [f64; 2],
+            /// Start angle of the sweep, in radians.
+            start_angle: f32,
+            /// End angle of the sweep, in radians.
+            end_angle: f32,
+        },
+    }
+
+    #[cfg(feature = "gradient")]
+    impl From<crate::GradientKind> for GradientKind {
+        fn from(kind: crate::GradientKind) -> Self {
+            let point = |point: crate::Point| [point.x, point.y];
+            match kind {
+                crate::GradientKind::Linear { start, end } => Self::Linear {
+                    start: point(start),
+                    end: point(end),
+                },
+                crate::GradientKind::Radial {
+                    start_center,
+                    start_radius,
+                    end_center,
+                    end_radius,
+                } => Self::Radial {
+                    start_center: point(start_center),
+                    start_radius,
+                    end_center: point(end_center),
+                    end_radius,
+                },
+                crate::GradientKind::Sweep {
+                    center,
+                    start_angle,
+                    end_angle,
+                } => Self::Sweep {
+                    center: point(center),
+                    start_angle,
+                    end_angle,
+                },
+            }
+        }
+    }
+
+    #[cfg(feature = "gradient")]
+    impl From<GradientKind> for crate::GradientKind {
+        fn from(kind: GradientKind) -> Self {
+            let point = |[x, y]: [f64; 2]| crate::Point::new(x, y);
+            match kind {
+                GradientKind::Linear { start, end } => Self::Linear {
+                    start: point(start),
+                    end: point(end),
+                },
+                GradientKind::Radial {
+                    start_center,
+                    start_radius,
+                    end_center,
+                    end_radius,
+                } => Self::Radial {
+                    start_center: point(start_center),
+                    start_radius,
+                    end_center: point(end_center),
+                    end_radius,
+                },
+                GradientKind::Sweep {
+                    center,
+                    start_angle,
+                    end_angle,
+                } => Self::Sweep {
+                    center: point(center),
+                    start_angle,
+                    end_angle,
+                },
+            }
+        }
+    }
+
+    /// Serialized form of a [gradient](crate::Gradient).
+    ///
+    /// The [identifier](crate::Gradient::id) was added after this version, and is
+    /// dropped when converting, as it is only a hint for caching.
+    #[cfg(feature = "gradient")]
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Gradient {
+        /// Kind and geometry of the gradient.
+        pub kind: GradientKind,
+        /// Extend mode.
+        pub extend: Extend,
+        /// CSS name of the color space in which the stops are interpolated.
+        pub interpolation_cs: String,
+        /// Direction of hue interpolation for polar color spaces.
+        pub hue_direction: HueDirection,
+        /// Color stops.
+        pub stops: Vec<ColorStop>,
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<crate::Gradient> for Gradient {
+        type Error = SchemaError;
+
+        fn try_from(gradient: crate::Gradient) -> Result<Self, SchemaError> {
+            let interpolation_cs = css_color_space_name(gradient.interpolation_cs)
+                .ok_or(SchemaError::Unrepresentable("color space"))?;
+            Ok(Self {
+                kind: gradient.kind.into(),
+                extend: gradient.extend.try_into()?,
+                interpolation_cs: interpolation_cs.to_owned(),
+                hue_direction: gradient.hue_direction.try_into()?,
+                stops: gradient
+                    .stops
+                    .iter()
+                    .map(|&stop| stop.try_into())
+                    .collect::<Result<_, _>>()?,
+            })
+        }
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<Gradient> for crate::Gradient {
+        type Error = SchemaError;
+
+        fn try_from(gradient: Gradient) -> Result<Self, SchemaError> {
+            let interpolation_cs = css_color_space_from_name(&gradient.interpolation_cs)
+                .ok_or(SchemaError::UnknownName(gradient.interpolation_cs))?;
+            let stops = gradient
+                .stops
+                .into_iter()
+                .map(crate::ColorStop::try_from)
+                .collect::<Result<crate::ColorStops, _>>()?;
+            Ok(Self {
+                kind: gradient.kind.into(),
+                extend: gradient.extend.into(),
+                interpolation_cs,
+                hue_direction: gradient.hue_direction.into(),
+                stops,
+                id: None,
+            })
+        }
+    }
+
+    /// Serialized form of an [image format](crate::ImageFormat).
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum ImageFormat {
+        /// 32-bit RGBA with 8-bit channels.
+        Rgba8,
+    }
+
+    /// Serialized form of an [image quality](crate::ImageQuality).
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum ImageQuality {
+        /// Lowest quality with best performance characteristics.
+        Low,
+        /// Medium quality with reasonable performance characteristics.
+        Medium,
+        /// Highest quality with worst performance characteristics.
+        High,
+    }
+
+    /// Serialized form of a [tile mode](crate::TileMode).
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum TileMode {
+        /// Tiles are repeated with the given spacing.
+        Repeat,
+        /// Whole tiles are repeated with the spacing stretched to fill the image.
+        Space,
+        /// Tiles are scaled so that a whole number of them fills the image.
+        Round,
+    }
+
+    /// Serialized form of the [tiling](crate::ImageTiling) of an image.
+    ///
+    /// Vectors and points are stored as `[x, y]`.
+    #[derive(Copy, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct ImageTiling {
+        /// Tile mode in the horizontal direction.
+        pub x_mode: TileMode,
+        /// Tile mode in the vertical direction.
+        pub y_mode: TileMode,
+        /// Space between tiles.
+        pub spacing: [f64; 2],
+        /// Position of the first tile.
+        pub origin: [f64; 2],
+    }
+
+    /// Serialized form of an [image](crate::Image).
+    ///
+    /// The [minification quality](crate::Image::minification) and
+    /// [border color](crate::Image::border_color) were added after this version. Images
+    /// with a minification quality other than the default fail to convert with
+    /// [`SchemaError::Unrepresentable`], and the border color is dropped, as it only
+    /// applies to [`Extend::Border`](crate::Extend::Border), which can't be represented.
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Image {
+        /// The pixel data.
+        #[serde(with = "serde_bytes")]
+        pub data: Vec<u8>,
+        /// Pixel format of the image.
+        pub format: ImageFormat,
+        /// Width of the image.
+        pub width: u32,
+        /// Height of the image.
+        pub height: u32,
+        /// Extend mode in the horizontal direction.
+        pub x_extend: Extend,
+        /// Extend mode in the vertical direction.
+        pub y_extend: Extend,
+        /// Hint for desired rendering quality.
+        pub quality: ImageQuality,
+        /// Tiling of the image.
+        pub tiling: ImageTiling,
+        /// An additional alpha multiplier to use with the image.
+        pub alpha: f32,
+    }
+
+    impl TryFrom<&crate::Image> for Image {
+        type Error = SchemaError;
+
+        fn try_from(image: &crate::Image) -> Result<Self, SchemaError> {
+            if image.minification != crate::MinificationQuality::default() {
+                return Err(SchemaError::Unrepresentable("minification"));
+            }
+            let tile_mode = |mode| match mode {
+                crate::TileMode::Repeat => TileMode::Repeat,
+                crate::TileMode::Space => TileMode::Space,
+                crate::TileMode::Round => TileMode::Round,
+            };
+            Ok(Self {
+                data: image.data.data().to_vec(),
+                format: match image.format {
+                    crate::ImageFormat::Rgba8 => ImageFormat::Rgba8,
+                },
+                width: image.width,
+                height: image.height,
+                x_extend: image.x_extend.try_into()?,
+                y_extend: image.y_extend.try_into()?,
+                quality: match image.quality {
+                    crate::ImageQuality::Low => ImageQuality::Low,
+                    crate::ImageQuality::Medium => ImageQuality::Medium,
+                    crate::ImageQuality::High => ImageQuality::High,
+                },
+                tiling: ImageTiling {
+                    x_mode: tile_mode(image.tiling.x_mode),
+                    y_mode: tile_mode(image.tiling.y_mode),
+                    spacing: [image.tiling.spacing.x, image.tiling.spacing.y],
+                    origin: [image.tiling.origin.x, image.tiling.origin.y],
+                },
+                alpha: image.alpha,
+            })
+        }
+    }
+
+    impl From<Image> for crate::Image {
+        fn from(image: Image) -> Self {
+            let tile_mode = |mode| match mode {
+                TileMode::Repeat => crate::TileMode::Repeat,
+                TileMode::Space => crate::TileMode::Space,
+                TileMode::Round => crate::TileMode::Round,
+            };
+            let format = match image.format {
+                ImageFormat::Rgba8 => crate::ImageFormat::Rgba8,
+            };
+            Self::new(image.data.into(), format, image.width, image.height)
+                .with_x_extend(image.x_extend.into())
+                .with_y_extend(image.y_extend.into())
+                .with_quality(match image.quality {
+                    ImageQuality::Low => crate::ImageQuality::Low,
+                    ImageQuality::Medium => crate::ImageQuality::Medium,
+                    ImageQuality::High => crate::ImageQuality::High,
+                })
+                .with_tiling(crate::ImageTiling {
+                    x_mode: tile_mode(image.tiling.x_mode),
+                    y_mode: tile_mode(image.tiling.y_mode),
+                    spacing: crate::Vec2::new(image.tiling.spacing[0], image.tiling.spacing[1]),
+                    origin: crate::Point::new(image.tiling.origin[0], image.tiling.origin[1]),
+                })
+                .with_alpha(image.alpha)
+        }
+    }
+
+    /// Serialized form of a [noise type](crate::NoiseType).
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum NoiseType {
+        /// Fractal noise, which is smooth.
+        FractalNoise,
+        /// Turbulence, which has sharp creases.
+        Turbulence,
+    }
+
+    /// Serialized form of a [noise brush](crate::NoiseBrush).
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct NoiseBrush {
+        /// Type of noise.
+        pub noise_type: NoiseType,
+        /// Base frequency of the noise, as `[x, y]`.
+        pub base_frequency: [f64; 2],
+        /// Number of octaves of noise which are summed.
+        pub octaves: u32,
+        /// Seed for the pseudo-random number generator.
+        pub seed: i32,
+        /// Whether the noise tiles seamlessly over the painted area.
+        pub stitch_tiles: bool,
+        /// An additional alpha multiplier to use with the noise.
+        pub alpha: f32,
+    }
+
+    impl From<crate::NoiseBrush> for NoiseBrush {
+        fn from(noise: crate::NoiseBrush) -> Self {
+            Self {
+                noise_type: match noise.noise_type {
+                    crate::NoiseType::FractalNoise => NoiseType::FractalNoise,
+                    crate::NoiseType::Turbulence => NoiseType::Turbulence,
+                },
+                base_frequency: [noise.base_frequency.x, noise.base_frequency.y],
+                octaves: noise.octaves,
+                seed: noise.seed,
+                stitch_tiles: noise.stitch_tiles,
+                alpha: noise.alpha,
+            }
+        }
+    }
+
+    impl From<NoiseBrush> for crate::NoiseBrush {
+        fn from(noise: NoiseBrush) -> Self {
+            Self {
+                noise_type: match noise.noise_type {
+                    NoiseType::FractalNoise => crate::NoiseType::FractalNoise,
+                    NoiseType::Turbulence => crate::NoiseType::Turbulence,
+                },
+                base_frequency: crate::Vec2::new(noise.base_frequency[0], noise.base_frequency[1]),
+                octaves: noise.octaves,
+                seed: noise.seed,
+                stitch_tiles: noise.stitch_tiles,
+                alpha: noise.alpha,
+            }
+        }
+    }
+
+    /// Serialized form of a [brush](crate::Brush).
+    #[cfg(feature = "gradient")]
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "type", content = "value", rename_all = "kebab-case")]
+    pub enum Brush {
+        /// Solid sRGB color brush, whose color space is always `srgb`.
+        Solid(Color),
+        /// Solid color brush with a color in any color space.
+        DynamicSolid(Color),
+        /// Gradient brush.
+        Gradient(Gradient),
+        /// Image brush.
+        Image(Image),
+        /// Noise brush.
+        Noise(NoiseBrush),
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<&crate::Brush> for Brush {
+        type Error = SchemaError;
+
+        fn try_from(brush: &crate::Brush) -> Result<Self, SchemaError> {
+            Ok(match brush {
+                crate::Brush::Solid(color) => Self::Solid((*color).into()),
+                crate::Brush::DynamicSolid(color) => Self::DynamicSolid((*color).try_into()?),
+                crate::Brush::Gradient(gradient) => Self::Gradient(gradient.clone().try_into()?),
+                crate::Brush::Image(image) => Self::Image(image.try_into()?),
+                crate::Brush::Procedural(crate::ProceduralBrush::Noise(noise)) => {
+                    Self::Noise((*noise).into())
+                }
+            })
+        }
+    }
+
+    #[cfg(feature = "gradient")]
+    impl TryFrom<Brush> for crate::Brush {
+        type Error = SchemaError;
+
+        fn try_from(brush: Brush) -> Result<Self, SchemaError> {
+            Ok(match brush {
+                Brush::Solid(color) => {
+                    Self::Solid(color::DynamicColor::try_from(color)?.to_alpha_color())
+                }
+                Brush::DynamicSolid(color) => Self::DynamicSolid(color.try_into()?),
+                Brush::Gradient(gradient) => Self::Gradient(gradient.try_into()?),
+                Brush::Image(image) => Self::Image(image.into()),
+                Brush::Noise(noise) => {
+                    Self::Procedural(crate::ProceduralBrush::Noise(noise.into()))
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{v1, SchemaError};
+    use crate::{BlendMode, Compose, Extend, Fill, Mix};
+
+    #[test]
+    fn blend_modes() {
+        for mode in BlendMode::all() {
+            let schema = v1::BlendMode::from(mode);
+            assert_eq!(BlendMode::try_from(schema), Ok(mode));
+        }
+        let mode = v1::BlendMode::from(BlendMode::new(Mix::ColorDodge, Compose::DestAtop));
+        assert_eq!(mode.mix, "color-dodge");
+        assert_eq!(mode.compose, "dest-atop");
+        let unknown = v1::BlendMode {
+            mix: "sparkle".into(),
+            compose: "src-over".into(),
+        };
+        assert_eq!(
+            BlendMode::try_from(unknown),
+            Err(SchemaError::UnknownName("sparkle".into()))
+        );
+    }
+
+    #[test]
+    fn enums() {
+        for fill in Fill::ALL {
+            assert_eq!(Fill::from(v1::Fill::from(fill)), fill);
+        }
+        for extend in [Extend::Pad, Extend::Repeat, Extend::Reflect] {
+            let schema = v1::Extend::try_from(extend).unwrap();
+            assert_eq!(Extend::from(schema), extend);
+        }
+        assert_eq!(
+            v1::Extend::try_from(Extend::Border),
+            Err(SchemaError::Unrepresentable("border"))
+        );
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn brushes() {
+        use crate::{
+            Blob, Brush, ColorStop, Gradient, Image, ImageFormat, ImageTiling, NoiseBrush,
+            NoiseType, ProceduralBrush, TileMode,
+        };
+        use color::{palette, ColorSpaceTag, DynamicColor, Flags, HueDirection, Missing};
+
+        let wide = DynamicColor {
+            cs: ColorSpaceTag::Oklch,
+            flags: Flags::from_missing(Missing::single(2)),
+            components: [0.7, 0.2, 0.0, 1.0],
+        };
+        let gradient = Gradient::new_sweep((1.0, 2.0), 0.5, 3.0)
+            .with_extend(Extend::Reflect)
+            .with_interpolation_cs(ColorSpaceTag::Oklch)
+            .with_hue_direction(HueDirection::Longer)
+            .with_stops([
+                ColorStop::from((0.0, palette::css::RED)).with_hint(0.3),
+                ColorStop {
+                    offset: 1.0,
+                    color: wide,
+                    hint: None,
+                },
+            ]);
+        let image = Image::new(Blob::from(vec![1, 2, 3, 4]), ImageFormat::Rgba8, 1, 1)
+            .with_x_extend(Extend::Repeat)
+            .with_tiling(
+                ImageTiling::default()
+                    .with_mode(TileMode::Round)
+                    .with_spacing((2.0, 3.0))
+                    .with_origin((4.0, 5.0)),
+            )
+            .with_alpha(0.5);
+        let noise = NoiseBrush::new(NoiseType::Turbulence, (0.05, 0.1), 3).with_seed(7);
+        for brush in [
+            Brush::Solid(palette::css::ORANGE),
+            Brush::DynamicSolid(wide),
+            Brush::Gradient(gradient.clone()),
+            Brush::Procedural(ProceduralBrush::Noise(noise)),
+        ] {
+            let schema = v1::Brush::try_from(&brush).unwrap();
+            assert_eq!(Brush::try_from(schema), Ok(brush));
+        }
+
+        // Blobs are compared by identity, so compare the pixels separately.
+        let image_schema = v1::Brush::try_from(&Brush::Image(image.clone())).unwrap();
+        let Ok(Brush::Image(mut round_trip)) = Brush::try_from(image_schema) else {
+            panic!("expected an image brush");
+        };
+        assert_eq!(round_trip.data.data(), image.data.data());
+        round_trip.data = image.data.clone();
+        assert_eq!(round_trip, image);
+
+        // The identifier of a gradient is dropped.
+        let schema = v1::Brush::try_from(&Brush::Gradient(gradient.clone().with_id(3))).unwrap();
+        assert_eq!(
+            Brush::try_from(schema),
+            Ok(Brush::Gradient(gradient.clone()))
+        );
+
+        // Values which were added after this version are rejected.
+        assert_eq!(
+            v1::Gradient::try_from(gradient.with_extend(Extend::Border)),
+            Err(SchemaError::Unrepresentable("border"))
+        );
+        assert_eq!(
+            v1::Image::try_from(&image.with_minification(crate::MinificationQuality::Trilinear)),
+            Err(SchemaError::Unrepresentable("minification"))
+        );
+        let unknown = v1::Color {
+            space: "cmyk".into(),
+            components: [Some(0.0); 4],
+        };
+        assert_eq!(
+            DynamicColor::try_from(unknown),
+            Err(SchemaError::UnknownName("cmyk".into()))
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn versioned() {
+        use super::Versioned;
+        use serde::de::IgnoredAny;
+
+        let document = v1::versioned(v1::Fill::EvenOdd);
+        let json = serde_json::to_string(&document).unwrap();
+        assert_eq!(json, r#"{"version":1,"value":"even-odd"}"#);
+        let read: Versioned<v1::Fill> = serde_json::from_str(&json).unwrap();
+        assert_eq!(v1::unversioned(read), Ok(v1::Fill::EvenOdd));
+
+        // The version can be read without knowing the format of the value.
+        let future = r#"{"version":2,"value":{"rule":"winding"}}"#;
+        let peeked: Versioned<IgnoredAny> = serde_json::from_str(future).unwrap();
+        assert_eq!(peeked.version, 2);
+        assert_eq!(
+            v1::unversioned(Versioned {
+                version: 2,
+                value: ()
+            }),
+            Err(SchemaError::UnsupportedVersion(2))
+        );
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn styles() {
+        use crate::Style;
+        use kurbo::{Cap, Join, Stroke};
+
+        let stroke = Stroke::new(2.5)
+            .with_join(Join::Round)
+            .with_start_cap(Cap::Square)
+            .with_dashes(1.0, [4.0, 2.0]);
+        for style in [Style::from(Fill::EvenOdd), Style::from(stroke)] {
+            let schema = v1::Style::from(style.clone());
+            let round_trip = Style::from(schema);
+            match (&style, &round_trip) {
+                (Style::Fill(a), Style::Fill(b)) => assert_eq!(a, b),
+                (Style::Stroke(a), Style::Stroke(b)) => {
+                    assert_eq!(a.width, b.width);
+                    assert_eq!(a.join, b.join);
+                    assert_eq!(a.miter_limit, b.miter_limit);
+                    assert_eq!(a.start_cap, b.start_cap);
+                    assert_eq!(a.end_cap, b.end_cap);
+                    assert_eq!(a.dash_pattern, b.dash_pattern);
+                    assert_eq!(a.dash_offset, b.dash_offset);
+                }
+                _ => panic!("style kind changed in round trip"),
+            }
+        }
+    }
+}
@@ -0,0 +1,923 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+extern crate alloc;
+use alloc::sync::Arc;
+
+use kurbo::{
+    BezPath, Circle, Ellipse, Line, ParamCurveNearest, PathEl, Point, Rect, RoundedRect,
+    RoundedRectRadii, Shape, Vec2,
+};
+
+#[cfg(not(feature = "std"))]
+use crate::floatfuncs::FloatFuncs as _;
+
+/// Handle length, relative to the radius, of a cubic Bézier approximating a
+/// quarter circle which passes through the circle at 45°.
+const CIRCLE_HANDLE: f64 = 0.552_284_749_831;
+
+/// A rectangle with smoothed ("squircle") corners.
+///
+/// Each corner is a quadrant of a [superellipse] rather than a circular arc, which
+/// avoids the visible discontinuity in curvature where a circular corner meets a
+/// straight edge. This is the style of corner used by iOS and by the "corner
+/// smoothing" option in several design tools.
+///
+/// Keeping this as a distinct shape, rather than flattening it to a path, lets
+/// renderers which can compute coverage for superellipses analytically do so.
+/// Other renderers can use the [`Shape`] implementation, which approximates each
+/// corner with a single cubic Bézier.
+///
+/// [superellipse]: https://en.wikipedia.org/wiki/Superellipse
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmoothRoundedRect {
+    /// The bounding rectangle.
+    pub rect: Rect,
+    /// The distance from each corner at which the corner curve meets the edges.
+    ///
+    /// This is clamped to half of the smaller side of the rectangle.
+    pub radius: f64,
+    /// The amount of corner smoothing, from `0.0` to `1.0`.
+    ///
+    /// A value of `0.0` produces circular corners, equivalent to a
+    /// [`RoundedRect`](kurbo::RoundedRect). Larger values pull the corner curve
+    /// towards the corner of the rectangle.
+    pub smoothing: f64,
+}
+
+impl SmoothRoundedRect {
+    /// Creates a new rectangle with smoothed corners.
+    #[must_use]
+    pub fn new(rect: impl Into<Rect>, radius: f64, smoothing: f64) -> Self {
+        Self {
+            rect: rect.into(),
+            radius,
+            smoothing,
+        }
+    }
+
+    /// Returns the corner radius, clamped to half of the smaller side of the
+    /// rectangle.
+    #[must_use]
+    pub fn clamped_radius(&self) -> f64 {
+        let rect = self.rect.abs();
+        self.radius
+            .min(0.5 * rect.width().min(rect.height()))
+            .max(0.0)
+    }
+
+    /// Returns the exponent of the superellipse which describes each corner.
+    ///
+    /// An exponent of `2.0` is a circular corner. The maximum smoothing corresponds
+    /// to an exponent of about `5.19`.
+    #[must_use]
+    pub fn exponent(&self) -> f64 {
+        // The corner curve passes through the point of the superellipse at 45°,
+        // which is at `2^(-1/n)` of the radius from the center of the corner.
+        let midpoint = (4.0 + 3.0 * self.handle()) / 8.0;
+        -core::f64::consts::LN_2 / midpoint.ln()
+    }
+
+    /// Handle length of the corner cubics, relative to the radius.
+    fn handle(&self) -> f64 {
+        let smoothing = self.smoothing.clamp(0.0, 1.0);
+        CIRCLE_HANDLE + (1.0 - CIRCLE_HANDLE) * smoothing
+    }
+
+    fn path_array(&self) -> [PathEl; 10] {
+        let Rect { x0, y0, x1, y1 } = self.rect.abs();
+        let r = self.clamped_radius();
+        // Distance from the corner to the control points.
+        let c = r * (1.0 - self.handle());
+        [
+            PathEl::MoveTo(Point::new(x0 + r, y0)),
+            PathEl::LineTo(Point::new(x1 - r, y0)),
+            PathEl::CurveTo(
+                Point::new(x1 - c, y0),
+                Point::new(x1, y0 + c),
+                Point::new(x1, y0 + r),
+            ),
+            PathEl::LineTo(Point::new(x1, y1 - r)),
+            PathEl::CurveTo(
+                Point::new(x1, y1 - c),
+                Point::new(x1 - c, y1),
+                Point::new(x1 - r, y1),
+            ),
+            PathEl::LineTo(Point::new(x0 + r, y1)),
+            PathEl::CurveTo(
+                Point::new(x0 + c, y1),
+                Point::new(x0, y1 - c),
+                Point::new(x0, y1 - r),
+            ),
+            PathEl::LineTo(Point::new(x0, y0 + r)),
+            PathEl::CurveTo(
+                Point::new(x0, y0 + c),
+                Point::new(x0 + c, y0),
+                Point::new(x0 + r, y0),
+            ),
+            PathEl::ClosePath,
+        ]
+    }
+}
+
+impl Shape for SmoothRoundedRect {
+    type PathElementsIter<'iter> = core::array::IntoIter<PathEl, 10>;
+
+    fn path_elements(&self, _tolerance: f64) -> Self::PathElementsIter<'_> {
+        self.path_array().into_iter()
+    }
+
+    fn area(&self) -> f64 {
+        self.path_array().area()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.path_array().perimeter(accuracy)
+    }
+
+    fn winding(&self, pt: Point) -> i32 {
+        self.path_array().winding(pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        self.rect.abs()
+    }
+}
+
+/// Radii of the corners of a rectangle, where each corner can be elliptical.
+///
+/// This matches the CSS `border-radius` property, where each corner has separate
+/// horizontal and vertical radii. [`RoundedRect`] only supports circular corners,
+/// so use [`to_geometry`](Self::to_geometry) to get the shape of a rectangle with
+/// these corners.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CornerRadii {
+    /// The horizontal and vertical radii of the top left corner.
+    pub top_left: Vec2,
+    /// The horizontal and vertical radii of the top right corner.
+    pub top_right: Vec2,
+    /// The horizontal and vertical radii of the bottom right corner.
+    pub bottom_right: Vec2,
+    /// The horizontal and vertical radii of the bottom left corner.
+    pub bottom_left: Vec2,
+}
+
+impl CornerRadii {
+    /// Creates new corner radii from the radii of each corner, in clockwise order
+    /// starting from the top left.
+    #[must_use]
+    pub fn new(
+        top_left: impl Into<Vec2>,
+        top_right: impl Into<Vec2>,
+        bottom_right: impl Into<Vec2>,
+        bottom_left: impl Into<Vec2>,
+    ) -> Self {
+        Self {
+            top_left: top_left.into(),
+            top_right: top_right.into(),
+            bottom_right: bottom_right.into(),
+            bottom_left: bottom_left.into(),
+        }
+    }
+
+    /// Creates corner radii where every corner is circular with the same radius.
+    #[must_use]
+    pub fn uniform(radius: f64) -> Self {
+        let radius = Vec2::new(radius, radius);
+        Self::new(radius, radius, radius, radius)
+    }
+
+    /// Returns the radii in clockwise order, starting from the top left.
+    #[must_use]
+    pub fn as_array(&self) -> [Vec2; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+
+    /// Returns `true` if every corner is circular.
+    #[must_use]
+    pub fn is_circular(&self) -> bool {
+        self.as_array().iter().all(|radius| radius.x == radius.y)
+    }
+
+    /// Returns the radii reduced to fit within `rect`, as CSS does.
+    ///
+    /// Negative and non-finite radii are treated as zero, and a corner with a zero
+    /// radius in either direction is square. Then, if the radii of adjacent corners
+    /// along any side sum to more than the length of that side, every radius is
+    /// scaled by the same factor so that they fit, keeping the shape of each
+    /// corner.
+    #[must_use]
+    pub fn normalized(&self, rect: Rect) -> Self {
+        let rect = rect.abs();
+        let [tl, tr, br, bl] = self.as_array().map(|radius| {
+            let valid = |r: f64| if r.is_finite() && r > 0.0 { r } else { 0.0 };
+            let (x, y) = (valid(radius.x), valid(radius.y));
+            if x == 0.0 || y == 0.0 {
+                Vec2::ZERO
+            } else {
+                Vec2::new(x, y)
+            }
+        });
+        let fit = |length: f64, sum: f64| if sum > 0.0 { length / sum } else { 1.0 };
+        let scale = fit(rect.width(), tl.x + tr.x)
+            .min(fit(rect.width(), bl.x + br.x))
+            .min(fit(rect.height(), tl.y + bl.y))
+            .min(fit(rect.height(), tr.y + br.y))
+            .min(1.0);
+        Self::new(tl * scale, tr * scale, br * scale, bl * scale)
+    }
+
+    /// Returns the geometry of `rect` with these corners, after
+    /// [normalizing](Self::normalized) the radii.
+    ///
+    /// This is a [`Geometry::RoundedRect`] if every corner is circular, and a
+    /// [`Geometry::Path`] otherwise, with each elliptical corner approximated by a
+    /// cubic Bézier.
+    #[must_use]
+    pub fn to_geometry(&self, rect: Rect) -> Geometry {
+        let rect = rect.abs();
+        let radii = self.normalized(rect);
+        if radii.is_circular() {
+            return Geometry::RoundedRect(RoundedRect::from_rect(
+                rect,
+                RoundedRectRadii::new(
+                    radii.top_left.x,
+                    radii.top_right.x,
+                    radii.bottom_right.x,
+                    radii.bottom_left.x,
+                ),
+            ));
+        }
+        let Rect { x0, y0, x1, y1 } = rect;
+        let c = 1.0 - CIRCLE_HANDLE;
+        let mut path = BezPath::new();
+        // Each corner is given by the point where it meets the previous side, its
+        // vertex, and the point where it meets the next side.
+        let corners = [
+            (
+                radii.top_left,
+                Point::new(x0, y0),
+                Vec2::new(0.0, 1.0),
+                Vec2::new(1.0, 0.0),
+            ),
+            (
+                radii.top_right,
+                Point::new(x1, y0),
+                Vec2::new(-1.0, 0.0),
+                Vec2::new(0.0, 1.0),
+            ),
+            (
+                radii.bottom_right,
+                Point::new(x1, y1),
+                Vec2::new(0.0, -1.0),
+                Vec2::new(-1.0, 0.0),
+            ),
+            (
+                radii.bottom_left,
+                Point::new(x0, y1),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(0.0, -1.0),
+            ),
+        ];
+        for (i, (radius, vertex, from, to)) in corners.into_iter().enumerate() {
+            let scale = |dir: Vec2, t: f64| Vec2::new(dir.x * radius.x * t, dir.y * radius.y * t);
+            let start = vertex + scale(from, 1.0);
+            let end = vertex + scale(to, 1.0);
+            if i == 0 {
+                path.move_to(start);
+            } else {
+                path.line_to(start);
+            }
+            if radius != Vec2::ZERO {
+                path.curve_to(vertex + scale(from, c), vertex + scale(to, c), end);
+            }
+        }
+        path.close_path();
+        Geometry::from(path)
+    }
+}
+
+impl From<RoundedRectRadii> for CornerRadii {
+    fn from(radii: RoundedRectRadii) -> Self {
+        let circle = |radius: f64| Vec2::new(radius, radius);
+        Self::new(
+            circle(radii.top_left),
+            circle(radii.top_right),
+            circle(radii.bottom_right),
+            circle(radii.bottom_left),
+        )
+    }
+}
+
+/// Geometry of a fill, stroke or clip.
+///
+/// This covers the shapes which renderers commonly handle specially, with
+/// [`Path`](Self::Path) for everything else. Unlike an `impl Shape` parameter, a
+/// geometry can be stored, so retained display lists can use the same type for
+/// draws and clips without flattening every shape to a path.
+///
+/// Paths are reference counted so that cloning a geometry is cheap.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Geometry {
+    /// A rectangle.
+    Rect(Rect),
+    /// A rectangle with circular corners.
+    RoundedRect(RoundedRect),
+    /// A rectangle with smoothed corners.
+    SmoothRoundedRect(SmoothRoundedRect),
+    /// A circle.
+    Circle(Circle),
+    /// An ellipse.
+    Ellipse(Ellipse),
+    /// A line segment.
+    Line(Line),
+    /// An arbitrary path.
+    Path(Arc<BezPath>),
+}
+
+impl Geometry {
+    /// Creates a geometry from any shape, using a specialized variant where the
+    /// shape reports being one through [`Shape::as_rect`] and similar.
+    ///
+    /// Other shapes are converted to a path with the given tolerance.
+    #[must_use]
+    pub fn from_shape(shape: &impl Shape, tolerance: f64) -> Self {
+        if let Some(rect) = shape.as_rect() {
+            Self::Rect(rect)
+        } else if let Some(rounded) = shape.as_rounded_rect() {
+            Self::RoundedRect(rounded)
+        } else if let Some(circle) = shape.as_circle() {
+            Self::Circle(circle)
+        } else if let Some(line) = shape.as_line() {
+            Self::Line(line)
+        } else {
+            Self::Path(Arc::new(shape.to_path(tolerance)))
+        }
+    }
+
+    /// Returns `true` if the geometry certainly covers all of `rect`.
+    ///
+    /// Renderers can use this to elide a clip which has no effect on the content
+    /// inside `rect`. This is conservative: it can return `false` for paths which do
+    /// cover the rectangle, but never returns `true` for geometry which doesn't.
+    #[must_use]
+    pub fn covers(&self, rect: Rect) -> bool {
+        let rect = rect.abs();
+        match self {
+            Self::Rect(clip) => {
+                let clip = clip.abs();
+                clip.x0 <= rect.x0 && clip.y0 <= rect.y0 && clip.x1 >= rect.x1 && clip.y1 >= rect.y1
+            }
+            // These shapes are convex, so they cover the rectangle if they contain
+            // each of its corners.
+            Self::RoundedRect(_)
+            | Self::SmoothRoundedRect(_)
+            | Self::Circle(_)
+            | Self::Ellipse(_) => [
+                Point::new(rect.x0, rect.y0),
+                Point::new(rect.x1, rect.y0),
+                Point::new(rect.x1, rect.y1),
+                Point::new(rect.x0, rect.y1),
+            ]
+            .into_iter()
+            .all(|corner| self.winding(corner) != 0),
+            Self::Line(_) | Self::Path(_) => false,
+        }
+    }
+
+    /// Returns the part of `rect` which may be inside the geometry, or `None` if
+    /// the geometry certainly excludes all of `rect`.
+    ///
+    /// Renderers can use this to skip content outside of a clip, and to tighten the
+    /// bounds of the layer for a clip. Rectangles and rounded rectangles are handled
+    /// exactly, so a rectangle within the cut off corner of a rounded rectangle is
+    /// excluded. Other geometry is handled by its [bounding box](Shape::bounding_box).
+    #[must_use]
+    pub fn intersect_bounds(&self, rect: Rect) -> Option<Rect> {
+        let bounds = self.bounding_box();
+        let rect = rect.abs();
+        let intersection = bounds.intersect(rect);
+        if bounds.x0 > rect.x1 || bounds.x1 < rect.x0 || bounds.y0 > rect.y1 || bounds.y1 < rect.y0
+        {
+            return None;
+        }
+        if let Self::RoundedRect(rounded) = self {
+            let radii = rounded.radii();
+            let corners = [
+                (Point::new(bounds.x0, bounds.y0), radii.top_left, 1.0, 1.0),
+                (Point::new(bounds.x1, bounds.y0), radii.top_right, -1.0, 1.0),
+                (
+                    Point::new(bounds.x1, bounds.y1),
+                    radii.bottom_right,
+                    -1.0,
+                    -1.0,
+                ),
+                (
+                    Point::new(bounds.x0, bounds.y1),
+                    radii.bottom_left,
+                    1.0,
+                    -1.0,
+                ),
+            ];
+            for (corner, radius, dx, dy) in corners {
+                // The intersection is excluded if it lies within the square of the
+                // corner, outside of the circle of the corner.
+                let center = corner + (dx * radius, dy * radius);
+                let square = Rect::from_points(corner, center);
+                let outside = Rect::from_points(
+                    (
+                        intersection.x0.clamp(square.x0, square.x1),
+                        intersection.y0.clamp(square.y0, square.y1),
+                    ),
+                    (
+                        intersection.x1.clamp(square.x0, square.x1),
+                        intersection.y1.clamp(square.y0, square.y1),
+                    ),
+                ) == intersection;
+                let nearest = Point::new(
+                    center.x.clamp(intersection.x0, intersection.x1),
+                    center.y.clamp(intersection.y0, intersection.y1),
+                );
+                if outside && radius > 0.0 && nearest.distance(center) > radius {
+                    return None;
+                }
+            }
+        }
+        Some(intersection)
+    }
+
+    /// Returns the geometry with redundant path elements removed.
+    ///
+    /// Segments which don't move further than `tolerance` from their start are
+    /// removed, as are the points between consecutive collinear lines, where
+    /// collinear means within `tolerance` of the line joining their neighbors.
+    /// Geometry other than paths is returned unchanged.
+    #[must_use]
+    pub fn simplified(&self, tolerance: f64) -> Self {
+        match self {
+            Self::Path(path) => Self::Path(Arc::new(simplify_path(path, tolerance))),
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns `true` if the geometry is of the same kind as `other`, with each
+    /// coordinate and radius within `tolerance` of the corresponding one.
+    ///
+    /// Paths are [simplified](Self::simplified) before comparing them, so paths
+    /// which differ only in redundant elements are equal. This allows scene diffing
+    /// to detect an unchanged clip when it has been regenerated with small numeric
+    /// differences.
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        let eq = |a: f64, b: f64| (a - b).abs() <= tolerance;
+        let points_eq = |a: Point, b: Point| eq(a.x, b.x) && eq(a.y, b.y);
+        let rects_eq =
+            |a: Rect, b: Rect| eq(a.x0, b.x0) && eq(a.y0, b.y0) && eq(a.x1, b.x1) && eq(a.y1, b.y1);
+        match (self, other) {
+            (Self::Rect(a), Self::Rect(b)) => rects_eq(*a, *b),
+            (Self::RoundedRect(a), Self::RoundedRect(b)) => {
+                let (ra, rb) = (a.radii(), b.radii());
+                rects_eq(a.rect(), b.rect())
+                    && eq(ra.top_left, rb.top_left)
+                    && eq(ra.top_right, rb.top_right)
+                    && eq(ra.bottom_right, rb.bottom_right)
+                    && eq(ra.bottom_left, rb.bottom_left)
+            }
+            (Self::SmoothRoundedRect(a), Self::SmoothRoundedRect(b)) => {
+                rects_eq(a.rect, b.rect) && eq(a.radius, b.radius) && eq(a.smoothing, b.smoothing)
+            }
+            (Self::Circle(a), Self::Circle(b)) => {
+                points_eq(a.center, b.center) && eq(a.radius, b.radius)
+            }
+            (Self::Ellipse(a), Self::Ellipse(b)) => {
+                let ((ra, rot_a), (rb, rot_b)) = (a.radii_and_rotation(), b.radii_and_rotation());
+                points_eq(a.center(), b.center())
+                    && eq(ra.x, rb.x)
+                    && eq(ra.y, rb.y)
+                    && eq(rot_a, rot_b)
+            }
+            (Self::Line(a), Self::Line(b)) => points_eq(a.p0, b.p0) && points_eq(a.p1, b.p1),
+            (Self::Path(a), Self::Path(b)) => {
+                let (a, b) = (simplify_path(a, tolerance), simplify_path(b, tolerance));
+                a.elements().len() == b.elements().len()
+                    && a.iter().zip(b.iter()).all(|pair| match pair {
+                        (PathEl::MoveTo(a1), PathEl::MoveTo(b1))
+                        | (PathEl::LineTo(a1), PathEl::LineTo(b1)) => points_eq(a1, b1),
+                        (PathEl::QuadTo(a1, a2), PathEl::QuadTo(b1, b2)) => {
+                            points_eq(a1, b1) && points_eq(a2, b2)
+                        }
+                        (PathEl::CurveTo(a1, a2, a3), PathEl::CurveTo(b1, b2, b3)) => {
+                            points_eq(a1, b1) && points_eq(a2, b2) && points_eq(a3, b3)
+                        }
+                        (PathEl::ClosePath, PathEl::ClosePath) => true,
+                        _ => false,
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Removes degenerate segments and collinear points from a path.
+fn simplify_path(path: &BezPath, tolerance: f64) -> BezPath {
+    let mut simplified = BezPath::new();
+    let mut current = Point::ZERO;
+    let mut subpath_start = Point::ZERO;
+    // The start of the last element of `simplified`, if that element is a line.
+    let mut line_start = None;
+    for el in path.iter() {
+        let degenerate = |points: &[Point]| {
+            points
+                .iter()
+                .all(|point| point.distance(current) <= tolerance)
+        };
+        match el {
+            PathEl::MoveTo(p) => {
+                simplified.push(el);
+                (current, subpath_start, line_start) = (p, p, None);
+            }
+            PathEl::LineTo(p) => {
+                if degenerate(&[p]) {
+                    continue;
+                }
+                match line_start {
+                    Some(start)
+                        if Line::new(start, p).nearest(current, 0.0).distance_sq
+                            <= tolerance * tolerance =>
+                    {
+                        simplified.pop();
+                        simplified.push(PathEl::LineTo(p));
+                    }
+                    _ => {
+                        simplified.push(el);
+                        line_start = Some(current);
+                    }
+                }
+                current = p;
+            }
+            PathEl::QuadTo(p1, p2) => {
+                if !degenerate(&[p1, p2]) {
+                    simplified.push(el);
+                    (current, line_start) = (p2, None);
+                }
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                if !degenerate(&[p1, p2, p3]) {
+                    simplified.push(el);
+                    (current, line_start) = (p3, None);
+                }
+            }
+            PathEl::ClosePath => {
+                simplified.push(el);
+                (current, line_start) = (subpath_start, None);
+            }
+        }
+    }
+    simplified
+}
+
+macro_rules! impl_from_shape {
+    ($($variant:ident),*) => {
+        $(
+            impl From<$variant> for Geometry {
+                fn from(shape: $variant) -> Self {
+                    Self::$variant(shape)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_shape!(Rect, RoundedRect, SmoothRoundedRect, Circle, Ellipse, Line);
+
+impl From<BezPath> for Geometry {
+    fn from(path: BezPath) -> Self {
+        Self::Path(Arc::new(path))
+    }
+}
+
+impl From<Arc<BezPath>> for Geometry {
+    fn from(path: Arc<BezPath>) -> Self {
+        Self::Path(path)
+    }
+}
+
+/// Iterator over the path elements of a [`Geometry`].
+pub struct GeometryPathIter<'a>(PathIter<'a>);
+
+enum PathIter<'a> {
+    Rect(<Rect as Shape>::PathElementsIter<'a>),
+    RoundedRect(<RoundedRect as Shape>::PathElementsIter<'a>),
+    SmoothRoundedRect(<SmoothRoundedRect as Shape>::PathElementsIter<'a>),
+    Circle(<Circle as Shape>::PathElementsIter<'a>),
+    Ellipse(<Ellipse as Shape>::PathElementsIter<'a>),
+    Line(<Line as Shape>::PathElementsIter<'a>),
+    Path(<BezPath as Shape>::PathElementsIter<'a>),
+}
+
+impl Iterator for GeometryPathIter<'_> {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        match &mut self.0 {
+            PathIter::Rect(iter) => iter.next(),
+            PathIter::RoundedRect(iter) => iter.next(),
+            PathIter::SmoothRoundedRect(iter) => iter.next(),
+            PathIter::Circle(iter) => iter.next(),
+            PathIter::Ellipse(iter) => iter.next(),
+            PathIter::Line(iter) => iter.next(),
+            PathIter::Path(iter) => iter.next(),
+        }
+    }
+}
+
+impl core::fmt::Debug for GeometryPathIter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GeometryPathIter").finish_non_exhaustive()
+    }
+}
+
+/// Calls `$f` with the shape contained in any variant of a geometry.
+macro_rules! with_shape {
+    ($geometry:expr, $shape:ident => $f:expr) => {
+        match $geometry {
+            Geometry::Rect($shape) => $f,
+            Geometry::RoundedRect($shape) => $f,
+            Geometry::SmoothRoundedRect($shape) => $f,
+            Geometry::Circle($shape) => $f,
+            Geometry::Ellipse($shape) => $f,
+            Geometry::Line($shape) => $f,
+            Geometry::Path($shape) => {
+                let $shape: &BezPath = $shape;
+                $f
+            }
+        }
+    };
+}
+
+impl Shape for Geometry {
+    type PathElementsIter<'iter> = GeometryPathIter<'iter>;
+
+    fn path_elements(&self, tolerance: f64) -> Self::PathElementsIter<'_> {
+        GeometryPathIter(match self {
+            Self::Rect(rect) => PathIter::Rect(rect.path_elements(tolerance)),
+            Self::RoundedRect(rounded) => PathIter::RoundedRect(rounded.path_elements(tolerance)),
+            Self::SmoothRoundedRect(smooth) => {
+                PathIter::SmoothRoundedRect(smooth.path_elements(tolerance))
+            }
+            Self::Circle(circle) => PathIter::Circle(circle.path_elements(tolerance)),
+            Self::Ellipse(ellipse) => PathIter::Ellipse(ellipse.path_elements(tolerance)),
+            Self::Line(line) => PathIter::Line(line.path_elements(tolerance)),
+            Self::Path(path) => PathIter::Path(path.path_elements(tolerance)),
+        })
+    }
+
+    fn area(&self) -> f64 {
+        with_shape!(self, shape => shape.area())
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        with_shape!(self, shape => shape.perimeter(accuracy))
+    }
+
+    fn winding(&self, pt: Point) -> i32 {
+        with_shape!(self, shape => shape.winding(pt))
+    }
+
+    fn bounding_box(&self) -> Rect {
+        with_shape!(self, shape => shape.bounding_box())
+    }
+
+    fn as_line(&self) -> Option<Line> {
+        with_shape!(self, shape => shape.as_line())
+    }
+
+    fn as_rect(&self) -> Option<Rect> {
+        with_shape!(self, shape => shape.as_rect())
+    }
+
+    fn as_rounded_rect(&self) -> Option<RoundedRect> {
+        with_shape!(self, shape => shape.as_rounded_rect())
+    }
+
+    fn as_circle(&self) -> Option<Circle> {
+        with_shape!(self, shape => shape.as_circle())
+    }
+
+    fn as_path_slice(&self) -> Option<&[PathEl]> {
+        with_shape!(self, shape => shape.as_path_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmoothRoundedRect;
+    use kurbo::{Point, Rect, RoundedRect, RoundedRectRadii, Shape};
+
+    #[test]
+    fn smooth_rounded_rect() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 60.0);
+        let circular = SmoothRoundedRect::new(rect, 20.0, 0.0);
+        assert!((circular.exponent() - 2.0).abs() < 1e-3);
+        let rounded = RoundedRect::from_rect(rect, 20.0);
+        assert!((circular.area() - rounded.area()).abs() < 1.0);
+
+        // Smoothing fills in the corners, but stays within the bounds.
+        let smooth = SmoothRoundedRect::new(rect, 20.0, 1.0);
+        assert!(smooth.exponent() > 5.0);
+        assert!(smooth.area() > circular.area());
+        assert!(smooth.area() < rect.area());
+        assert!(smooth.contains(Point::new(50.0, 30.0)));
+        assert!(!smooth.contains(Point::new(1.0, 1.0)));
+        assert_eq!(smooth.bounding_box(), rect);
+
+        // The radius is clamped to half of the smaller side.
+        let pill = SmoothRoundedRect::new(rect, 1000.0, 0.5);
+        assert_eq!(pill.clamped_radius(), 30.0);
+    }
+
+    #[test]
+    fn geometry() {
+        use super::Geometry;
+        use kurbo::{BezPath, Circle, Ellipse, Line};
+
+        let rect = Rect::new(0.0, 0.0, 10.0, 20.0);
+        let path = rect.to_path(0.1);
+        let geometries = [
+            Geometry::from(rect),
+            Geometry::from(RoundedRect::from_rect(rect, 2.0)),
+            Geometry::from(SmoothRoundedRect::new(rect, 2.0, 0.5)),
+            Geometry::from(Ellipse::from_rect(rect)),
+            Geometry::from(path.clone()),
+        ];
+        for geometry in &geometries {
+            assert_eq!(geometry.bounding_box(), rect);
+            assert!(geometry.contains(Point::new(5.0, 10.0)));
+            assert_eq!(
+                geometry.path_elements(0.1).count(),
+                geometry.to_path(0.1).elements().len()
+            );
+        }
+        assert_eq!(geometries[0].area(), 200.0);
+        assert_eq!(geometries[0].as_rect(), Some(rect));
+        assert_eq!(geometries[4].as_path_slice(), Some(path.elements()));
+
+        // Shapes which report a specialized form keep it.
+        let circle = Circle::new((1.0, 2.0), 3.0);
+        assert_eq!(Geometry::from_shape(&circle, 0.1), Geometry::Circle(circle));
+        let line = Line::new((0.0, 0.0), (1.0, 1.0));
+        assert_eq!(Geometry::from_shape(&line, 0.1), Geometry::Line(line));
+        assert!(matches!(
+            Geometry::from_shape(&Ellipse::from_rect(rect), 0.1),
+            Geometry::Path(_)
+        ));
+        let mut triangle = BezPath::new();
+        triangle.move_to((0.0, 0.0));
+        triangle.line_to((1.0, 0.0));
+        triangle.line_to((0.0, 1.0));
+        triangle.close_path();
+        assert_eq!(Geometry::from(triangle.clone()).area(), triangle.area());
+    }
+
+    #[test]
+    fn clip_queries() {
+        use super::Geometry;
+        use kurbo::{Circle, Line};
+
+        let clip = Geometry::from(Rect::new(0.0, 0.0, 100.0, 100.0));
+        assert!(clip.covers(Rect::new(0.0, 0.0, 100.0, 100.0)));
+        assert!(clip.covers(Rect::new(10.0, 10.0, 20.0, 20.0)));
+        assert!(!clip.covers(Rect::new(90.0, 90.0, 110.0, 110.0)));
+        assert_eq!(
+            clip.intersect_bounds(Rect::new(90.0, 90.0, 110.0, 110.0)),
+            Some(Rect::new(90.0, 90.0, 100.0, 100.0))
+        );
+        assert_eq!(
+            clip.intersect_bounds(Rect::new(200.0, 0.0, 300.0, 10.0)),
+            None
+        );
+
+        let rounded = Geometry::from(RoundedRect::new(0.0, 0.0, 100.0, 100.0, 20.0));
+        assert!(rounded.covers(Rect::new(10.0, 10.0, 90.0, 90.0)));
+        assert!(!rounded.covers(Rect::new(0.0, 0.0, 100.0, 100.0)));
+        // Within the top left corner, but outside of its arc.
+        assert_eq!(
+            rounded.intersect_bounds(Rect::new(0.0, 0.0, 4.0, 4.0)),
+            None
+        );
+        // Overlapping the arc.
+        assert_eq!(
+            rounded.intersect_bounds(Rect::new(0.0, 0.0, 10.0, 10.0)),
+            Some(Rect::new(0.0, 0.0, 10.0, 10.0))
+        );
+        // Within the square of the bottom right corner, but inside the arc.
+        assert!(rounded
+            .intersect_bounds(Rect::new(85.0, 85.0, 90.0, 90.0))
+            .is_some());
+
+        let circle = Geometry::from(Circle::new((50.0, 50.0), 50.0));
+        assert!(circle.covers(Rect::new(40.0, 40.0, 60.0, 60.0)));
+        assert!(!circle.covers(Rect::new(0.0, 0.0, 20.0, 20.0)));
+
+        // Paths fall back to their bounds.
+        let path = Geometry::from(Rect::new(0.0, 0.0, 10.0, 10.0).to_path(0.1));
+        assert!(!path.covers(Rect::new(1.0, 1.0, 9.0, 9.0)));
+        assert_eq!(
+            path.intersect_bounds(Rect::new(5.0, 5.0, 20.0, 20.0)),
+            Some(Rect::new(5.0, 5.0, 10.0, 10.0))
+        );
+        assert!(!Geometry::from(Line::new((0.0, 0.0), (10.0, 10.0))).covers(Rect::ZERO));
+    }
+
+    #[test]
+    fn approx_eq() {
+        use super::Geometry;
+        use kurbo::BezPath;
+
+        let rect = Geometry::from(Rect::new(0.0, 0.0, 10.0, 10.0));
+        assert!(rect.approx_eq(&Geometry::from(Rect::new(0.0, 1e-9, 10.0, 10.0)), 1e-6));
+        assert!(!rect.approx_eq(&Geometry::from(Rect::new(0.0, 1.0, 10.0, 10.0)), 1e-6));
+        assert!(!rect.approx_eq(
+            &Geometry::from(RoundedRect::new(0.0, 0.0, 10.0, 10.0, 0.0)),
+            1e-6
+        ));
+
+        // A triangle with a collinear point, a degenerate line and a degenerate curve.
+        let mut noisy = BezPath::new();
+        noisy.move_to((0.0, 0.0));
+        noisy.line_to((5.0, 1e-9));
+        noisy.line_to((10.0, 0.0));
+        noisy.line_to((10.0, 1e-9));
+        noisy.curve_to((10.0, 0.0), (10.0, 0.0), (10.0, 0.0));
+        noisy.line_to((0.0, 10.0));
+        noisy.close_path();
+        let mut triangle = BezPath::new();
+        triangle.move_to((0.0, 0.0));
+        triangle.line_to((10.0, 0.0));
+        triangle.line_to((0.0, 10.0));
+        triangle.close_path();
+        let Geometry::Path(simplified) = Geometry::from(noisy.clone()).simplified(1e-6) else {
+            panic!("simplifying changed the kind of geometry");
+        };
+        assert_eq!(simplified.elements(), triangle.elements());
+        assert!(Geometry::from(noisy).approx_eq(&Geometry::from(triangle.clone()), 1e-6));
+
+        // A corner isn't collinear.
+        let mut square = triangle.clone();
+        square.truncate(2);
+        square.line_to((10.0, 10.0));
+        square.line_to((0.0, 10.0));
+        square.close_path();
+        assert!(!Geometry::from(square).approx_eq(&Geometry::from(triangle), 1e-6));
+    }
+
+    #[test]
+    fn corner_radii() {
+        use super::{CornerRadii, Geometry};
+        use kurbo::Vec2;
+
+        let rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        // Fits already, and square corners where either radius is zero.
+        let degenerate = CornerRadii::new((10.0, 5.0), (0.0, 20.0), (-1.0, 4.0), (f64::NAN, 1.0));
+        assert_eq!(
+            degenerate.normalized(rect),
+            CornerRadii::new((10.0, 5.0), Vec2::ZERO, Vec2::ZERO, Vec2::ZERO)
+        );
+
+        // The left side is 50 long, but the radii sum to 100, so everything halves.
+        let overlapping = CornerRadii::new((20.0, 60.0), (10.0, 10.0), (10.0, 10.0), (30.0, 40.0));
+        assert_eq!(
+            overlapping.normalized(rect),
+            CornerRadii::new((10.0, 30.0), (5.0, 5.0), (5.0, 5.0), (15.0, 20.0))
+        );
+
+        // Circular corners become a rounded rectangle, clamped by the CSS rules.
+        assert_eq!(
+            CornerRadii::uniform(40.0).to_geometry(rect),
+            Geometry::RoundedRect(RoundedRect::from_rect(rect, 25.0))
+        );
+        assert_eq!(
+            CornerRadii::from(RoundedRectRadii::from(5.0)),
+            CornerRadii::uniform(5.0)
+        );
+
+        // Elliptical corners become a path.
+        let square = CornerRadii::default().to_geometry(rect);
+        assert_eq!(square.bounding_box(), rect);
+        let pill = CornerRadii::new((50.0, 25.0), (50.0, 25.0), (50.0, 25.0), (50.0, 25.0))
+            .to_geometry(rect);
+        assert!(matches!(pill, Geometry::Path(_)));
+        assert_eq!(pill.bounding_box(), rect);
+        let area = core::f64::consts::PI * 50.0 * 25.0;
+        assert!((pill.area() - area).abs() < 0.01 * area);
+        assert!(!pill.contains(Point::new(2.0, 2.0)));
+        assert!(pill.contains(Point::new(50.0, 25.0)));
+    }
+}
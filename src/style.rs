@@ -1,25 +1,196 @@
 // Copyright 2022 the Peniko Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use kurbo::Stroke;
+#[cfg(feature = "kurbo")]
+use core::fmt;
+use core::ops::{BitAnd, BitOr, BitOrAssign};
+
+#[cfg(all(feature = "kurbo", not(feature = "std")))]
+use crate::floatfuncs::FloatFuncs as _;
+
+#[cfg(feature = "kurbo")]
+use kurbo::{Affine, BezPath, Cap, Join, Point, Rect, Shape, Stroke, StrokeOpts};
 
 /// Describes the rule that determines the interior portion of a shape.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Fill {
     /// Non-zero fill rule.
     NonZero = 0,
     /// Even-odd fill rule.
     EvenOdd = 1,
-    // NOTICE: If a new value is added, be sure to modify `MAX_VALUE` in the bytemuck impl.
+    // NOTICE: If a new value is added, be sure to modify `MAX_VALUE` in the bytemuck impl,
+    // `Fill::ALL` and the `u8` conversions.
+}
+
+impl_u8_conversions!(Fill {
+    NonZero = 0,
+    EvenOdd = 1,
+});
+
+impl Fill {
+    /// All fill rules, in order of their values.
+    pub const ALL: [Self; 2] = [Self::NonZero, Self::EvenOdd];
+}
+
+/// Defines the antialiasing method requested for a scene or draw operation.
+///
+/// Renderers which don't support the requested method should fall back to the
+/// closest method they do support.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum AntialiasingMode {
+    /// Analytic area coverage.
+    ///
+    /// This is the highest quality method.
+    #[default]
+    Area,
+    /// Multisampling with 8 samples per pixel.
+    Msaa8,
+    /// Multisampling with 16 samples per pixel.
+    Msaa16,
+    /// No antialiasing.
+    ///
+    /// Each pixel is either fully covered or not covered at all.
+    None,
+}
+
+impl_u8_conversions!(AntialiasingMode {
+    Area = 0,
+    Msaa8 = 1,
+    Msaa16 = 2,
+    None = 3,
+});
+
+impl AntialiasingMode {
+    /// Returns the number of samples per pixel for multisampling methods, or
+    /// `None` for other methods.
+    #[must_use]
+    pub const fn msaa_samples(self) -> Option<u32> {
+        match self {
+            Self::Msaa8 => Some(8),
+            Self::Msaa16 => Some(16),
+            Self::Area | Self::None => None,
+        }
+    }
+}
+
+/// Hints to the renderer about the trade-offs to make when drawing.
+///
+/// These are a set of flags which can be combined with `|`. All hints are optional
+/// and renderers are free to ignore any of them. The default is no hints, which asks
+/// for the renderer's normal behavior.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct RenderHints(u8);
+
+impl RenderHints {
+    /// No hints.
+    pub const NONE: Self = Self(0);
+    /// Prefer rendering speed over quality, for example by using cheaper antialiasing
+    /// or image filtering.
+    pub const PREFER_SPEED: Self = Self(1 << 0);
+    /// Allow reducing the level of detail, for example by flattening curves with a
+    /// coarser tolerance or sampling images from a lower resolution.
+    pub const ALLOW_LOD_DEGRADATION: Self = Self(1 << 1);
+    /// Snap geometry to the pixel grid to produce crisp edges.
+    pub const SNAP_TO_PIXEL: Self = Self(1 << 2);
+
+    const ALL_BITS: u8 =
+        Self::PREFER_SPEED.0 | Self::ALLOW_LOD_DEGRADATION.0 | Self::SNAP_TO_PIXEL.0;
+
+    /// Returns the raw bits of the hints.
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Creates hints from raw bits, returning `None` if any unknown bits are set.
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        if bits & !Self::ALL_BITS == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Creates hints from raw bits, ignoring any unknown bits.
+    #[must_use]
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Self(bits & Self::ALL_BITS)
+    }
+
+    /// Returns `true` if no hints are set.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all hints in `other` are set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the hints which are set in either `self` or `other`.
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the hints which are set in both `self` and `other`.
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Sets the hints in `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clears the hints in `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for RenderHints {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for RenderHints {
+    fn bitor_assign(&mut self, other: Self) {
+        self.insert(other);
+    }
+}
+
+impl BitAnd for RenderHints {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
 }
 
 /// Describes draw style-- either a [fill](Fill) or [stroke](Stroke).
 ///
 /// See also [`StyleRef`] which can be used to avoid allocations.
+///
+/// The serialized form of strokes is defined by peniko rather than by kurbo, so
+/// that it doesn't change with kurbo's serde implementation. It has the same fields
+/// as [`Stroke`], with the dash pattern as a plain array.
+#[cfg(feature = "kurbo")]
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Style {
     /// Filled draw operation.
     Fill(Fill),
@@ -27,23 +198,255 @@ pub enum Style {
     Stroke(Stroke),
 }
 
+#[cfg(feature = "kurbo")]
+impl Style {
+    /// Creates a fill style with the [non-zero](Fill::NonZero) fill rule.
+    #[must_use]
+    pub const fn fill_non_zero() -> Self {
+        Self::Fill(Fill::NonZero)
+    }
+
+    /// Creates a stroke style with the given width and the default stroke parameters.
+    ///
+    /// See [`Stroke::new`] for the defaults.
+    #[must_use]
+    pub fn stroke(width: f64) -> Self {
+        Self::Stroke(Stroke::new(width))
+    }
+
+    /// Returns `true` if this is a fill style.
+    #[must_use]
+    pub const fn is_fill(&self) -> bool {
+        matches!(self, Self::Fill(_))
+    }
+
+    /// Returns `true` if this is a stroke style.
+    #[must_use]
+    pub const fn is_stroke(&self) -> bool {
+        matches!(self, Self::Stroke(_))
+    }
+
+    /// Returns the path to fill in order to draw `shape` with this style, along with
+    /// the fill rule to use.
+    ///
+    /// See [`StyleRef::apply_to_shape`] for details.
+    #[must_use]
+    pub fn apply_to_shape(&self, shape: &impl Shape, tolerance: f64) -> (BezPath, Fill) {
+        StyleRef::from(self).apply_to_shape(shape, tolerance)
+    }
+
+    /// Returns `true` if `point` is inside the area covered by drawing `shape` with
+    /// this style.
+    ///
+    /// See [`StyleRef::hit_test`] for details.
+    #[must_use]
+    pub fn hit_test(&self, shape: &impl Shape, point: Point, tolerance: f64) -> bool {
+        StyleRef::from(self).hit_test(shape, point, tolerance)
+    }
+
+    /// Checks that the dash pattern of a stroke style is valid.
+    ///
+    /// See [`StyleRef::validate_dashes`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first problem with the dash pattern.
+    pub fn validate_dashes(&self) -> Result<(), DashPatternError> {
+        StyleRef::from(self).validate_dashes()
+    }
+
+    /// Normalizes the dash pattern of a stroke style, following the rules of SVG.
+    ///
+    /// After this, the dash pattern is either empty (a solid stroke) or has an even
+    /// number of finite, non-negative lengths with a positive sum, and the dash offset
+    /// is in the range `[0, sum)`:
+    ///
+    /// - A pattern with an odd number of lengths is repeated to make it even.
+    /// - Negative lengths are clamped to zero.
+    /// - A pattern with non-finite lengths, or with lengths that sum to zero, is removed.
+    /// - A non-finite dash offset is set to zero.
+    ///
+    /// This does nothing for fill styles.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first problem with the original dash pattern,
+    /// as reported by [`validate_dashes`](Self::validate_dashes). The pattern is
+    /// normalized regardless.
+    pub fn normalize_dashes(&mut self) -> Result<(), DashPatternError> {
+        let Self::Stroke(stroke) = self else {
+            return Ok(());
+        };
+        let result = StyleRef::Stroke(stroke).validate_dashes();
+        if !stroke.dash_offset.is_finite() {
+            stroke.dash_offset = 0.0;
+        }
+        let pattern = &mut stroke.dash_pattern;
+        if pattern.iter().any(|length| !length.is_finite()) {
+            pattern.clear();
+        }
+        for length in pattern.iter_mut() {
+            *length = length.max(0.0);
+        }
+        let sum = pattern.iter().sum::<f64>();
+        if sum <= 0.0 {
+            pattern.clear();
+            return result;
+        }
+        let period = if pattern.len() % 2 == 1 {
+            let repeat = pattern.clone();
+            pattern.extend_from_slice(&repeat);
+            2.0 * sum
+        } else {
+            sum
+        };
+        stroke.dash_offset -= period * (stroke.dash_offset / period).floor();
+        result
+    }
+
+    /// Returns conservative bounds of the area covered by drawing `shape` with this
+    /// style and `transform`.
+    ///
+    /// See [`StyleRef::bounds`] for details.
+    #[must_use]
+    pub fn bounds(&self, shape: &impl Shape, transform: Affine) -> Rect {
+        StyleRef::from(self).bounds(shape, transform)
+    }
+}
+
+/// The default style is a fill with the [non-zero](Fill::NonZero) fill rule.
+#[cfg(feature = "kurbo")]
+impl Default for Style {
+    fn default() -> Self {
+        Self::fill_non_zero()
+    }
+}
+
+#[cfg(feature = "kurbo")]
 impl From<Fill> for Style {
     fn from(fill: Fill) -> Self {
         Self::Fill(fill)
     }
 }
 
+#[cfg(feature = "kurbo")]
 impl From<Stroke> for Style {
     fn from(stroke: Stroke) -> Self {
         Self::Stroke(stroke)
     }
 }
 
+/// Serialization of [`Style`] in the format of kurbo 0.11.
+///
+/// The strokes are converted through the [`schema::v1`](crate::schema::v1) types, and
+/// the definitions here only name their variants as kurbo does.
+#[cfg(all(feature = "kurbo", feature = "serde"))]
+mod serde_impl {
+    use super::{Fill, Style};
+    use crate::schema::v1;
+
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(remote = "v1::Join", rename = "Join")]
+    enum JoinDef {
+        Bevel,
+        Miter,
+        Round,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(remote = "v1::Cap", rename = "Cap")]
+    enum CapDef {
+        Butt,
+        Square,
+        Round,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(remote = "v1::Stroke", rename = "Stroke")]
+    struct StrokeDef {
+        width: f64,
+        #[serde(with = "JoinDef")]
+        join: v1::Join,
+        miter_limit: f64,
+        #[serde(with = "CapDef")]
+        start_cap: v1::Cap,
+        #[serde(with = "CapDef")]
+        end_cap: v1::Cap,
+        dash_pattern: Vec<f64>,
+        dash_offset: f64,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename = "Style")]
+    enum StyleDef {
+        Fill(Fill),
+        Stroke(#[serde(with = "StrokeDef")] v1::Stroke),
+    }
+
+    impl serde::Serialize for Style {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            match self {
+                Self::Fill(fill) => StyleDef::Fill(*fill),
+                Self::Stroke(stroke) => StyleDef::Stroke(stroke.into()),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Style {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            Ok(match StyleDef::deserialize(deserializer)? {
+                StyleDef::Fill(fill) => Self::Fill(fill),
+                StyleDef::Stroke(stroke) => Self::Stroke(stroke.into()),
+            })
+        }
+    }
+}
+
+/// Problem with the dash pattern of a [stroke style](Style::Stroke).
+///
+/// This is returned by [`Style::validate_dashes`] and [`Style::normalize_dashes`].
+#[cfg(feature = "kurbo")]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DashPatternError {
+    /// A dash length or the dash offset is infinite or NaN.
+    NonFinite,
+    /// A dash length is negative.
+    Negative,
+    /// The dash lengths sum to zero, so the pattern has no period.
+    ZeroLength,
+}
+
+#[cfg(feature = "kurbo")]
+impl fmt::Display for DashPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NonFinite => "dash pattern contains a non-finite value",
+            Self::Negative => "dash pattern contains a negative length",
+            Self::ZeroLength => "dash pattern lengths sum to zero",
+        })
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl core::error::Error for DashPatternError {}
+
 /// Reference to a [draw style](Style).
 ///
 /// This is useful for methods that would like to accept draw styles by reference. Defining
 /// the type as `impl<Into<DrawRef>>` allows accepting types like `&Stroke` or `Fill`
 /// directly without cloning or allocating.
+#[cfg(feature = "kurbo")]
 #[expect(
     variant_size_differences,
     reason = "We don't expect this enum to be operated on in bulk."
@@ -56,6 +459,7 @@ pub enum StyleRef<'a> {
     Stroke(&'a Stroke),
 }
 
+#[cfg(feature = "kurbo")]
 impl StyleRef<'_> {
     /// Converts the reference to an owned draw.
     #[must_use]
@@ -65,20 +469,149 @@ impl StyleRef<'_> {
             Self::Stroke(stroke) => Style::Stroke((*stroke).clone()),
         }
     }
+
+    /// Returns `true` if this is a fill style.
+    #[must_use]
+    pub const fn is_fill(&self) -> bool {
+        matches!(self, Self::Fill(_))
+    }
+
+    /// Returns `true` if this is a stroke style.
+    #[must_use]
+    pub const fn is_stroke(&self) -> bool {
+        matches!(self, Self::Stroke(_))
+    }
+
+    /// Checks that the dash pattern of a stroke style is valid.
+    ///
+    /// A dash pattern is valid if it is empty, or if its lengths are all finite and
+    /// non-negative with a positive sum. The dash offset must also be finite. Fill
+    /// styles are always valid.
+    ///
+    /// Patterns with an odd number of lengths are valid, as they are repeated to make
+    /// an even number of lengths. Use [`Style::normalize_dashes`] to apply that and to
+    /// fix invalid patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first problem with the dash pattern.
+    pub fn validate_dashes(&self) -> Result<(), DashPatternError> {
+        let Self::Stroke(stroke) = self else {
+            return Ok(());
+        };
+        let pattern = &stroke.dash_pattern;
+        if pattern.is_empty() {
+            return Ok(());
+        }
+        if !stroke.dash_offset.is_finite() || pattern.iter().any(|length| !length.is_finite()) {
+            return Err(DashPatternError::NonFinite);
+        }
+        if pattern.iter().any(|length| *length < 0.0) {
+            return Err(DashPatternError::Negative);
+        }
+        if pattern.iter().sum::<f64>() <= 0.0 {
+            return Err(DashPatternError::ZeroLength);
+        }
+        Ok(())
+    }
+
+    /// Returns the path to fill in order to draw `shape` with this style, along with
+    /// the fill rule to use.
+    ///
+    /// For a fill, this is the path of the shape and the fill rule of the style. For a
+    /// stroke, this is the outline of the stroke computed with [`kurbo::stroke`], which
+    /// is filled with the [non-zero](Fill::NonZero) rule.
+    ///
+    /// This is a single entry point for consumers which only handle fills, such as CPU
+    /// rasterizers and hit testers. The `tolerance` is used both for converting the shape
+    /// to a path and for approximating the stroke outline.
+    #[must_use]
+    pub fn apply_to_shape(&self, shape: &impl Shape, tolerance: f64) -> (BezPath, Fill) {
+        match self {
+            Self::Fill(fill) => (shape.to_path(tolerance), *fill),
+            Self::Stroke(stroke) => (
+                kurbo::stroke(
+                    shape.path_elements(tolerance),
+                    stroke,
+                    &StrokeOpts::default(),
+                    tolerance,
+                ),
+                Fill::NonZero,
+            ),
+        }
+    }
+
+    /// Returns `true` if `point` is inside the area covered by drawing `shape` with
+    /// this style.
+    ///
+    /// Fills respect the fill rule. Strokes respect the width, joins, caps and dashes
+    /// of the stroke, by testing against the outline from
+    /// [`apply_to_shape`](Self::apply_to_shape), so the result matches what is drawn
+    /// up to `tolerance`. Antialiasing is not taken into account.
+    #[must_use]
+    pub fn hit_test(&self, shape: &impl Shape, point: Point, tolerance: f64) -> bool {
+        let winding = match self {
+            Self::Fill(_) => shape.winding(point),
+            Self::Stroke(_) => {
+                let (outline, _) = self.apply_to_shape(shape, tolerance);
+                if !outline.bounding_box().contains(point) {
+                    return false;
+                }
+                outline.winding(point)
+            }
+        };
+        match self {
+            Self::Fill(Fill::EvenOdd) => winding % 2 != 0,
+            _ => winding != 0,
+        }
+    }
+
+    /// Returns conservative bounds of the area covered by drawing `shape` with this
+    /// style and `transform`, in the coordinate space after the transform.
+    ///
+    /// For strokes, the bounding box of the shape is expanded by the furthest distance
+    /// that the stroke can extend from the path, which accounts for the stroke width,
+    /// miter joins and square caps. Dashes can only remove parts of a stroke, so they
+    /// are ignored. The stroke is expanded before the transform is applied, as it is
+    /// when drawing.
+    ///
+    /// The bounds are suitable for culling, but may be larger than the exact bounds.
+    #[must_use]
+    pub fn bounds(&self, shape: &impl Shape, transform: Affine) -> Rect {
+        let bounds = shape.bounding_box();
+        let bounds = match self {
+            Self::Fill(_) => bounds,
+            Self::Stroke(stroke) => {
+                let half_width = 0.5 * stroke.width.abs();
+                let mut scale: f64 = 1.0;
+                if stroke.join == Join::Miter {
+                    scale = scale.max(stroke.miter_limit);
+                }
+                if stroke.start_cap == Cap::Square || stroke.end_cap == Cap::Square {
+                    scale = scale.max(core::f64::consts::SQRT_2);
+                }
+                bounds.inflate(half_width * scale, half_width * scale)
+            }
+        };
+        transform.transform_rect_bbox(bounds)
+    }
 }
 
+#[cfg(feature = "kurbo")]
 impl From<Fill> for StyleRef<'_> {
     fn from(fill: Fill) -> Self {
         Self::Fill(fill)
     }
 }
 
+#[cfg(feature = "kurbo")]
 impl<'a> From<&'a Stroke> for StyleRef<'a> {
     fn from(stroke: &'a Stroke) -> Self {
         Self::Stroke(stroke)
     }
 }
 
+#[cfg(feature = "kurbo")]
 impl<'a> From<&'a Style> for StyleRef<'a> {
     fn from(draw: &'a Style) -> Self {
         match draw {
@@ -87,3 +620,205 @@ impl<'a> From<&'a Style> for StyleRef<'a> {
         }
     }
 }
+
+#[cfg(all(test, feature = "kurbo"))]
+mod tests {
+    use super::{DashPatternError, Fill, RenderHints, Style, StyleRef};
+    use kurbo::{Affine, Join, Point, Rect, Shape, Stroke};
+
+    #[test]
+    fn render_hints() {
+        let speed_snap = RenderHints::PREFER_SPEED | RenderHints::SNAP_TO_PIXEL;
+        assert_eq!(speed_snap.bits(), 0b101);
+        assert_eq!(RenderHints::from_bits(0b101), Some(speed_snap));
+        // Unknown bits are rejected or masked.
+        assert_eq!(RenderHints::from_bits(0b1000_0101), None);
+        assert_eq!(RenderHints::from_bits_truncate(0b1000_0101), speed_snap);
+        assert_eq!(RenderHints::from_bits_truncate(0xf8), RenderHints::NONE);
+
+        assert!(speed_snap.contains(RenderHints::PREFER_SPEED));
+        assert!(speed_snap.contains(RenderHints::NONE));
+        assert!(!speed_snap.contains(RenderHints::ALLOW_LOD_DEGRADATION));
+        assert!(
+            !speed_snap.contains(RenderHints::SNAP_TO_PIXEL | RenderHints::ALLOW_LOD_DEGRADATION)
+        );
+        assert_eq!(
+            speed_snap.union(RenderHints::ALLOW_LOD_DEGRADATION).bits(),
+            0b111
+        );
+        assert_eq!(
+            speed_snap
+                .intersection(RenderHints::SNAP_TO_PIXEL | RenderHints::ALLOW_LOD_DEGRADATION),
+            RenderHints::SNAP_TO_PIXEL
+        );
+        assert_eq!(
+            speed_snap & RenderHints::PREFER_SPEED,
+            RenderHints::PREFER_SPEED
+        );
+        assert!(RenderHints::default().is_empty());
+
+        let mut hints = RenderHints::NONE;
+        hints.insert(RenderHints::ALLOW_LOD_DEGRADATION);
+        hints |= RenderHints::PREFER_SPEED;
+        assert_eq!(hints.bits(), 0b011);
+        hints.remove(RenderHints::ALLOW_LOD_DEGRADATION);
+        assert_eq!(hints, RenderHints::PREFER_SPEED);
+        // Removing hints which aren't set has no effect.
+        hints.remove(RenderHints::SNAP_TO_PIXEL);
+        assert_eq!(hints, RenderHints::PREFER_SPEED);
+        hints.remove(RenderHints::PREFER_SPEED);
+        assert!(hints.is_empty());
+    }
+
+    #[test]
+    fn dash_patterns() {
+        let mut odd = Style::Stroke(Stroke::new(1.0).with_dashes(-1.0, [1.0, 2.0, 3.0]));
+        assert_eq!(odd.validate_dashes(), Ok(()));
+        assert_eq!(odd.normalize_dashes(), Ok(()));
+        let Style::Stroke(ref stroke) = odd else {
+            panic!("expected a stroke");
+        };
+        assert_eq!(
+            stroke.dash_pattern.as_slice(),
+            [1.0, 2.0, 3.0, 1.0, 2.0, 3.0]
+        );
+        assert_eq!(stroke.dash_offset, 11.0);
+
+        let mut negative = Style::Stroke(Stroke::new(1.0).with_dashes(0.0, [4.0, -2.0]));
+        assert_eq!(negative.normalize_dashes(), Err(DashPatternError::Negative));
+        let Style::Stroke(ref clamped) = negative else {
+            panic!("expected a stroke");
+        };
+        assert_eq!(clamped.dash_pattern.as_slice(), [4.0, 0.0]);
+
+        let mut zero = Style::Stroke(Stroke::new(1.0).with_dashes(0.0, [0.0, 0.0]));
+        assert_eq!(zero.validate_dashes(), Err(DashPatternError::ZeroLength));
+        assert_eq!(zero.normalize_dashes(), Err(DashPatternError::ZeroLength));
+        assert_eq!(zero.validate_dashes(), Ok(()));
+
+        let mut infinite = Style::Stroke(Stroke::new(1.0).with_dashes(0.0, [f64::INFINITY]));
+        assert_eq!(
+            infinite.normalize_dashes(),
+            Err(DashPatternError::NonFinite)
+        );
+        let Style::Stroke(ref solid) = infinite else {
+            panic!("expected a stroke");
+        };
+        assert!(solid.dash_pattern.is_empty());
+        assert_eq!(Style::default().normalize_dashes(), Ok(()));
+    }
+
+    #[test]
+    fn constructors() {
+        let fill = Style::default();
+        assert!(matches!(fill, Style::Fill(Fill::NonZero)));
+        assert!(fill.is_fill() && !fill.is_stroke());
+        let stroke = Style::stroke(2.0);
+        let Style::Stroke(ref inner) = stroke else {
+            panic!("expected a stroke");
+        };
+        assert_eq!(inner.width, 2.0);
+        assert!(stroke.is_stroke() && StyleRef::from(&stroke).is_stroke());
+        assert!(StyleRef::from(&Style::fill_non_zero()).is_fill());
+    }
+
+    #[test]
+    fn apply_to_shape() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let (path, fill) = Style::Fill(Fill::EvenOdd).apply_to_shape(&rect, 0.1);
+        assert_eq!(fill, Fill::EvenOdd);
+        assert_eq!(path, rect.to_path(0.1));
+
+        let (outline, stroke_fill) = Style::Stroke(Stroke::new(2.0)).apply_to_shape(&rect, 0.1);
+        assert_eq!(stroke_fill, Fill::NonZero);
+        assert_ne!(outline.winding(Point::new(10.5, 5.0)), 0);
+        assert_eq!(outline.winding(Point::new(5.0, 5.0)), 0);
+        assert_eq!(outline.winding(Point::new(11.5, 5.0)), 0);
+    }
+
+    #[test]
+    fn bounds() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let transform = Affine::translate((5.0, 0.0)) * Affine::scale(2.0);
+        assert_eq!(
+            Style::Fill(Fill::NonZero).bounds(&rect, transform),
+            Rect::new(5.0, 0.0, 25.0, 20.0)
+        );
+        let round = Style::Stroke(Stroke::new(2.0));
+        assert_eq!(
+            round.bounds(&rect, Affine::IDENTITY),
+            Rect::new(-1.0, -1.0, 11.0, 11.0)
+        );
+        let miter = Style::Stroke(
+            Stroke::new(2.0)
+                .with_join(Join::Miter)
+                .with_miter_limit(4.0),
+        );
+        assert_eq!(
+            miter.bounds(&rect, Affine::IDENTITY),
+            Rect::new(-4.0, -4.0, 14.0, 14.0)
+        );
+    }
+
+    #[test]
+    fn hit_test() {
+        // Two overlapping squares with the same orientation.
+        let mut path = Rect::new(0.0, 0.0, 10.0, 10.0).to_path(0.1);
+        path.extend(Rect::new(5.0, 5.0, 15.0, 15.0).path_elements(0.1));
+        let overlap = Point::new(7.0, 7.0);
+        assert!(Style::Fill(Fill::NonZero).hit_test(&path, overlap, 0.1));
+        assert!(!Style::Fill(Fill::EvenOdd).hit_test(&path, overlap, 0.1));
+
+        let line = kurbo::Line::new((0.0, 0.0), (10.0, 0.0));
+        let butt = Style::Stroke(Stroke::new(4.0).with_caps(kurbo::Cap::Butt));
+        assert!(butt.hit_test(&line, Point::new(5.0, 1.5), 0.1));
+        assert!(!butt.hit_test(&line, Point::new(5.0, 2.5), 0.1));
+        assert!(!butt.hit_test(&line, Point::new(11.0, 0.0), 0.1));
+        let square = Style::Stroke(Stroke::new(4.0).with_caps(kurbo::Cap::Square));
+        assert!(square.hit_test(&line, Point::new(11.0, 0.0), 0.1));
+        let dashed = Style::Stroke(
+            Stroke::new(4.0)
+                .with_caps(kurbo::Cap::Butt)
+                .with_dashes(0.0, [2.0, 2.0]),
+        );
+        assert!(dashed.hit_test(&line, Point::new(1.0, 0.0), 0.1));
+        assert!(!dashed.hit_test(&line, Point::new(3.0, 0.0), 0.1));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn stroke_serde() {
+        use kurbo::Cap;
+
+        let stroke = Stroke::new(2.0)
+            .with_join(Join::Round)
+            .with_end_cap(Cap::Square)
+            .with_dashes(0.5, [4.0, 2.0]);
+        let json = serde_json::to_value(Style::from(stroke.clone())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "Stroke": {
+                    "width": 2.0,
+                    "join": "Round",
+                    "miter_limit": 4.0,
+                    "start_cap": "Round",
+                    "end_cap": "Square",
+                    "dash_pattern": [4.0, 2.0],
+                    "dash_offset": 0.5,
+                }
+            })
+        );
+        // This matches the format of kurbo 0.11, so existing documents still load.
+        assert_eq!(json["Stroke"], serde_json::to_value(&stroke).unwrap());
+        let Style::Stroke(round_trip) = serde_json::from_value(json).unwrap() else {
+            panic!("expected a stroke style");
+        };
+        assert_eq!(round_trip.dash_pattern, stroke.dash_pattern);
+        assert_eq!(round_trip.end_cap, Cap::Square);
+
+        let fill = serde_json::to_value(Style::from(Fill::EvenOdd)).unwrap();
+        assert_eq!(fill, serde_json::json!({ "Fill": "EvenOdd" }));
+        assert!(serde_json::from_value::<Style>(fill).unwrap().is_fill());
+    }
+}
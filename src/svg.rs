@@ -0,0 +1,263 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Conversion of gradients to SVG paint servers.
+
+use core::fmt::{self, Write};
+
+extern crate alloc;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use color::{ColorSpaceTag, Srgb};
+
+use crate::{Extend, Gradient, GradientKind};
+
+/// Errors which can occur when converting a [`Gradient`] to an [`SvgGradient`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SvgGradientError {
+    /// The gradient is a sweep gradient, which has no equivalent SVG element.
+    UnsupportedKind,
+    /// The extend mode has no equivalent `spreadMethod`.
+    UnsupportedExtend(Extend),
+}
+
+impl fmt::Display for SvgGradientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedKind => f.write_str("sweep gradients have no SVG equivalent"),
+            Self::UnsupportedExtend(extend) => {
+                write!(f, "extend mode {extend:?} has no SVG spread method")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SvgGradientError {}
+
+/// The element of an [`SvgGradient`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SvgGradientElement {
+    /// A `<linearGradient>` element.
+    Linear,
+    /// A `<radialGradient>` element.
+    Radial,
+}
+
+impl SvgGradientElement {
+    /// Returns the name of the element, such as `"linearGradient"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Linear => "linearGradient",
+            Self::Radial => "radialGradient",
+        }
+    }
+}
+
+/// A `<stop>` element of an [`SvgGradient`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct SvgStop {
+    /// The `offset` attribute, between `0` and `1`.
+    pub offset: f32,
+    /// The `stop-color` attribute, as a hex color such as `"#ff0000"`.
+    pub color: String,
+    /// The `stop-opacity` attribute.
+    pub opacity: f32,
+}
+
+/// The attributes and stops of an SVG paint server element for a [`Gradient`].
+///
+/// This can be used as structured data by SVG writers, or written as markup with
+/// [`write_element`](Self::write_element).
+#[derive(Clone, PartialEq, Debug)]
+pub struct SvgGradient {
+    /// The gradient element.
+    pub element: SvgGradientElement,
+    /// The attributes of the element, apart from `id`, in the order they are written.
+    pub attributes: Vec<(&'static str, String)>,
+    /// The stops of the gradient.
+    pub stops: Vec<SvgStop>,
+}
+
+impl SvgGradient {
+    /// Writes the gradient as an SVG element with the given `id`, including its stops.
+    ///
+    /// The `id` is written as given, so it must not need escaping. `stop-opacity` is
+    /// omitted for opaque stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    pub fn write_element(&self, w: &mut impl Write, id: &str) -> fmt::Result {
+        let name = self.element.name();
+        write!(w, "<{name} id=\"{id}\"")?;
+        for (attribute, value) in &self.attributes {
+            write!(w, " {attribute}=\"{value}\"")?;
+        }
+        w.write_char('>')?;
+        for stop in &self.stops {
+            write!(
+                w,
+                "<stop offset=\"{}\" stop-color=\"{}\"",
+                stop.offset, stop.color
+            )?;
+            if stop.opacity != 1.0 {
+                write!(w, " stop-opacity=\"{}\"", stop.opacity)?;
+            }
+            w.write_str("/>")?;
+        }
+        write!(w, "</{name}>")
+    }
+}
+
+impl Gradient {
+    /// Returns the attributes and stops of the equivalent SVG paint server element.
+    ///
+    /// Coordinates are in user space, with `gradientUnits="userSpaceOnUse"`. The
+    /// start circle of a radial gradient is the focal circle, given by `fx`, `fy`
+    /// and (when it isn't zero) `fr`, and the end circle is given by `cx`, `cy` and
+    /// `r`. The extend mode is the `spreadMethod`.
+    ///
+    /// Stops are [normalized](crate::ColorStops::normalize), and their colors are
+    /// converted to sRGB with 8 bits per channel. SVG interpolates in sRGB, or in
+    /// linear sRGB with `color-interpolation="linearRGB"`, which is used for an
+    /// [interpolation color space](Self::interpolation_cs) of
+    /// [linear sRGB](ColorSpaceTag::LinearSrgb). Other color spaces and
+    /// [interpolation hints](crate::ColorStop::hint) aren't represented; they can be
+    /// approximated by inserting stops first, such as with
+    /// [`ColorStops::insert_perceptual_midpoints`](crate::ColorStops::insert_perceptual_midpoints).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for sweep gradients and for [`Extend::Border`]. See
+    /// [`SvgGradientError`].
+    pub fn to_svg(&self) -> Result<SvgGradient, SvgGradientError> {
+        let (element, mut attributes) = match self.kind {
+            GradientKind::Linear { start, end } => (
+                SvgGradientElement::Linear,
+                alloc::vec![
+                    ("x1", start.x.to_string()),
+                    ("y1", start.y.to_string()),
+                    ("x2", end.x.to_string()),
+                    ("y2", end.y.to_string()),
+                ],
+            ),
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => {
+                let mut attributes = alloc::vec![
+                    ("cx", end_center.x.to_string()),
+                    ("cy", end_center.y.to_string()),
+                    ("r", end_radius.to_string()),
+                    ("fx", start_center.x.to_string()),
+                    ("fy", start_center.y.to_string()),
+                ];
+                if start_radius != 0.0 {
+                    attributes.push(("fr", start_radius.to_string()));
+                }
+                (SvgGradientElement::Radial, attributes)
+            }
+            GradientKind::Sweep { .. } => return Err(SvgGradientError::UnsupportedKind),
+        };
+        let spread_method = match self.extend {
+            Extend::Pad => "pad",
+            Extend::Repeat => "repeat",
+            Extend::Reflect => "reflect",
+            Extend::Border => return Err(SvgGradientError::UnsupportedExtend(Extend::Border)),
+        };
+        attributes.push(("gradientUnits", "userSpaceOnUse".into()));
+        attributes.push(("spreadMethod", spread_method.into()));
+        if self.interpolation_cs == ColorSpaceTag::LinearSrgb {
+            attributes.push(("color-interpolation", "linearRGB".into()));
+        }
+        let mut stops = self.stops.clone();
+        stops.normalize();
+        let stops = stops
+            .iter()
+            .map(|stop| {
+                let color = stop.color.to_alpha_color::<Srgb>();
+                let [r, g, b, _] = color.to_rgba8().to_u8_array();
+                SvgStop {
+                    offset: stop.offset,
+                    color: format!("#{r:02x}{g:02x}{b:02x}"),
+                    opacity: color.components[3],
+                }
+            })
+            .collect();
+        Ok(SvgGradient {
+            element,
+            attributes,
+            stops,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SvgGradientElement, SvgGradientError};
+    use crate::{Extend, Gradient};
+    use color::{palette::css, ColorSpaceTag};
+
+    #[test]
+    fn linear_svg() {
+        let gradient = Gradient::new_linear((0.0, 0.5), (10.0, 0.5))
+            .with_extend(Extend::Reflect)
+            .with_interpolation_cs(ColorSpaceTag::LinearSrgb)
+            .with_stops([css::RED, css::BLUE.with_alpha(0.5)]);
+        let svg = gradient.to_svg().unwrap();
+        assert_eq!(svg.element, SvgGradientElement::Linear);
+        let mut markup = String::new();
+        svg.write_element(&mut markup, "g1").unwrap();
+        assert_eq!(
+            markup,
+            "<linearGradient id=\"g1\" x1=\"0\" y1=\"0.5\" x2=\"10\" y2=\"0.5\" \
+             gradientUnits=\"userSpaceOnUse\" spreadMethod=\"reflect\" \
+             color-interpolation=\"linearRGB\">\
+             <stop offset=\"0\" stop-color=\"#ff0000\"/>\
+             <stop offset=\"1\" stop-color=\"#0000ff\" stop-opacity=\"0.5\"/>\
+             </linearGradient>"
+        );
+    }
+
+    #[test]
+    fn radial_svg() {
+        let gradient = Gradient::new_two_point_radial((1.0, 2.0), 0.5, (3.0, 4.0), 5.0)
+            .with_stops([css::WHITE, css::BLACK]);
+        let svg = gradient.to_svg().unwrap();
+        assert_eq!(svg.element.name(), "radialGradient");
+        let attributes: Vec<_> = svg
+            .attributes
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+        assert_eq!(
+            attributes,
+            [
+                ("cx", "3"),
+                ("cy", "4"),
+                ("r", "5"),
+                ("fx", "1"),
+                ("fy", "2"),
+                ("fr", "0.5"),
+                ("gradientUnits", "userSpaceOnUse"),
+                ("spreadMethod", "pad"),
+            ]
+        );
+        assert_eq!(svg.stops[1].color, "#000000");
+
+        assert_eq!(
+            Gradient::new_sweep((0.0, 0.0), 0.0, 1.0).to_svg(),
+            Err(SvgGradientError::UnsupportedKind)
+        );
+        assert_eq!(
+            gradient.with_extend(Extend::Border).to_svg(),
+            Err(SvgGradientError::UnsupportedExtend(Extend::Border))
+        );
+    }
+}
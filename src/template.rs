@@ -0,0 +1,298 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::fmt;
+
+extern crate alloc;
+use alloc::sync::Arc;
+#[cfg(feature = "gradient")]
+use alloc::vec::Vec;
+
+use color::{AlphaColor, ColorSpace, DynamicColor};
+
+use crate::Brush;
+#[cfg(feature = "gradient")]
+use crate::{ColorStop, ColorStops, Gradient};
+
+/// A symbolic color which is resolved by a [`Palette`] when drawing.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ColorSlot {
+    /// The current color, as with the CSS and SVG `currentColor` keyword.
+    Current,
+    /// A named color, such as a design token of a theme.
+    Named(Arc<str>),
+}
+
+impl ColorSlot {
+    /// Creates a slot for the named color.
+    #[must_use]
+    pub fn named(name: &str) -> Self {
+        Self::Named(name.into())
+    }
+}
+
+impl fmt::Display for ColorSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Current => f.write_str("currentColor"),
+            Self::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Source of the colors for [color slots](ColorSlot).
+///
+/// This is implemented for closures taking a slot, so a palette can be a lookup in
+/// any collection of colors.
+pub trait Palette {
+    /// Returns the color for the slot, or `None` if the palette doesn't have one.
+    fn color(&self, slot: &ColorSlot) -> Option<DynamicColor>;
+}
+
+impl<F> Palette for F
+where
+    F: Fn(&ColorSlot) -> Option<DynamicColor>,
+{
+    fn color(&self, slot: &ColorSlot) -> Option<DynamicColor> {
+        self(slot)
+    }
+}
+
+/// Error returned when resolving a template with a [`Palette`] which is missing a
+/// color.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// The palette has no color for the slot.
+    MissingColor(ColorSlot),
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColor(slot) => write!(f, "palette has no color for `{slot}`"),
+        }
+    }
+}
+
+impl core::error::Error for ResolveError {}
+
+/// A color which is either known, or given by a [slot](ColorSlot) of a palette.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TemplateColor {
+    /// A known color.
+    Color(DynamicColor),
+    /// A color resolved from a palette.
+    Slot(ColorSlot),
+}
+
+impl TemplateColor {
+    /// Returns the color, looking it up in the palette if it is a slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResolveError::MissingColor`] if the palette doesn't have the color.
+    pub fn resolve(&self, palette: &impl Palette) -> Result<DynamicColor, ResolveError> {
+        match self {
+            Self::Color(color) => Ok(*color),
+            Self::Slot(slot) => palette
+                .color(slot)
+                .ok_or_else(|| ResolveError::MissingColor(slot.clone())),
+        }
+    }
+}
+
+impl<CS: ColorSpace> From<AlphaColor<CS>> for TemplateColor {
+    fn from(color: AlphaColor<CS>) -> Self {
+        Self::Color(DynamicColor::from_alpha_color(color))
+    }
+}
+
+impl From<DynamicColor> for TemplateColor {
+    fn from(color: DynamicColor) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<ColorSlot> for TemplateColor {
+    fn from(slot: ColorSlot) -> Self {
+        Self::Slot(slot)
+    }
+}
+
+/// A gradient stop whose color can be given by a [slot](ColorSlot).
+#[cfg(feature = "gradient")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TemplateStop {
+    /// Normalized offset of the stop.
+    pub offset: f32,
+    /// Color at the specified offset.
+    pub color: TemplateColor,
+    /// Offset of the [interpolation hint](ColorStop::hint) between this stop and the
+    /// next one.
+    pub hint: Option<f32>,
+}
+
+#[cfg(feature = "gradient")]
+impl<C: Into<TemplateColor>> From<(f32, C)> for TemplateStop {
+    fn from((offset, color): (f32, C)) -> Self {
+        Self {
+            offset,
+            color: color.into(),
+            hint: None,
+        }
+    }
+}
+
+/// A brush whose colors can refer to [slots](ColorSlot) of a palette, such as the
+/// current color or the colors of a theme.
+///
+/// This defers choosing the colors until drawing, when the template is
+/// [resolved](Self::resolve) into a [`Brush`] with a [`Palette`], so the same
+/// template can be drawn with different current colors or themes.
+#[derive(Clone, PartialEq, Debug)]
+pub enum BrushTemplate {
+    /// A brush without any slots.
+    Brush(Brush),
+    /// A solid color brush.
+    Solid(TemplateColor),
+    /// A gradient brush.
+    #[cfg(feature = "gradient")]
+    Gradient {
+        /// The gradient, whose stops are replaced by `stops` when resolving.
+        gradient: Gradient,
+        /// The stops of the gradient.
+        stops: Vec<TemplateStop>,
+    },
+}
+
+impl BrushTemplate {
+    /// Creates a template for a solid brush of the current color.
+    #[must_use]
+    pub fn current_color() -> Self {
+        Self::Solid(TemplateColor::Slot(ColorSlot::Current))
+    }
+
+    /// Returns `true` if the template refers to any slot.
+    #[must_use]
+    pub fn has_slots(&self) -> bool {
+        match self {
+            Self::Brush(_) => false,
+            Self::Solid(color) => matches!(color, TemplateColor::Slot(_)),
+            #[cfg(feature = "gradient")]
+            Self::Gradient { stops, .. } => stops
+                .iter()
+                .any(|stop| matches!(stop.color, TemplateColor::Slot(_))),
+        }
+    }
+
+    /// Returns the brush, with the colors of slots looked up in the palette.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResolveError::MissingColor`] if the palette doesn't have a color
+    /// used by the template.
+    pub fn resolve(&self, palette: &impl Palette) -> Result<Brush, ResolveError> {
+        match self {
+            Self::Brush(brush) => Ok(brush.clone()),
+            Self::Solid(color) => Ok(Brush::DynamicSolid(color.resolve(palette)?)),
+            #[cfg(feature = "gradient")]
+            Self::Gradient { gradient, stops } => {
+                let stops = stops
+                    .iter()
+                    .map(|stop| {
+                        Ok(ColorStop {
+                            offset: stop.offset,
+                            color: stop.color.resolve(palette)?,
+                            hint: stop.hint,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Brush::Gradient(Gradient {
+                    stops: ColorStops::from(stops),
+                    ..gradient.clone()
+                }))
+            }
+        }
+    }
+}
+
+impl From<Brush> for BrushTemplate {
+    fn from(brush: Brush) -> Self {
+        Self::Brush(brush)
+    }
+}
+
+impl From<TemplateColor> for BrushTemplate {
+    fn from(color: TemplateColor) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<ColorSlot> for BrushTemplate {
+    fn from(slot: ColorSlot) -> Self {
+        Self::Solid(slot.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BrushTemplate, ColorSlot, ResolveError};
+    use crate::{Brush, Color};
+    use color::{palette::css, DynamicColor};
+
+    fn palette(slot: &ColorSlot) -> Option<DynamicColor> {
+        match slot {
+            ColorSlot::Current => Some(DynamicColor::from_alpha_color(css::RED)),
+            ColorSlot::Named(name) if &**name == "accent" => {
+                Some(DynamicColor::from_alpha_color(css::BLUE))
+            }
+            ColorSlot::Named(_) => None,
+        }
+    }
+
+    #[test]
+    fn solid() {
+        let brush = BrushTemplate::current_color().resolve(&palette).unwrap();
+        assert_eq!(
+            brush,
+            Brush::DynamicSolid(DynamicColor::from_alpha_color(css::RED))
+        );
+
+        let fixed = BrushTemplate::from(Brush::from(Color::WHITE));
+        assert!(!fixed.has_slots());
+        assert_eq!(fixed.resolve(&palette).unwrap(), Brush::from(Color::WHITE));
+
+        let missing = BrushTemplate::from(ColorSlot::named("warning"));
+        assert_eq!(
+            missing.resolve(&palette),
+            Err(ResolveError::MissingColor(ColorSlot::named("warning")))
+        );
+        assert_eq!(
+            missing.resolve(&palette).unwrap_err().to_string(),
+            "palette has no color for `warning`"
+        );
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn gradient() {
+        use super::TemplateStop;
+        use crate::Gradient;
+
+        let template = BrushTemplate::Gradient {
+            gradient: Gradient::new_linear((0.0, 0.0), (1.0, 0.0)),
+            stops: vec![
+                TemplateStop::from((0.0, ColorSlot::Current)),
+                TemplateStop::from((1.0, ColorSlot::named("accent"))),
+            ],
+        };
+        assert!(template.has_slots());
+        let Brush::Gradient(gradient) = template.resolve(&palette).unwrap() else {
+            panic!("expected a gradient");
+        };
+        let expected =
+            Gradient::new_linear((0.0, 0.0), (1.0, 0.0)).with_stops([css::RED, css::BLUE]);
+        assert_eq!(gradient, expected);
+    }
+}
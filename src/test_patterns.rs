@@ -0,0 +1,144 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Deterministic images for renderer tests.
+
+use super::{Blob, Color, Image, ImageFormat};
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use color::palette::css;
+
+impl Image {
+    /// Creates a checkerboard image, with square cells of `cell_size` pixels.
+    ///
+    /// The top left cell is `first`, and cells alternate between `first` and `second`
+    /// in both directions. Partial cells are used at the right and bottom edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` is zero, or if the size of the image in bytes overflows
+    /// `usize`.
+    #[must_use]
+    pub fn checkerboard(
+        width: u32,
+        height: u32,
+        cell_size: u32,
+        first: Color,
+        second: Color,
+    ) -> Self {
+        assert!(cell_size > 0, "checkerboard cells must not be empty");
+        let colors = [
+            first.to_rgba8().to_u8_array(),
+            second.to_rgba8().to_u8_array(),
+        ];
+        Self::from_fn(width, height, |x, y| {
+            colors[((x / cell_size + y / cell_size) % 2) as usize]
+        })
+    }
+
+    /// Creates an image with a horizontal ramp from `start` at the left edge to `end`
+    /// at the right edge.
+    ///
+    /// Each channel is interpolated linearly in 8-bit sRGB with separate alpha, and
+    /// rounded to the nearest value, so the pixels are identical on every platform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the image in bytes overflows `usize`.
+    #[must_use]
+    pub fn ramp(width: u32, height: u32, start: Color, end: Color) -> Self {
+        let start = start.to_rgba8().to_u8_array();
+        let end = end.to_rgba8().to_u8_array();
+        let last = u64::from(width.saturating_sub(1).max(1));
+        Self::from_fn(width, height, |x, _| {
+            core::array::from_fn(|i| {
+                let (a, b) = (u64::from(start[i]), u64::from(end[i]));
+                let x = u64::from(x);
+                let value = (a * (last - x) + b * x + last / 2) / last;
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    reason = "The value is between two u8 values."
+                )]
+                let value = value as u8;
+                value
+            })
+        })
+    }
+
+    /// Creates an image of eight vertical color bars of equal width.
+    ///
+    /// From left to right, the bars are white, yellow, cyan, green, magenta, red, blue
+    /// and black, as in classic television test cards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the image in bytes overflows `usize`.
+    #[must_use]
+    pub fn color_bars(width: u32, height: u32) -> Self {
+        let bars = [
+            css::WHITE,
+            css::YELLOW,
+            css::AQUA,
+            css::LIME,
+            css::FUCHSIA,
+            css::RED,
+            css::BLUE,
+            css::BLACK,
+        ]
+        .map(|color| color.to_rgba8().to_u8_array());
+        let total_width = u64::from(width.max(1));
+        Self::from_fn(width, height, |x, _| {
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "The index is less than the number of bars."
+            )]
+            let index = (u64::from(x) * 8 / total_width) as usize;
+            bars[index]
+        })
+    }
+
+    /// Creates an RGBA8 image with each pixel produced by `pixel(x, y)`.
+    fn from_fn(width: u32, height: u32, mut pixel: impl FnMut(u32, u32) -> [u8; 4]) -> Self {
+        let format = ImageFormat::Rgba8;
+        let size = format
+            .size_in_bytes(width, height)
+            .expect("image size overflows usize");
+        let mut data = Vec::with_capacity(size);
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&pixel(x, y));
+            }
+        }
+        Self::new(Blob::from(data), format, width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Image;
+    use color::palette::css;
+
+    #[test]
+    fn test_patterns() {
+        let board = Image::checkerboard(5, 4, 2, css::WHITE, css::BLACK);
+        let board = board.as_data_ref();
+        assert_eq!(board.pixel(0, 0), Some([255; 4]));
+        assert_eq!(board.pixel(2, 0), Some([0, 0, 0, 255]));
+        assert_eq!(board.pixel(2, 2), Some([255; 4]));
+        assert_eq!(board.pixel(4, 3), Some([0, 0, 0, 255]));
+
+        let ramp = Image::ramp(3, 1, css::BLACK, css::WHITE);
+        assert_eq!(
+            ramp.data.data(),
+            [0, 0, 0, 255, 128, 128, 128, 255, 255, 255, 255, 255]
+        );
+
+        let bars = Image::color_bars(16, 2);
+        let bars = bars.as_data_ref();
+        assert_eq!(bars.pixel(1, 1), Some([255; 4]));
+        assert_eq!(bars.pixel(2, 0), Some([255, 255, 0, 255]));
+        assert_eq!(bars.pixel(15, 1), Some([0, 0, 0, 255]));
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Canonical test vectors for the semantics of the vocabulary types.
+//!
+//! Renderers can check that they implement gradients, blend modes and extend modes
+//! as documented by asserting these cases directly, without golden images. Colors
+//! are non-premultiplied sRGB components, and computed colors should be compared
+//! with a tolerance, such as [`TOLERANCE`] for floating point pipelines, or one
+//! step of the channel depth for fixed point pipelines.
+
+#[cfg(feature = "gradient")]
+use crate::{Color, ColorStop, Gradient};
+use crate::{Extend, Mix};
+#[cfg(feature = "gradient")]
+use color::{ColorSpaceTag, DynamicColor};
+#[cfg(feature = "gradient")]
+use core::f32::consts::FRAC_1_SQRT_2;
+
+/// Suggested tolerance for comparing colors computed with `f32` arithmetic.
+pub const TOLERANCE: f32 = 1e-4;
+
+/// A color stop of a [gradient case](GradientCase).
+#[cfg(feature = "gradient")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TestStop {
+    /// Normalized offset of the stop.
+    pub offset: f32,
+    /// Non-premultiplied sRGB color of the stop.
+    pub color: [f32; 4],
+    /// Offset of the interpolation hint between this stop and the next one.
+    pub hint: Option<f32>,
+}
+
+/// A gradient with the colors expected when sampling it.
+#[cfg(feature = "gradient")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GradientCase {
+    /// Short description of the behavior tested by the case.
+    pub name: &'static str,
+    /// Color stops of the gradient.
+    pub stops: &'static [TestStop],
+    /// Color space in which the colors are interpolated.
+    pub interpolation_cs: ColorSpaceTag,
+    /// Pairs of offsets along the gradient and the non-premultiplied sRGB colors
+    /// expected there.
+    pub samples: &'static [(f32, [f32; 4])],
+}
+
+#[cfg(feature = "gradient")]
+impl GradientCase {
+    /// Returns the gradient of this case, as a linear gradient from `(0, 0)` to
+    /// `(1, 0)`, so that the offset of a sample is also its x coordinate.
+    #[must_use]
+    pub fn gradient(&self) -> Gradient {
+        let mut gradient = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_interpolation_cs(self.interpolation_cs);
+        for stop in self.stops {
+            gradient.stops.push(ColorStop {
+                offset: stop.offset,
+                color: DynamicColor::from_alpha_color(Color::new(stop.color)),
+                hint: stop.hint,
+            });
+        }
+        gradient
+    }
+}
+
+#[cfg(feature = "gradient")]
+const BLACK_TO_WHITE: &[TestStop] = &[
+    TestStop {
+        offset: 0.0,
+        color: [0.0, 0.0, 0.0, 1.0],
+        hint: None,
+    },
+    TestStop {
+        offset: 1.0,
+        color: [1.0, 1.0, 1.0, 1.0],
+        hint: None,
+    },
+];
+
+/// Gradient cases, covering interpolation color spaces, premultiplied alpha and
+/// interpolation hints.
+#[cfg(feature = "gradient")]
+pub const GRADIENT_CASES: &[GradientCase] = &[
+    GradientCase {
+        name: "sRGB interpolation is linear in the encoded components",
+        stops: BLACK_TO_WHITE,
+        interpolation_cs: ColorSpaceTag::Srgb,
+        samples: &[
+            (0.0, [0.0, 0.0, 0.0, 1.0]),
+            (0.25, [0.25, 0.25, 0.25, 1.0]),
+            (0.5, [0.5, 0.5, 0.5, 1.0]),
+            (1.0, [1.0, 1.0, 1.0, 1.0]),
+        ],
+    },
+    GradientCase {
+        name: "linear sRGB interpolation is linear in light intensity",
+        stops: BLACK_TO_WHITE,
+        interpolation_cs: ColorSpaceTag::LinearSrgb,
+        samples: &[
+            (0.25, [0.537_099, 0.537_099, 0.537_099, 1.0]),
+            (0.5, [0.735_357, 0.735_357, 0.735_357, 1.0]),
+        ],
+    },
+    GradientCase {
+        name: "Oklab interpolation is linear in perceptual lightness",
+        stops: BLACK_TO_WHITE,
+        interpolation_cs: ColorSpaceTag::Oklab,
+        samples: &[
+            (0.25, [0.131_499, 0.131_499, 0.131_499, 1.0]),
+            (0.5, [0.388_573, 0.388_573, 0.388_573, 1.0]),
+        ],
+    },
+    GradientCase {
+        name: "colors are interpolated with premultiplied alpha",
+        stops: &[
+            TestStop {
+                offset: 0.0,
+                color: [1.0, 0.0, 0.0, 1.0],
+                hint: None,
+            },
+            TestStop {
+                offset: 1.0,
+                color: [0.0, 0.0, 1.0, 0.0],
+                hint: None,
+            },
+        ],
+        interpolation_cs: ColorSpaceTag::Srgb,
+        samples: &[(0.5, [1.0, 0.0, 0.0, 0.5]), (0.75, [1.0, 0.0, 0.0, 0.25])],
+    },
+    GradientCase {
+        name: "the color is halfway between the stops at the hint",
+        stops: &[
+            TestStop {
+                offset: 0.0,
+                color: [0.0, 0.0, 0.0, 1.0],
+                hint: Some(0.25),
+            },
+            TestStop {
+                offset: 1.0,
+                color: [1.0, 1.0, 1.0, 1.0],
+                hint: None,
+            },
+        ],
+        interpolation_cs: ColorSpaceTag::Srgb,
+        samples: &[
+            (0.25, [0.5, 0.5, 0.5, 1.0]),
+            (0.5, [FRAC_1_SQRT_2, FRAC_1_SQRT_2, FRAC_1_SQRT_2, 1.0]),
+        ],
+    },
+    GradientCase {
+        name: "the color is constant between stops at the same offset",
+        stops: &[
+            TestStop {
+                offset: 0.0,
+                color: [1.0, 0.0, 0.0, 1.0],
+                hint: None,
+            },
+            TestStop {
+                offset: 0.5,
+                color: [1.0, 0.0, 0.0, 1.0],
+                hint: None,
+            },
+            TestStop {
+                offset: 0.5,
+                color: [0.0, 0.0, 1.0, 1.0],
+                hint: None,
+            },
+            TestStop {
+                offset: 1.0,
+                color: [0.0, 0.0, 1.0, 1.0],
+                hint: None,
+            },
+        ],
+        interpolation_cs: ColorSpaceTag::Srgb,
+        samples: &[(0.25, [1.0, 0.0, 0.0, 1.0]), (0.75, [0.0, 0.0, 1.0, 1.0])],
+    },
+];
+
+/// A separable blend mode applied to opaque colors.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BlendCase {
+    /// The color mixing function.
+    pub mix: Mix,
+    /// Color of the backdrop.
+    pub backdrop: [f32; 3],
+    /// Color of the source.
+    pub source: [f32; 3],
+    /// Expected result of mixing the source with the backdrop.
+    pub expected: [f32; 3],
+}
+
+const fn blend(mix: Mix, backdrop: [f32; 3], source: [f32; 3], expected: [f32; 3]) -> BlendCase {
+    BlendCase {
+        mix,
+        backdrop,
+        source,
+        expected,
+    }
+}
+
+const BACKDROP: [f32; 3] = [0.25, 0.5, 0.75];
+const SOURCE: [f32; 3] = [0.75, 0.5, 0.25];
+
+/// Blend cases for the separable blend modes, following the [Compositing and
+/// Blending] specification.
+///
+/// [Compositing and Blending]: https://drafts.fxtf.org/compositing/#blending
+pub const BLEND_CASES: &[BlendCase] = &[
+    blend(Mix::Normal, BACKDROP, SOURCE, [0.75, 0.5, 0.25]),
+    blend(Mix::Multiply, BACKDROP, SOURCE, [0.1875, 0.25, 0.1875]),
+    blend(Mix::Screen, BACKDROP, SOURCE, [0.8125, 0.75, 0.8125]),
+    blend(Mix::Overlay, BACKDROP, SOURCE, [0.375, 0.5, 0.625]),
+    blend(Mix::Darken, BACKDROP, SOURCE, [0.25, 0.5, 0.25]),
+    blend(Mix::Lighten, BACKDROP, SOURCE, [0.75, 0.5, 0.75]),
+    blend(
+        Mix::ColorDodge,
+        [0.25, 0.5, 0.0],
+        [0.5, 0.75, 0.5],
+        [0.5, 1.0, 0.0],
+    ),
+    blend(
+        Mix::ColorBurn,
+        [0.75, 0.5, 1.0],
+        [0.5, 0.75, 0.0],
+        [0.5, 0.333_333, 1.0],
+    ),
+    blend(Mix::HardLight, BACKDROP, SOURCE, [0.625, 0.5, 0.375]),
+    blend(Mix::SoftLight, BACKDROP, SOURCE, [0.375, 0.5, 0.656_25]),
+    blend(Mix::Difference, BACKDROP, SOURCE, [0.5, 0.0, 0.5]),
+    blend(Mix::Exclusion, BACKDROP, SOURCE, [0.625, 0.5, 0.625]),
+];
+
+/// A texel index and the index it resolves to with an extend mode.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ExtendCase {
+    /// The extend mode.
+    pub extend: Extend,
+    /// Number of texels along the axis.
+    pub size: u32,
+    /// Texel index, which may lie outside of the image.
+    pub index: i64,
+    /// The index of the texel which is sampled, or `None` for a texel in the border of
+    /// [`Extend::Border`], which has the border color.
+    pub expected: Option<u32>,
+}
+
+const fn extend(extend: Extend, index: i64, expected: Option<u32>) -> ExtendCase {
+    ExtendCase {
+        extend,
+        size: 4,
+        index,
+        expected,
+    }
+}
+
+/// Extend cases for an axis of four texels.
+pub const EXTEND_CASES: &[ExtendCase] = &[
+    extend(Extend::Pad, -5, Some(0)),
+    extend(Extend::Pad, 2, Some(2)),
+    extend(Extend::Pad, 9, Some(3)),
+    extend(Extend::Repeat, -1, Some(3)),
+    extend(Extend::Repeat, -5, Some(3)),
+    extend(Extend::Repeat, 4, Some(0)),
+    extend(Extend::Repeat, 9, Some(1)),
+    extend(Extend::Reflect, -1, Some(0)),
+    extend(Extend::Reflect, -4, Some(3)),
+    extend(Extend::Reflect, 4, Some(3)),
+    extend(Extend::Reflect, 7, Some(0)),
+    extend(Extend::Reflect, 8, Some(0)),
+    extend(Extend::Reflect, 13, Some(2)),
+    extend(Extend::Border, -1, None),
+    extend(Extend::Border, 2, Some(2)),
+    extend(Extend::Border, 9, None),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{BLEND_CASES, EXTEND_CASES, TOLERANCE};
+    use crate::Mix;
+
+    fn assert_close(actual: &[f32], expected: &[f32], name: &str) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() < TOLERANCE,
+                "{name}: {actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn extend_cases() {
+        for case in EXTEND_CASES {
+            assert_eq!(
+                case.extend.resolve_texel_or_border(case.index, case.size),
+                case.expected,
+                "{case:?}"
+            );
+        }
+    }
+
+    /// Independent implementation of the separable blend modes, to check the cases.
+    fn mix(mix: Mix, b: f32, s: f32) -> f32 {
+        let hard_light = |lower: f32, upper: f32| {
+            if upper <= 0.5 {
+                lower * 2.0 * upper
+            } else {
+                let screen = 2.0 * upper - 1.0;
+                lower + screen - lower * screen
+            }
+        };
+        match mix {
+            Mix::Normal => s,
+            Mix::Multiply => b * s,
+            Mix::Screen => b + s - b * s,
+            Mix::Overlay => hard_light(s, b),
+            Mix::Darken => b.min(s),
+            Mix::Lighten => b.max(s),
+            Mix::ColorDodge if b == 0.0 => 0.0,
+            Mix::ColorDodge if s == 1.0 => 1.0,
+            Mix::ColorDodge => (b / (1.0 - s)).min(1.0),
+            Mix::ColorBurn if b == 1.0 => 1.0,
+            Mix::ColorBurn if s == 0.0 => 0.0,
+            Mix::ColorBurn => 1.0 - ((1.0 - b) / s).min(1.0),
+            Mix::HardLight => hard_light(b, s),
+            Mix::SoftLight if s <= 0.5 => b - (1.0 - 2.0 * s) * b * (1.0 - b),
+            Mix::SoftLight => {
+                let d = if b <= 0.25 {
+                    ((16.0 * b - 12.0) * b + 4.0) * b
+                } else {
+                    b.sqrt()
+                };
+                b + (2.0 * s - 1.0) * (d - b)
+            }
+            Mix::Difference => (b - s).abs(),
+            Mix::Exclusion => b + s - 2.0 * b * s,
+            _ => unreachable!("only separable modes have cases"),
+        }
+    }
+
+    #[test]
+    fn blend_cases() {
+        for case in BLEND_CASES {
+            let actual: Vec<f32> = (0..3)
+                .map(|i| mix(case.mix, case.backdrop[i], case.source[i]))
+                .collect();
+            assert_close(&actual, &case.expected, case.mix.name());
+        }
+    }
+
+    /// Checks the gradient cases against the interpolation of the color crate.
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn gradient_cases() {
+        use super::GRADIENT_CASES;
+        use color::{HueDirection, Srgb};
+
+        for case in GRADIENT_CASES {
+            let gradient = case.gradient();
+            for &(t, expected) in case.samples {
+                let segment = gradient
+                    .stops
+                    .windows(2)
+                    .rev()
+                    .find(|pair| pair[0].offset <= t)
+                    .unwrap();
+                let weight = segment[0].interpolation_weight(&segment[1], t);
+                let color = segment[0]
+                    .color
+                    .interpolate(
+                        segment[1].color,
+                        case.interpolation_cs,
+                        HueDirection::default(),
+                    )
+                    .eval(weight)
+                    .to_alpha_color::<Srgb>();
+                assert_close(&color.components, &expected, case.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::fmt;
+
+extern crate alloc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// A theme, for choosing a variant of a [`Themed`] value.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub enum Theme {
+    /// The light theme.
+    #[default]
+    Light,
+    /// The dark theme.
+    Dark,
+    /// A theme with the given name, such as a high contrast theme.
+    Named(Arc<str>),
+}
+
+impl Theme {
+    /// Creates the theme with the given name.
+    #[must_use]
+    pub fn named(name: &str) -> Self {
+        Self::Named(name.into())
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Light => f.write_str("light"),
+            Self::Dark => f.write_str("dark"),
+            Self::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+/// A value, such as a brush or a color, with a variant for each [`Theme`].
+///
+/// Every value has a light and a dark variant, and can have variants for named
+/// themes. Named themes without a variant use the light variant.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Themed<T> {
+    light: T,
+    dark: T,
+    named: Vec<(Arc<str>, T)>,
+}
+
+impl<T> Themed<T> {
+    /// Creates a value with the given light and dark variants.
+    #[must_use]
+    pub fn new(light: T, dark: T) -> Self {
+        Self {
+            light,
+            dark,
+            named: Vec::new(),
+        }
+    }
+
+    /// Creates a value which is the same in every theme.
+    #[must_use]
+    pub fn uniform(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(value.clone(), value)
+    }
+
+    /// Returns the value with the variant for the named theme set to `value`.
+    #[must_use]
+    pub fn with_named(mut self, name: &str, value: T) -> Self {
+        self.set(&Theme::named(name), value);
+        self
+    }
+
+    /// Sets the variant for the theme.
+    pub fn set(&mut self, theme: &Theme, value: T) {
+        match theme {
+            Theme::Light => self.light = value,
+            Theme::Dark => self.dark = value,
+            Theme::Named(name) => match self.named.iter_mut().find(|(n, _)| n == name) {
+                Some((_, variant)) => *variant = value,
+                None => self.named.push((name.clone(), value)),
+            },
+        }
+    }
+
+    /// Returns the variant for the theme.
+    #[must_use]
+    pub fn resolve(&self, theme: &Theme) -> &T {
+        match theme {
+            Theme::Light => &self.light,
+            Theme::Dark => &self.dark,
+            Theme::Named(name) => self
+                .named
+                .iter()
+                .find(|(n, _)| n == name)
+                .map_or(&self.light, |(_, variant)| variant),
+        }
+    }
+
+    /// Returns the value with `f` applied to every variant.
+    #[must_use]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Themed<U> {
+        Themed {
+            light: f(self.light),
+            dark: f(self.dark),
+            named: self
+                .named
+                .into_iter()
+                .map(|(name, value)| (name, f(value)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Theme, Themed};
+    use crate::{Brush, Color};
+
+    #[test]
+    fn themed() {
+        let brush = Themed::new(Brush::from(Color::BLACK), Brush::from(Color::WHITE))
+            .with_named("high-contrast", Brush::from(Color::from_rgb8(255, 255, 0)));
+        assert_eq!(brush.resolve(&Theme::Light), &Brush::from(Color::BLACK));
+        assert_eq!(brush.resolve(&Theme::Dark), &Brush::from(Color::WHITE));
+        assert_eq!(
+            brush.resolve(&Theme::named("high-contrast")),
+            &Brush::from(Color::from_rgb8(255, 255, 0))
+        );
+        // Unknown themes use the light variant.
+        assert_eq!(
+            brush.resolve(&Theme::named("sepia")),
+            &Brush::from(Color::BLACK)
+        );
+
+        let mut opacity = Themed::uniform(1.0_f32).map(|alpha| alpha * 0.5);
+        opacity.set(&Theme::named("dim"), 0.25);
+        opacity.set(&Theme::named("dim"), 0.2);
+        assert_eq!(*opacity.resolve(&Theme::Dark), 0.5);
+        assert_eq!(*opacity.resolve(&Theme::named("dim")), 0.2);
+        assert_eq!(Theme::named("dim").to_string(), "dim");
+    }
+}
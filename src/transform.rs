@@ -0,0 +1,138 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::f64::consts::FRAC_PI_2;
+
+use kurbo::{Affine, Vec2};
+
+#[cfg(not(feature = "std"))]
+use crate::floatfuncs::FloatFuncs as _;
+
+/// Decomposition of an affine transform, such as a brush transform, into
+/// translation, rotation, scale and skew.
+///
+/// The transform is `translate * rotate * scale * skew`, where the skew is
+/// applied first and shears along the x axis. A reflection is represented by a
+/// negative y scale.
+///
+/// Renderers whose samplers only support some transforms, such as those without
+/// skew or rotation, can use this to detect the cases they can draw directly,
+/// and only fall back to a more general path when necessary.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AffineDecomposition {
+    /// The translation, applied last.
+    pub translation: Vec2,
+    /// The rotation in radians, in the range `(-π, π]`.
+    pub rotation: f64,
+    /// The scale along each axis, before the rotation. The x scale is positive.
+    pub scale: Vec2,
+    /// The skew factor, where a point `(x, y)` is sheared to `(x + skew * y, y)`.
+    pub skew: f64,
+}
+
+impl AffineDecomposition {
+    /// Decomposes the transform, or returns `None` if it isn't invertible.
+    #[must_use]
+    pub fn new(transform: Affine) -> Option<Self> {
+        let [a, b, c, d, e, f] = transform.as_coeffs();
+        let det = transform.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let x_axis = Vec2::new(a, b);
+        let scale_x = x_axis.hypot();
+        let scale_y = det / scale_x;
+        let skew = (a * c + b * d) / (scale_x * scale_x);
+        Some(Self {
+            translation: Vec2::new(e, f),
+            rotation: x_axis.atan2(),
+            scale: Vec2::new(scale_x, scale_y),
+            skew,
+        })
+    }
+
+    /// Returns the transform composed from the parts.
+    #[must_use]
+    pub fn to_affine(&self) -> Affine {
+        Affine::translate(self.translation)
+            * Affine::rotate(self.rotation)
+            * Affine::scale_non_uniform(self.scale.x, self.scale.y)
+            * Affine::skew(self.skew, 0.0)
+    }
+
+    /// Returns `true` if the skew is within `tolerance` of zero.
+    #[must_use]
+    pub fn is_skew_free(&self, tolerance: f64) -> bool {
+        self.skew.abs() <= tolerance
+    }
+
+    /// Returns `true` if the transform has no skew and the same scale along both
+    /// axes (ignoring reflection), within `tolerance`, so that it preserves circles.
+    #[must_use]
+    pub fn is_similarity(&self, tolerance: f64) -> bool {
+        self.is_skew_free(tolerance) && (self.scale.x - self.scale.y.abs()).abs() <= tolerance
+    }
+
+    /// Returns `true` if the transform has no skew and its rotation is within
+    /// `tolerance` of a multiple of a quarter turn, so that it maps axis-aligned
+    /// rectangles to axis-aligned rectangles.
+    #[must_use]
+    pub fn is_axis_aligned(&self, tolerance: f64) -> bool {
+        let quarter_turns = self.rotation / FRAC_PI_2;
+        self.is_skew_free(tolerance)
+            && (quarter_turns - quarter_turns.round()).abs() * FRAC_PI_2 <= tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AffineDecomposition;
+    use kurbo::{Affine, Vec2};
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn assert_round_trip(transform: Affine) -> AffineDecomposition {
+        let parts = AffineDecomposition::new(transform).unwrap();
+        let composed = parts.to_affine().as_coeffs();
+        for (actual, expected) in composed.iter().zip(transform.as_coeffs()) {
+            assert!((actual - expected).abs() < TOLERANCE, "{transform:?}");
+        }
+        parts
+    }
+
+    #[test]
+    fn decompose() {
+        let transform = Affine::translate((3.0, -4.0))
+            * Affine::rotate(0.5)
+            * Affine::scale_non_uniform(2.0, 3.0)
+            * Affine::skew(0.25, 0.0);
+        let parts = assert_round_trip(transform);
+        assert!((parts.rotation - 0.5).abs() < TOLERANCE);
+        assert!((parts.scale - Vec2::new(2.0, 3.0)).hypot() < TOLERANCE);
+        assert!((parts.skew - 0.25).abs() < TOLERANCE);
+        assert_eq!(parts.translation, Vec2::new(3.0, -4.0));
+        assert!(!parts.is_skew_free(TOLERANCE));
+
+        let rotated =
+            assert_round_trip(Affine::rotate(core::f64::consts::FRAC_PI_2) * Affine::scale(2.0));
+        assert!(rotated.is_similarity(TOLERANCE));
+        assert!(rotated.is_axis_aligned(TOLERANCE));
+
+        let reflected = assert_round_trip(Affine::FLIP_Y * Affine::scale_non_uniform(1.0, 2.0));
+        assert!(reflected.scale.y < 0.0);
+        assert!(reflected.is_axis_aligned(TOLERANCE));
+        assert!(!reflected.is_similarity(TOLERANCE));
+
+        // A skew in y is a rotation and scale combined with a skew in x.
+        let skewed = assert_round_trip(Affine::skew(0.0, 0.5));
+        assert!(!skewed.is_skew_free(TOLERANCE));
+        assert!(!AffineDecomposition::new(Affine::rotate(0.3))
+            .unwrap()
+            .is_axis_aligned(TOLERANCE));
+
+        assert_eq!(
+            AffineDecomposition::new(Affine::scale_non_uniform(1.0, 0.0)),
+            None
+        );
+    }
+}
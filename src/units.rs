@@ -0,0 +1,224 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+#[cfg(feature = "kurbo")]
+use kurbo::Affine;
+
+/// A length in physical pixels.
+///
+/// Physical pixels are the units of the render target. Lengths which are passed to a
+/// renderer, such as stroke widths in a scene that is not otherwise scaled, are usually
+/// in physical pixels.
+///
+/// Lengths are stored as `f64` to match the geometry types in `kurbo`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Px(pub f64);
+
+/// A length in device-independent pixels.
+///
+/// Device-independent pixels (also known as logical pixels) are the units that
+/// layout usually operates in. They are converted to [physical pixels](Px) by
+/// multiplying with a [`ScaleFactor`].
+///
+/// Lengths are stored as `f64` to match the geometry types in `kurbo`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Dip(pub f64);
+
+/// The number of [physical pixels](Px) per [device-independent pixel](Dip).
+///
+/// This is typically provided by the windowing system and can differ between
+/// monitors, so it can change over the lifetime of a window.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ScaleFactor(pub f64);
+
+impl Px {
+    /// Converts to device-independent pixels using the given scale factor.
+    #[must_use]
+    pub fn to_dip(self, scale: ScaleFactor) -> Dip {
+        Dip(self.0 / scale.0)
+    }
+}
+
+impl Dip {
+    /// Converts to physical pixels using the given scale factor.
+    #[must_use]
+    pub fn to_px(self, scale: ScaleFactor) -> Px {
+        Px(self.0 * scale.0)
+    }
+}
+
+impl ScaleFactor {
+    /// The scale factor where one device-independent pixel is one physical pixel.
+    pub const IDENTITY: Self = Self(1.0);
+
+    /// Creates a new scale factor.
+    #[must_use]
+    pub const fn new(scale: f64) -> Self {
+        Self(scale)
+    }
+
+    /// Returns the scale factor which converts in the opposite direction.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self(1.0 / self.0)
+    }
+
+    /// Returns the transform which maps device-independent coordinates to
+    /// physical coordinates.
+    #[cfg(feature = "kurbo")]
+    #[must_use]
+    pub fn to_affine(self) -> Affine {
+        Affine::scale(self.0)
+    }
+}
+
+impl Default for ScaleFactor {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul<ScaleFactor> for Dip {
+    type Output = Px;
+
+    fn mul(self, scale: ScaleFactor) -> Px {
+        self.to_px(scale)
+    }
+}
+
+impl Div<ScaleFactor> for Px {
+    type Output = Dip;
+
+    fn div(self, scale: ScaleFactor) -> Dip {
+        self.to_dip(scale)
+    }
+}
+
+/// Implements the arithmetic operators which preserve the unit of a length.
+macro_rules! impl_length_ops {
+    ($ty:ident) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl AddAssign for $ty {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl SubAssign for $ty {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $ty {
+            type Output = Self;
+
+            fn mul(self, factor: f64) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl Div<f64> for $ty {
+            type Output = Self;
+
+            fn div(self, divisor: f64) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+    };
+}
+
+impl_length_ops!(Px);
+impl_length_ops!(Dip);
+
+#[cfg(test)]
+mod tests {
+    use super::{Dip, Px, ScaleFactor};
+
+    #[test]
+    fn conversions() {
+        let scale = ScaleFactor::new(2.0);
+        assert_eq!(Dip(3.0).to_px(scale), Px(6.0));
+        assert_eq!(Px(6.0).to_dip(scale), Dip(3.0));
+        assert_eq!(Dip(3.0) * scale, Px(6.0));
+        assert_eq!(Px(6.0) / scale, Dip(3.0));
+        // Lengths round-trip through scale factors which are powers of two.
+        for exact in [0.5, 1.0, 4.0].map(ScaleFactor::new) {
+            let dip = Dip(12.5);
+            assert_eq!(dip.to_px(exact).to_dip(exact), dip);
+        }
+        let fractional = ScaleFactor::new(1.25);
+        assert!((Px(7.0).to_dip(fractional).to_px(fractional).0 - 7.0).abs() < 1e-12);
+        assert_eq!(ScaleFactor::default(), ScaleFactor::IDENTITY);
+        assert_eq!(Dip(5.0).to_px(ScaleFactor::IDENTITY), Px(5.0));
+    }
+
+    #[test]
+    fn inverse() {
+        let scale = ScaleFactor::new(4.0);
+        assert_eq!(scale.inverse(), ScaleFactor::new(0.25));
+        assert_eq!(scale.inverse().inverse(), scale);
+        // Converting to physical pixels with the inverse converts back.
+        assert_eq!(Dip(8.0).to_px(scale.inverse()), Px(2.0));
+    }
+
+    #[test]
+    fn length_ops() {
+        assert_eq!(Px(1.5) + Px(2.0), Px(3.5));
+        assert_eq!(Dip(1.5) - Dip(2.0), Dip(-0.5));
+        assert_eq!(-Px(2.0), Px(-2.0));
+        assert_eq!(Dip(3.0) * 2.0, Dip(6.0));
+        assert_eq!(Px(3.0) / 2.0, Px(1.5));
+        let mut length = Dip(1.0);
+        length += Dip(2.0);
+        assert_eq!(length, Dip(3.0));
+        length -= Dip(0.5);
+        assert_eq!(length, Dip(2.5));
+        assert!(Px(1.0) < Px(2.0));
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn to_affine() {
+        use kurbo::{Affine, Point};
+
+        let scale = ScaleFactor::new(1.5);
+        assert_eq!(scale.to_affine(), Affine::scale(1.5));
+        assert_eq!(
+            scale.to_affine() * Point::new(2.0, 4.0),
+            Point::new(3.0, 6.0)
+        );
+        assert_eq!(ScaleFactor::IDENTITY.to_affine(), Affine::IDENTITY);
+    }
+}
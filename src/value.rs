@@ -0,0 +1,115 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Conversions to and from self-describing values, for scripting and bindings.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Error, Value};
+
+use crate::{
+    BlendMode, Brush, Compose, Extend, Fill, GlyphRenderingOptions, Image, ImageFormat,
+    ImageQuality, ImageTiling, MinificationQuality, Mix, NoiseBrush, ProceduralBrush, TileMode,
+};
+#[cfg(feature = "gradient")]
+use crate::{ColorStop, ColorStops, Gradient, GradientKind};
+#[cfg(feature = "kurbo")]
+use crate::{SmoothRoundedRect, Style};
+
+mod sealed {
+    #[expect(unnameable_types, reason = "the trait is sealed on purpose")]
+    pub trait Sealed {}
+}
+
+/// Conversion of style types to and from [`serde_json::Value`].
+///
+/// This gives scripting and bindings layers, such as for Python or Lua, structured
+/// access to styles as nested maps, lists, numbers and strings, without needing a
+/// serde format for each language. The structure of the values is that of the
+/// `serde` implementations of the types.
+pub trait ValueConvert: Serialize + DeserializeOwned + sealed::Sealed {
+    /// Converts the value to a [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value can't be represented, which doesn't happen
+    /// for the types in this crate.
+    fn to_value(&self) -> Result<Value, Error> {
+        serde_json::to_value(self)
+    }
+
+    /// Converts a [`Value`] to this type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value doesn't have the structure of this type.
+    fn from_value(value: Value) -> Result<Self, Error> {
+        serde_json::from_value(value)
+    }
+}
+
+macro_rules! impl_value_convert {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+            impl ValueConvert for $ty {}
+        )*
+    };
+}
+
+impl_value_convert!(
+    BlendMode,
+    Brush,
+    Compose,
+    Extend,
+    Fill,
+    GlyphRenderingOptions,
+    Image,
+    ImageFormat,
+    ImageQuality,
+    ImageTiling,
+    MinificationQuality,
+    Mix,
+    NoiseBrush,
+    ProceduralBrush,
+    TileMode,
+);
+
+#[cfg(feature = "gradient")]
+impl_value_convert!(ColorStop, ColorStops, Gradient, GradientKind);
+
+#[cfg(feature = "kurbo")]
+impl_value_convert!(SmoothRoundedRect, Style);
+
+#[cfg(test)]
+mod tests {
+    use super::ValueConvert;
+    use crate::{BlendMode, Brush, Color, Compose, Mix};
+    use serde_json::json;
+
+    #[test]
+    fn round_trip() {
+        let mode = BlendMode::new(Mix::Multiply, Compose::SrcOver);
+        let value = mode.to_value().unwrap();
+        assert_eq!(value, json!({ "mix": "Multiply", "compose": "SrcOver" }));
+        assert_eq!(BlendMode::from_value(value).unwrap(), mode);
+
+        let brush = Brush::from(Color::new([1.0, 0.5, 0.25, 1.0]));
+        assert_eq!(Brush::from_value(brush.to_value().unwrap()).unwrap(), brush);
+
+        assert!(BlendMode::from_value(json!({ "mix": "Sparkle" })).is_err());
+    }
+
+    #[cfg(feature = "kurbo")]
+    #[test]
+    fn styles() {
+        use crate::Style;
+
+        let value = Style::stroke(2.0).to_value().unwrap();
+        assert_eq!(value["Stroke"]["width"], json!(2.0));
+        let Style::Stroke(stroke) = Style::from_value(value).unwrap() else {
+            panic!("expected a stroke");
+        };
+        assert_eq!(stroke.width, 2.0);
+    }
+}
@@ -0,0 +1,428 @@
+// Copyright 2025 the Peniko Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Conversions between peniko types and the concepts of the web canvas API.
+
+#[cfg(feature = "gradient")]
+use core::f32::consts::TAU;
+use core::fmt;
+
+extern crate alloc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use js_sys::{Array, Object, Reflect, Uint8ClampedArray};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::{Blob, Brush, Color, Image, ImageFormat};
+#[cfg(feature = "gradient")]
+use crate::{Extend, Gradient, GradientKind};
+#[cfg(feature = "gradient")]
+use color::{ColorSpaceTag, DynamicColor};
+
+/// Errors which can occur when converting a web value to a peniko type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WebError {
+    /// The CSS color string couldn't be parsed.
+    InvalidColor(color::ParseError),
+    /// The value isn't a well-formed `ImageData` object.
+    InvalidImageData,
+}
+
+impl fmt::Display for WebError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidColor(err) => write!(f, "invalid CSS color: {err}"),
+            Self::InvalidImageData => f.write_str("value is not a valid `ImageData` object"),
+        }
+    }
+}
+
+impl std::error::Error for WebError {}
+
+impl From<WebError> for JsValue {
+    fn from(err: WebError) -> Self {
+        js_sys::Error::new(&err.to_string()).into()
+    }
+}
+
+/// The kind and geometry of a canvas gradient, matching the arguments of the
+/// canvas gradient constructors.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CanvasGradientKind {
+    /// Arguments of `createLinearGradient`.
+    Linear {
+        /// The x coordinate of the start point.
+        x0: f64,
+        /// The y coordinate of the start point.
+        y0: f64,
+        /// The x coordinate of the end point.
+        x1: f64,
+        /// The y coordinate of the end point.
+        y1: f64,
+    },
+    /// Arguments of `createRadialGradient`.
+    Radial {
+        /// The x coordinate of the center of the start circle.
+        x0: f64,
+        /// The y coordinate of the center of the start circle.
+        y0: f64,
+        /// The radius of the start circle.
+        r0: f64,
+        /// The x coordinate of the center of the end circle.
+        x1: f64,
+        /// The y coordinate of the center of the end circle.
+        y1: f64,
+        /// The radius of the end circle.
+        r1: f64,
+    },
+    /// Arguments of `createConicGradient`.
+    Conic {
+        /// The angle at which to begin the gradient, in radians.
+        start_angle: f64,
+        /// The x coordinate of the center.
+        x: f64,
+        /// The y coordinate of the center.
+        y: f64,
+    },
+}
+
+/// Description of a canvas gradient, which can be passed to JavaScript to create a
+/// `CanvasGradient`.
+///
+/// This is created by [`Gradient::to_canvas_gradient`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct CanvasGradientDescriptor {
+    /// The kind and geometry of the gradient.
+    pub kind: CanvasGradientKind,
+    /// The offsets and CSS colors to pass to `addColorStop`.
+    pub stops: Vec<(f64, String)>,
+}
+
+impl CanvasGradientDescriptor {
+    /// Converts the descriptor to a JavaScript object.
+    ///
+    /// The object has a `type` property of `"linear"`, `"radial"` or `"conic"`, a
+    /// property for each argument of the corresponding constructor, and a `stops`
+    /// property containing an array of `[offset, color]` pairs.
+    #[must_use]
+    pub fn to_js(&self) -> JsValue {
+        let object = Object::new();
+        let set = |key: &str, value: JsValue| {
+            // Setting a property on a plain object can't fail.
+            Reflect::set(&object, &key.into(), &value).ok();
+        };
+        match self.kind {
+            CanvasGradientKind::Linear { x0, y0, x1, y1 } => {
+                set("type", "linear".into());
+                set("x0", x0.into());
+                set("y0", y0.into());
+                set("x1", x1.into());
+                set("y1", y1.into());
+            }
+            CanvasGradientKind::Radial {
+                x0,
+                y0,
+                r0,
+                x1,
+                y1,
+                r1,
+            } => {
+                set("type", "radial".into());
+                set("x0", x0.into());
+                set("y0", y0.into());
+                set("r0", r0.into());
+                set("x1", x1.into());
+                set("y1", y1.into());
+                set("r1", r1.into());
+            }
+            CanvasGradientKind::Conic { start_angle, x, y } => {
+                set("type", "conic".into());
+                set("startAngle", start_angle.into());
+                set("x", x.into());
+                set("y", y.into());
+            }
+        }
+        let stops = self
+            .stops
+            .iter()
+            .map(|(offset, color)| Array::of2(&(*offset).into(), &color.into()))
+            .collect::<Array>();
+        set("stops", stops.into());
+        object.into()
+    }
+}
+
+/// Returns the CSS representation of an sRGB color, quantized to 8 bits per
+/// channel as used by the canvas.
+fn css_color(color: Color) -> String {
+    color.to_rgba8().to_string()
+}
+
+#[cfg(feature = "gradient")]
+impl Gradient {
+    /// Returns a description of the equivalent canvas gradient.
+    ///
+    /// Stop colors are converted to sRGB, as canvas gradients interpolate in sRGB.
+    /// A sweep which covers less than a full turn is mapped onto the full turn of a
+    /// conic gradient, with the remainder filled with the final color.
+    ///
+    /// Canvas gradients have no [interpolation hints](crate::ColorStop::hint) and
+    /// can't interpolate in other color spaces. Gradients which use either are
+    /// approximated by subdividing each transition into evenly spaced stops, which
+    /// are interpolated with the hints in the
+    /// [interpolation color space](Self::interpolation_cs).
+    ///
+    /// Returns `None` if the extend mode isn't [`Extend::Pad`], as canvas gradients
+    /// don't repeat.
+    #[must_use]
+    pub fn to_canvas_gradient(&self) -> Option<CanvasGradientDescriptor> {
+        if self.extend != Extend::Pad {
+            return None;
+        }
+        let stop = |offset: f32, color: Color| (f64::from(offset), css_color(color));
+        let mut stops = self.canvas_stops();
+        let kind = match self.kind {
+            GradientKind::Linear { start, end } => CanvasGradientKind::Linear {
+                x0: start.x,
+                y0: start.y,
+                x1: end.x,
+                y1: end.y,
+            },
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => CanvasGradientKind::Radial {
+                x0: start_center.x,
+                y0: start_center.y,
+                r0: start_radius.into(),
+                x1: end_center.x,
+                y1: end_center.y,
+                r1: end_radius.into(),
+            },
+            GradientKind::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                let span = ((end_angle - start_angle) / TAU).clamp(0.0, 1.0);
+                for (offset, _) in &mut stops {
+                    *offset *= span;
+                }
+                if let Some(&(_, last)) = stops.last() {
+                    if span < 1.0 {
+                        stops.push((1.0, last));
+                    }
+                }
+                CanvasGradientKind::Conic {
+                    start_angle: start_angle.into(),
+                    x: center.x,
+                    y: center.y,
+                }
+            }
+        };
+        Some(CanvasGradientDescriptor {
+            kind,
+            stops: stops
+                .into_iter()
+                .map(|(offset, color)| stop(offset, color))
+                .collect(),
+        })
+    }
+
+    /// Returns the stops as sRGB colors, subdividing the transitions if the canvas
+    /// can't interpolate them exactly.
+    fn canvas_stops(&self) -> Vec<(f32, Color)> {
+        /// Number of parts each approximated transition is subdivided into.
+        const SUBDIVISIONS: u8 = 16;
+
+        let srgb = |color: DynamicColor| color.to_alpha_color::<color::Srgb>();
+        if self.interpolation_cs == ColorSpaceTag::Srgb
+            && self.stops.iter().all(|stop| stop.hint.is_none())
+        {
+            return self
+                .stops
+                .iter()
+                .map(|stop| (stop.offset, srgb(stop.color)))
+                .collect();
+        }
+        let mut stops = Vec::new();
+        for pair in self.stops.windows(2) {
+            let (start, end) = (&pair[0], &pair[1]);
+            stops.push((start.offset, srgb(start.color)));
+            let span = end.offset - start.offset;
+            if span.is_nan() || span <= 0.0 {
+                continue;
+            }
+            let interpolator =
+                start
+                    .color
+                    .interpolate(end.color, self.interpolation_cs, self.hue_direction);
+            for i in 1..SUBDIVISIONS {
+                let offset = start.offset + span * f32::from(i) / f32::from(SUBDIVISIONS);
+                let color = interpolator.eval(start.interpolation_weight(end, offset));
+                stops.push((offset, srgb(color)));
+            }
+        }
+        stops.extend(
+            self.stops
+                .last()
+                .map(|stop| (stop.offset, srgb(stop.color))),
+        );
+        stops
+    }
+}
+
+impl Brush {
+    /// Parses a brush from a CSS color string, such as those used for canvas fill
+    /// and stroke styles.
+    ///
+    /// The color is converted to sRGB.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WebError::InvalidColor`] if the string isn't a valid CSS color.
+    pub fn from_css_color(css: &str) -> Result<Self, WebError> {
+        let color = color::parse_color(css).map_err(WebError::InvalidColor)?;
+        Ok(Self::Solid(color.to_alpha_color()))
+    }
+
+    /// Returns the CSS color string for a solid brush, or `None` for other brushes.
+    #[must_use]
+    pub fn to_css_color(&self) -> Option<String> {
+        match self {
+            Self::Solid(color) => Some(css_color(*color)),
+            Self::DynamicSolid(color) => Some(css_color(color.to_alpha_color())),
+            _ => None,
+        }
+    }
+}
+
+impl Image {
+    /// Creates an image from a web `ImageData` object, such as one returned by
+    /// `getImageData` on a canvas context.
+    ///
+    /// The pixels are copied from the object.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WebError::InvalidImageData`] if the value doesn't have a numeric
+    /// `width` and `height` and a `data` array of the matching size.
+    pub fn from_web_image_data(image_data: &JsValue) -> Result<Self, WebError> {
+        let get = |key: &str| Reflect::get(image_data, &key.into()).ok();
+        let dimension = |key: &str| {
+            get(key)
+                .and_then(|value| value.as_f64())
+                .filter(|value| value.fract() == 0.0 && (0.0..=f64::from(u32::MAX)).contains(value))
+        };
+        let (Some(width), Some(height)) = (dimension("width"), dimension("height")) else {
+            return Err(WebError::InvalidImageData);
+        };
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "dimensions were checked to be integers in range"
+        )]
+        let (width, height) = (width as u32, height as u32);
+        let data = get("data")
+            .and_then(|data| data.dyn_into::<Uint8ClampedArray>().ok())
+            .ok_or(WebError::InvalidImageData)?
+            .to_vec();
+        if Some(data.len()) != ImageFormat::Rgba8.size_in_bytes(width, height) {
+            return Err(WebError::InvalidImageData);
+        }
+        Ok(Self::new(
+            Blob::from(data),
+            ImageFormat::Rgba8,
+            width,
+            height,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebError;
+    use crate::{Brush, Color};
+
+    #[test]
+    fn css_colors() {
+        let brush = Brush::from_css_color("rebeccapurple").unwrap();
+        assert_eq!(brush.to_css_color().as_deref(), Some("rgb(102, 51, 153)"));
+        assert_eq!(
+            Brush::from(Color::from_rgba8(255, 0, 0, 128))
+                .to_css_color()
+                .as_deref(),
+            Some("rgba(255, 0, 0, 0.5019608)")
+        );
+        assert!(matches!(
+            Brush::from_css_color("not a color"),
+            Err(WebError::InvalidColor(_))
+        ));
+    }
+
+    #[cfg(feature = "gradient")]
+    #[test]
+    fn canvas_gradients() {
+        use super::CanvasGradientKind;
+        use crate::{ColorStop, Extend, Gradient};
+        use color::ColorSpaceTag;
+        use core::f32::consts::PI;
+
+        let linear = Gradient::new_linear((0.0, 0.0), (10.0, 0.0))
+            .with_stops([Color::WHITE, Color::BLACK])
+            .to_canvas_gradient()
+            .unwrap();
+        assert_eq!(
+            linear.kind,
+            CanvasGradientKind::Linear {
+                x0: 0.0,
+                y0: 0.0,
+                x1: 10.0,
+                y1: 0.0
+            }
+        );
+        assert_eq!(
+            linear.stops,
+            [
+                (0.0, "rgb(255, 255, 255)".into()),
+                (1.0, "rgb(0, 0, 0)".into())
+            ]
+        );
+
+        // A half turn sweep fills the rest of the turn with the final color.
+        let sweep = Gradient::new_sweep((1.0, 2.0), 0.0, PI)
+            .with_stops([Color::WHITE, Color::BLACK])
+            .to_canvas_gradient()
+            .unwrap();
+        let offsets = sweep.stops.iter().map(|(t, _)| *t).collect::<Vec<_>>();
+        assert_eq!(offsets, [0.0, 0.5, 1.0]);
+        assert_eq!(sweep.stops[2].1, "rgb(0, 0, 0)");
+
+        // Hints and other interpolation color spaces are approximated with more stops.
+        let hinted = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([
+                ColorStop::from((0.0, Color::WHITE)).with_hint(0.25),
+                ColorStop::from((1.0, Color::BLACK)),
+            ])
+            .to_canvas_gradient()
+            .unwrap();
+        assert_eq!(hinted.stops.len(), 17);
+        assert_eq!(hinted.stops[4], (0.25, "rgb(128, 128, 128)".into()));
+        let oklab = Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_stops([Color::WHITE, Color::BLACK])
+            .with_interpolation_cs(ColorSpaceTag::Oklab)
+            .to_canvas_gradient()
+            .unwrap();
+        assert_eq!(oklab.stops.len(), 17);
+        assert_eq!(oklab.stops[8].0, 0.5);
+        assert_ne!(oklab.stops[8].1, "rgb(128, 128, 128)");
+
+        assert!(Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+            .with_extend(Extend::Repeat)
+            .to_canvas_gradient()
+            .is_none());
+    }
+}